@@ -0,0 +1,447 @@
+use turbo::*;
+use crate::model::multiplayer::Decor;
+
+// Saved arenas kept at most; the level select has room for this many tiles
+pub const MAX_ARENAS: usize = 4;
+
+// Axis-aligned patch of the field: a stretch of river or a bridge over one
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub struct Zone {
+    pub x: f32,
+    pub y: f32,
+    pub w: f32,
+    pub h: f32,
+}
+
+impl Zone {
+    pub fn new(x: f32, y: f32, w: f32, h: f32) -> Self {
+        Self { x, y, w, h }
+    }
+
+    // Spanning two corners in either order
+    pub fn between(a: (f32, f32), b: (f32, f32)) -> Self {
+        Self::new(a.0.min(b.0), a.1.min(b.1), (a.0 - b.0).abs(), (a.1 - b.1).abs())
+    }
+
+    // Edges don't count, so a bridge flush with a river's bank is dry
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        x > self.x && x < self.x + self.w && y > self.y && y < self.y + self.h
+    }
+
+    pub fn center(&self) -> (f32, f32) {
+        (self.x + self.w / 2.0, self.y + self.h / 2.0)
+    }
+
+    // Rivers flow, and bridges cross, along their longer side
+    pub fn is_vertical(&self) -> bool {
+        self.h > self.w
+    }
+
+    // A bridge crosses the river under it, or failing that runs along its longer side
+    pub fn crosses_vertically(&self, water: &[Zone]) -> bool {
+        let (cx, cy) = self.center();
+        match water.iter().find(|z| z.contains(cx, cy)) {
+            Some(river) => !river.is_vertical(),
+            None => self.is_vertical(),
+        }
+    }
+
+    // A bridge's two landings, just off the water either side
+    pub fn landings(&self, water: &[Zone]) -> ((f32, f32), (f32, f32)) {
+        let (cx, cy) = self.center();
+        if self.crosses_vertically(water) {
+            ((cx, self.y - 10.0), (cx, self.y + self.h + 10.0))
+        } else {
+            ((self.x - 10.0, cy), (self.x + self.w + 10.0, cy))
+        }
+    }
+}
+
+pub fn in_any(zones: &[Zone], x: f32, y: f32) -> bool {
+    zones.iter().any(|z| z.contains(x, y))
+}
+
+// River layouts of the built-in levels
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub enum RiverLayout {
+    None,
+    // One river down the middle, two bridges (levels 3 and 4)
+    Straight,
+    // Two rivers crossing in the middle, a bridge on each arm (level 5)
+    Cross,
+}
+
+impl RiverLayout {
+    pub fn water(&self) -> Vec<Zone> {
+        match self {
+            RiverLayout::Cross => vec![Zone::new(236.0, -16.0, 40.0, 320.0), Zone::new(-16.0, 124.0, 544.0, 40.0)],
+            RiverLayout::Straight => vec![Zone::new(236.0, -16.0, 40.0, 320.0)],
+            RiverLayout::None => vec![],
+        }
+    }
+
+    pub fn bridges(&self) -> Vec<Zone> {
+        match self {
+            RiverLayout::Cross => vec![
+                Zone::new(236.0, 50.0, 40.0, 28.0),
+                Zone::new(236.0, 210.0, 40.0, 28.0),
+                Zone::new(100.0, 124.0, 28.0, 40.0),
+                Zone::new(380.0, 124.0, 28.0, 40.0),
+            ],
+            RiverLayout::Straight => vec![Zone::new(236.0, 86.0, 40.0, 28.0), Zone::new(236.0, 186.0, 40.0, 28.0)],
+            RiverLayout::None => vec![],
+        }
+    }
+}
+
+// Which mechanics a multiplayer match plays with. The five levels are presets of these;
+// an arena carries its own, so any mix works.
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub struct ArenaRules {
+    // Level the rules are named after (titles, instructions, the level select)
+    pub level: u32,
+    // Houses laid out on a generated field (per team side when `teams` is on)
+    pub houses: u32,
+    // Houses jump to new spots every 15 seconds
+    pub shuffle: bool,
+    // Bombs go off and wander, wood hurts, snowmen freeze
+    pub hazards: bool,
+    // Bombs and snowmen laid out on a generated field
+    pub bombs: u32,
+    pub snowmen: u32,
+    // Houses belong to teams and move once captured
+    pub teams: bool,
+    // Players leave trails that slow the other teams
+    pub trails: bool,
+    // Seconds between the power house's risky gifts, 0 for no power house
+    pub risky_gift_secs: u32,
+    // A bad risky gift sets the dog on you
+    pub dog: bool,
+    pub river: RiverLayout,
+}
+
+impl ArenaRules {
+    // Presets for the built-in levels 1-5
+    pub fn level(level: u32) -> Self {
+        let level = level.clamp(1, 5);
+        Self {
+            level,
+            houses: match level { 1 => 10, 2 => 14, 5 => 8, _ => 10 },
+            shuffle: level == 2,
+            hazards: level >= 2,
+            bombs: match level { 1 => 0, 2 => 8, _ => 6 },
+            snowmen: if level >= 4 { 5 } else { 0 },
+            teams: level >= 3,
+            trails: level >= 3,
+            risky_gift_secs: match level { 4 => 15, 5 => 8, _ => 0 },
+            dog: level == 5,
+            river: match level { 3 | 4 => RiverLayout::Straight, 5 => RiverLayout::Cross, _ => RiverLayout::None },
+        }
+    }
+
+    pub fn power_house(&self) -> bool {
+        self.risky_gift_secs > 0
+    }
+}
+
+// Seconds between risky gifts the editor offers, "none" first
+const RISKY_GIFT_CHOICES: [u32; 3] = [0, 15, 8];
+const RIVER_CHOICES: [RiverLayout; 3] = [RiverLayout::None, RiverLayout::Straight, RiverLayout::Cross];
+
+// One line of the editor's rules panel: a level preset to start from, then
+// each mechanic on its own so they can be mixed freely
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub enum ArenaRule {
+    Preset,
+    Teams,
+    Trails,
+    Hazards,
+    Shuffle,
+    RiskyGifts,
+    Dog,
+    River,
+}
+
+impl ArenaRule {
+    pub const ALL: [ArenaRule; 8] = [
+        ArenaRule::Preset, ArenaRule::Teams, ArenaRule::Trails, ArenaRule::Hazards,
+        ArenaRule::Shuffle, ArenaRule::RiskyGifts, ArenaRule::Dog, ArenaRule::River,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ArenaRule::Preset => "PRESET",
+            ArenaRule::Teams => "TEAMS",
+            ArenaRule::Trails => "TRAILS",
+            ArenaRule::Hazards => "BOMBS GO OFF",
+            ArenaRule::Shuffle => "SHUFFLE HOUSES",
+            ArenaRule::RiskyGifts => "RISKY GIFTS",
+            ArenaRule::Dog => "DOG",
+            ArenaRule::River => "RIVER",
+        }
+    }
+
+    pub fn value(&self, rules: &ArenaRules) -> String {
+        let on_off = |on: bool| if on { "ON" } else { "OFF" }.to_string();
+        match self {
+            ArenaRule::Preset => format!("LEVEL {}", rules.level),
+            ArenaRule::Teams => on_off(rules.teams),
+            ArenaRule::Trails => on_off(rules.trails),
+            ArenaRule::Hazards => on_off(rules.hazards),
+            ArenaRule::Shuffle => on_off(rules.shuffle),
+            ArenaRule::RiskyGifts if rules.risky_gift_secs == 0 => "OFF".to_string(),
+            ArenaRule::RiskyGifts => format!("EVERY {}s", rules.risky_gift_secs),
+            ArenaRule::Dog => on_off(rules.dog),
+            ArenaRule::River => match rules.river {
+                RiverLayout::None => "NONE",
+                RiverLayout::Straight => "STRAIGHT",
+                RiverLayout::Cross => "CROSS",
+            }.to_string(),
+        }
+    }
+}
+
+// The choice `step` places along from `current` (wrapping round)
+fn step_choice<T: Copy + PartialEq>(choices: &[T], current: T, step: i32) -> T {
+    let at = choices.iter().position(|c| *c == current).unwrap_or(0) as i32;
+    choices[(at + step).rem_euclid(choices.len() as i32) as usize]
+}
+
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub struct ArenaHouse {
+    pub x: f32,
+    pub y: f32,
+    // 0 = Neutral, otherwise the owning team
+    pub team: u8,
+    // Power House (only with risky gifts in the rules)
+    pub power: bool,
+}
+
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub struct ArenaObstacle {
+    pub x: f32,
+    pub y: f32,
+    pub kind: u8, // 0 = Bomb, 1 = Wood, 2 = Snowman
+}
+
+impl ArenaObstacle {
+    pub fn size(&self) -> (f32, f32) {
+        if self.kind == 2 { (20.0, 30.0) } else { (24.0, 24.0) }
+    }
+}
+
+// A hand-made multiplayer field. Saved, exported and stored in replays as JSON.
+#[turbo::serialize]
+#[derive(PartialEq)]
+pub struct Arena {
+    pub name: String,
+    pub rules: ArenaRules,
+    pub houses: Vec<ArenaHouse>,
+    pub obstacles: Vec<ArenaObstacle>,
+    pub water: Vec<Zone>,
+    pub bridges: Vec<Zone>,
+    pub decors: Vec<Decor>,
+    pub cage: (f32, f32),
+}
+
+impl Arena {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            rules: ArenaRules::level(1),
+            houses: vec![],
+            obstacles: vec![],
+            water: vec![],
+            bridges: vec![],
+            decors: vec![],
+            cage: (30.0, 260.0),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    // None for malformed text or an arena no level could play
+    pub fn from_json(text: &str) -> Option<Self> {
+        let arena: Arena = serde_json::from_str(text).ok()?;
+        let on_field = |x: f32, y: f32| (0.0..=512.0).contains(&x) && (0.0..=288.0).contains(&y);
+        let valid = (1..=5).contains(&arena.rules.level)
+            && arena.houses.iter().all(|h| on_field(h.x, h.y) && h.team <= 4)
+            && arena.obstacles.iter().all(|o| on_field(o.x, o.y) && o.kind <= 2)
+            && arena.decors.iter().all(|d| on_field(d.x, d.y) && d.kind <= 1)
+            && on_field(arena.cage.0, arena.cage.1);
+        valid.then_some(arena)
+    }
+
+    // Moves one rule of the rules panel on by `step` (switches flip either way).
+    // A preset replaces every rule; a river layout replaces the arena's rivers
+    // and bridges with that layout's.
+    pub fn change_rule(&mut self, rule: ArenaRule, step: i32) {
+        let rules = &mut self.rules;
+        match rule {
+            ArenaRule::Preset => *rules = ArenaRules::level(step_choice(&[1, 2, 3, 4, 5], rules.level, step)),
+            ArenaRule::Teams => rules.teams = !rules.teams,
+            ArenaRule::Trails => rules.trails = !rules.trails,
+            ArenaRule::Hazards => rules.hazards = !rules.hazards,
+            ArenaRule::Shuffle => rules.shuffle = !rules.shuffle,
+            ArenaRule::RiskyGifts => rules.risky_gift_secs = step_choice(&RISKY_GIFT_CHOICES, rules.risky_gift_secs, step),
+            ArenaRule::Dog => rules.dog = !rules.dog,
+            ArenaRule::River => {
+                rules.river = step_choice(&RIVER_CHOICES, rules.river, step);
+                self.water = rules.river.water();
+                self.bridges = rules.river.bridges();
+            },
+        }
+    }
+
+    pub fn in_water(&self, x: f32, y: f32) -> bool {
+        in_any(&self.water, x, y) && !in_any(&self.bridges, x, y)
+    }
+
+    pub fn house_at(&self, x: f32, y: f32) -> Option<usize> {
+        self.houses.iter().position(|h| near((h.x, h.y), (x, y)))
+    }
+
+    // Removes whatever is under the point: a house, obstacle or decor first,
+    // then a bridge, then a river. False when there was nothing there.
+    pub fn erase_at(&mut self, x: f32, y: f32) -> bool {
+        if let Some(i) = self.house_at(x, y) {
+            self.houses.remove(i);
+        } else if let Some(i) = self.obstacles.iter().position(|o| near((o.x, o.y), (x, y))) {
+            self.obstacles.remove(i);
+        } else if let Some(i) = self.decors.iter().position(|d| near((d.x, d.y), (x, y))) {
+            self.decors.remove(i);
+        } else if let Some(i) = self.bridges.iter().rposition(|b| b.contains(x, y)) {
+            self.bridges.remove(i);
+        } else if let Some(i) = self.water.iter().rposition(|z| z.contains(x, y)) {
+            self.water.remove(i);
+        } else {
+            return false;
+        }
+        true
+    }
+}
+
+// Close enough to pick with the editor cursor
+fn near(a: (f32, f32), b: (f32, f32)) -> bool {
+    (a.0 - b.0).abs() < 12.0 && (a.1 - b.1).abs() < 12.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Arena {
+        let mut arena = Arena::new("FORK".to_string());
+        arena.rules = ArenaRules::level(4);
+        arena.houses.push(ArenaHouse { x: 80.0, y: 60.0, team: 1, power: false });
+        arena.houses.push(ArenaHouse { x: 400.0, y: 200.0, team: 0, power: true });
+        arena.obstacles.push(ArenaObstacle { x: 200.0, y: 150.0, kind: 2 });
+        arena.water.push(Zone::new(300.0, 0.0, 30.0, 288.0));
+        arena.bridges.push(Zone::new(300.0, 100.0, 30.0, 24.0));
+        arena.decors.push(Decor { x: 20.0, y: 20.0, kind: 0 });
+        arena
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let arena = sample();
+        let json = arena.to_json();
+        assert!(json.contains("\"name\":\"FORK\""));
+        assert_eq!(Arena::from_json(&json), Some(arena));
+    }
+
+    #[test]
+    fn rejects_arenas_off_the_field_or_with_unknown_rules() {
+        let mut arena = sample();
+        arena.rules.level = 6;
+        assert_eq!(Arena::from_json(&arena.to_json()), None);
+
+        let mut arena = sample();
+        arena.houses[0].x = 600.0;
+        assert_eq!(Arena::from_json(&arena.to_json()), None);
+
+        assert_eq!(Arena::from_json("{\"name\": 3}"), None);
+    }
+
+    #[test]
+    fn bridges_keep_their_stretch_of_river_dry() {
+        let arena = sample();
+        assert!(arena.in_water(315.0, 50.0));
+        assert!(!arena.in_water(315.0, 110.0));
+        assert!(!arena.in_water(250.0, 50.0));
+    }
+
+    #[test]
+    fn erasing_takes_the_top_thing_first() {
+        let mut arena = sample();
+        arena.obstacles.push(ArenaObstacle { x: 315.0, y: 112.0, kind: 1 });
+        assert!(arena.erase_at(310.0, 110.0));
+        assert_eq!(arena.obstacles.len(), 1);
+        assert!(arena.erase_at(310.0, 110.0));
+        assert!(arena.bridges.is_empty());
+        assert!(arena.erase_at(310.0, 110.0));
+        assert!(arena.water.is_empty());
+        assert!(!arena.erase_at(310.0, 110.0));
+        assert_eq!(arena.house_at(85.0, 55.0), Some(0));
+    }
+
+    #[test]
+    fn level_presets_stack_up() {
+        let one = ArenaRules::level(1);
+        assert!(!one.hazards && !one.teams && !one.power_house() && one.river == RiverLayout::None);
+        assert!(ArenaRules::level(2).shuffle);
+        let four = ArenaRules::level(4);
+        assert!(four.teams && four.trails && four.power_house() && !four.dog);
+        assert_eq!(four.river, RiverLayout::Straight);
+        let five = ArenaRules::level(5);
+        assert!(five.dog && five.risky_gift_secs < four.risky_gift_secs);
+        assert_eq!(five.river, RiverLayout::Cross);
+    }
+
+    #[test]
+    fn rules_mix_one_at_a_time() {
+        let mut arena = Arena::new("MIX".to_string());
+        arena.change_rule(ArenaRule::Trails, 1);
+        arena.change_rule(ArenaRule::Dog, -1);
+        arena.change_rule(ArenaRule::RiskyGifts, 1);
+        // Level 1 otherwise: no teams, no bombs going off
+        assert!(arena.rules.trails && arena.rules.dog && !arena.rules.teams && !arena.rules.hazards);
+        assert_eq!(arena.rules.risky_gift_secs, 15);
+        assert_eq!(ArenaRule::RiskyGifts.value(&arena.rules), "EVERY 15s");
+        arena.change_rule(ArenaRule::RiskyGifts, -1);
+        assert_eq!(ArenaRule::RiskyGifts.value(&arena.rules), "OFF");
+
+        // Presets wrap and replace everything
+        arena.change_rule(ArenaRule::Preset, -1);
+        assert!(arena.rules == ArenaRules::level(5));
+        arena.change_rule(ArenaRule::Preset, 1);
+        assert!(arena.rules == ArenaRules::level(1));
+
+        // Picking a river lays it out; the mix still saves
+        arena.change_rule(ArenaRule::River, 1);
+        assert!(arena.water == RiverLayout::Straight.water() && arena.bridges == RiverLayout::Straight.bridges());
+        arena.change_rule(ArenaRule::River, -1);
+        assert!(arena.water.is_empty() && arena.bridges.is_empty());
+        arena.change_rule(ArenaRule::Teams, 1);
+        assert_eq!(Arena::from_json(&arena.to_json()), Some(arena));
+    }
+
+    #[test]
+    fn landings_sit_either_side_of_the_water() {
+        // Matches the level 3 bridge ends the CPU rival used to hard-code
+        assert_eq!(RiverLayout::Straight.bridges()[0].landings(&RiverLayout::Straight.water()), ((226.0, 100.0), (286.0, 100.0)));
+        assert_eq!(RiverLayout::Cross.bridges()[2].landings(&RiverLayout::Cross.water()), ((114.0, 114.0), (114.0, 174.0)));
+        // A square bridge goes the way its river needs
+        let water = [Zone::new(-16.0, 180.0, 544.0, 30.0)];
+        assert_eq!(Zone::new(400.0, 180.0, 30.0, 30.0).landings(&water), ((415.0, 170.0), (415.0, 220.0)));
+        assert_eq!(Zone::between((50.0, 90.0), (10.0, 30.0)), Zone::new(10.0, 30.0, 40.0, 60.0));
+    }
+}
@@ -1,13 +1,68 @@
-    use turbo::*;
+use turbo::*;
+use crate::model::rng::Rng;
+use crate::model::input::Input;
+use crate::model::game_mode::GameMode;
+use crate::model::replay::Replay;
+use crate::model::records::RecordKind;
+use crate::model::storage::SaveData;
+use crate::model::settings::Settings;
+use crate::model::breaker_powerups::{BreakerCapsule, BreakerPower, BreakerPowers, CAPSULE_H, CAPSULE_W, MAX_BALLS};
+use crate::model::breaker_levels::{BreakerLevel, LEVEL_COLS, PACK};
+use crate::model::breaker_physics::{paddle_bounce, reflect, sweep_ball, SweepHit};
+
+// Frames between laser volleys
+const LASER_DELAY: u32 = 15;
+// Bricks further apart than this (center to center) don't catch an ornament's blast
+const BLAST_RADIUS: f32 = 35.0;
+// How far a moving row slides either side of where it was built
+const SLIDE_RANGE: f32 = 16.0;
+// The ball speeds up each level, up to this
+const MAX_BALL_SPEED: f32 = 7.0;
+// Most bounces worked out for one ball in one frame
+const MAX_BOUNCES: usize = 4;
+// Brick size plus gap on a full-width field; narrower fields shrink it
+const BRICK_PITCH: f32 = 23.0;
+// Top of the wall
+const WALL_TOP: f32 = 40.0;
+// Versus: a wall grown down past this buries its player
+const BURY_LINE: f32 = 210.0;
+const GARBAGE_COLOR: u32 = 0x7F8C8DFF;
+// Player 1's paddle, player 2's
+const PADDLE_COLORS: [u32; 2] = [0xC0392BFF, 0x27AE60FF];
 
 #[turbo::serialize]
 #[derive(Copy, PartialEq)]
 pub enum BreakerState {
     Menu,
+    // Picking the starting level, once more than one is unlocked
+    LevelSelect,
     Playing,
     GameOver,
 }
 
+// Who's playing, picked on the difficulty menu
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub enum BreakerPlayers {
+    Solo,
+    // Two paddles along the bottom of one field, sharing the lives
+    Coop,
+    // A field each, side by side; clearing a row sends the other a row of garbage
+    Versus,
+}
+
+impl BreakerPlayers {
+    pub const ALL: [BreakerPlayers; 3] = [BreakerPlayers::Solo, BreakerPlayers::Coop, BreakerPlayers::Versus];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BreakerPlayers::Solo => "1 PLAYER",
+            BreakerPlayers::Coop => "2P CO-OP",
+            BreakerPlayers::Versus => "2P VERSUS",
+        }
+    }
+}
+
 #[turbo::serialize]
 pub struct BreakerBall {
     pub x: f32,
@@ -15,8 +70,19 @@ pub struct BreakerBall {
     pub r: f32,
     pub dx: f32,
     pub dy: f32,
-    pub speed: f32,
+    // False while it sits on the paddle waiting for A
     pub active: bool,
+    // Where on the paddle it sits, from the middle (sticky catches)
+    pub offset: f32,
+    // The paddle it sits on, or last came off
+    pub paddle: usize,
+}
+
+// Laser shot from the paddle, flying straight up
+#[turbo::serialize]
+pub struct BreakerShot {
+    pub x: f32,
+    pub y: f32,
 }
 
 #[turbo::serialize]
@@ -26,6 +92,21 @@ pub struct BreakerPaddle {
     pub w: f32,
     pub h: f32,
     pub speed: f32,
+    // Gamepad moving it
+    pub pad: usize,
+    // Frames until its laser fires again
+    pub cooldown: u32,
+}
+
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub enum BrickKind {
+    // Ordinary present; may take several hits
+    Gift,
+    // Never breaks, and doesn't count towards clearing the level
+    Steel,
+    // Blows up its neighbors when it breaks
+    Ornament,
 }
 
 #[turbo::serialize]
@@ -36,6 +117,41 @@ pub struct BreakerBrick {
     pub h: f32,
     pub color: u32,
     pub active: bool,
+    pub kind: BrickKind,
+    pub hp: i32,
+    pub max_hp: i32,
+    // Sideways speed of a moving brick, and the spot it slides around
+    pub vx: f32,
+    pub home_x: f32,
+}
+
+impl BreakerBrick {
+    pub fn new(x: f32, y: f32, size: f32, color: u32, kind: BrickKind, hp: i32) -> Self {
+        Self { x, y, w: size, h: size, color, active: true, kind, hp, max_hp: hp, vx: 0.0, home_x: x }
+    }
+
+    pub fn is_breakable(&self) -> bool {
+        self.kind != BrickKind::Steel
+    }
+
+    pub fn center(&self) -> (f32, f32) {
+        (self.x + self.w / 2.0, self.y + self.h / 2.0)
+    }
+
+    // Hits it has taken (drawn as cracks)
+    pub fn damage(&self) -> i32 {
+        self.max_hp - self.hp
+    }
+
+    pub fn slide(&mut self) {
+        if self.vx == 0.0 {
+            return;
+        }
+        self.x += self.vx;
+        if (self.x - self.home_x).abs() >= SLIDE_RANGE {
+            self.vx = -self.vx;
+        }
+    }
 }
 
 #[turbo::serialize]
@@ -46,117 +162,671 @@ pub struct BreakerSnow {
     pub v: f32,
 }
 
+// What a ball touches first on its way
+enum Touch {
+    Brick(usize),
+    Paddle(usize),
+}
+
+// One wall of bricks and the paddles defending it. Solo and co-op play on a
+// single full-width field; versus gives each player half the screen.
 #[turbo::serialize]
-pub struct BreakerGame {
+pub struct BreakerField {
+    // Walls either side
+    pub left: f32,
+    pub right: f32,
+    // Built back to front, so versus players face mirror images
+    pub mirrored: bool,
+    pub paddles: Vec<BreakerPaddle>,
+    pub balls: Vec<BreakerBall>,
+    // Every ball's speed; goes up each level
+    pub ball_speed: f32,
+    pub bricks: Vec<BreakerBrick>,
+    pub capsules: Vec<BreakerCapsule>,
+    pub shots: Vec<BreakerShot>,
+    pub powers: BreakerPowers,
     pub score: i32,
     pub lives: i32,
+    // Rows emptied since the last take_cleared_rows (versus garbage)
+    pub cleared_rows: u32,
+    pub rng: Rng,
+    // Screen shake requested this frame (pixels)
+    pub shake: usize,
+}
+
+impl BreakerField {
+    // One paddle per gamepad in `pads`, spread evenly along the bottom
+    pub fn new(left: f32, right: f32, pads: &[usize], seed: u32) -> Self {
+        let mut field = Self {
+            left,
+            right,
+            mirrored: false,
+            paddles: pads.iter().map(|&pad| BreakerPaddle {
+                x: 0.0, // Set in reset_paddles
+                y: 0.0,
+                w: 60.0,
+                h: 10.0,
+                speed: 6.0,
+                pad,
+                cooldown: 0,
+            }).collect(),
+            balls: vec![],
+            ball_speed: 4.0,
+            bricks: vec![],
+            capsules: vec![],
+            shots: vec![],
+            powers: BreakerPowers::new(),
+            score: 0,
+            lives: 3,
+            cleared_rows: 0,
+            rng: Rng::new(seed),
+            shake: 0,
+        };
+        field.reset_paddles();
+        field.reset_balls();
+        field
+    }
+
+    pub fn width(&self) -> f32 {
+        self.right - self.left
+    }
+
+    // Brick spacing: as before on a full-width field, and small enough for
+    // a full row to slide inside a narrow one
+    pub fn pitch(&self) -> f32 {
+        ((self.width() - 2.0 * SLIDE_RANGE) / LEVEL_COLS as f32).min(BRICK_PITCH)
+    }
+
+    fn reset_paddles(&mut self) {
+        let n = self.paddles.len() as f32;
+        for (i, p) in self.paddles.iter_mut().enumerate() {
+            let center = self.left + (self.right - self.left) * (i as f32 + 1.0) / (n + 1.0);
+            p.x = center - p.w / 2.0;
+            p.y = 288.0 - 30.0;
+        }
+    }
+
+    // Back to a single ball on each paddle
+    fn reset_balls(&mut self) {
+        let r = 5.0;
+        self.balls = self.paddles.iter().enumerate().map(|(i, p)| BreakerBall {
+            x: p.x + p.w / 2.0,
+            y: p.y - r - 2.0,
+            r,
+            dx: 0.0,
+            dy: 0.0,
+            active: false,
+            offset: 0.0,
+            paddle: i,
+        }).collect();
+    }
+
+    fn launch_ball(&mut self, i: usize) {
+        let speed = self.ball_speed;
+        // Random angle, unless it was caught off-center
+        let r = (self.rng.u32() % 100) as f32 / 100.0; // 0.0 - 1.0
+        let b = &mut self.balls[i];
+        b.active = true;
+        b.dy = -speed;
+        b.dx = if b.offset != 0.0 { b.offset * 0.2 } else { (r - 0.5) * 4.0 };
+        b.offset = 0.0;
+    }
+
+    // Power-ups, capsules and laser fire all go with the paddle that caught them
+    fn reset_powers(&mut self) {
+        self.powers = BreakerPowers::new();
+        self.capsules.clear();
+        self.shots.clear();
+        self.resize_paddles();
+    }
+
+    // Keeps each paddle centered where it was as it grows or shrinks
+    fn resize_paddles(&mut self) {
+        let w = self.powers.paddle_width();
+        for p in &mut self.paddles {
+            let center = p.x + p.w / 2.0;
+            p.w = w;
+            p.x = (center - w / 2.0).clamp(self.left, self.right - w);
+        }
+    }
+
+    pub fn grant_power(&mut self, kind: BreakerPower) {
+        self.powers.grant(kind);
+        match kind {
+            BreakerPower::Wide | BreakerPower::Narrow => self.resize_paddles(),
+            BreakerPower::Multiball => self.split_balls(),
+            BreakerPower::Sticky | BreakerPower::Laser | BreakerPower::Slow => {},
+        }
+    }
+
+    // Every ball gets two more, fanned left and right of it
+    fn split_balls(&mut self) {
+        let speed = self.ball_speed;
+        let mut extra = vec![];
+        for b in &self.balls {
+            for side in [-1.5, 1.5] {
+                let dy = if b.active { b.dy } else { -speed };
+                extra.push(BreakerBall { dx: b.dx + side, dy, active: true, offset: 0.0, ..b.clone() });
+            }
+        }
+        let room = MAX_BALLS.saturating_sub(self.balls.len());
+        self.balls.extend(extra.into_iter().take(room));
+    }
+
+    pub fn end_power(&mut self, kind: BreakerPower) {
+        match kind {
+            BreakerPower::Wide | BreakerPower::Narrow => self.resize_paddles(),
+            // The extras melt; each paddle keeps one of its balls: one it's
+            // still holding, otherwise the one nearest it
+            BreakerPower::Multiball => {
+                let mut kept: Vec<BreakerBall> = vec![];
+                for b in self.balls.drain(..) {
+                    let better = |k: &BreakerBall| (!b.active, b.y) > (!k.active, k.y);
+                    match kept.iter_mut().find(|k| k.paddle == b.paddle) {
+                        Some(k) if better(k) => *k = b,
+                        Some(_) => {},
+                        None => kept.push(b),
+                    }
+                }
+                self.balls = kept;
+            },
+            // Anything still held goes up
+            BreakerPower::Sticky => {
+                for i in 0..self.balls.len() {
+                    if !self.balls[i].active && self.balls[i].offset != 0.0 {
+                        self.launch_ball(i);
+                    }
+                }
+            },
+            BreakerPower::Laser | BreakerPower::Slow => {},
+        }
+    }
+
+    // A ball or laser strike; steel shrugs it off
+    pub fn hit_brick(&mut self, i: usize) {
+        let br = &mut self.bricks[i];
+        if !br.is_breakable() {
+            return;
+        }
+        br.hp -= 1;
+        if br.hp <= 0 {
+            self.break_brick(i);
+        }
+    }
+
+    // Breaks it outright; ornaments take every breakable brick nearby with them,
+    // and any ornaments among those go off in turn
+    fn break_brick(&mut self, i: usize) {
+        let blast_radius = BLAST_RADIUS * self.pitch() / BRICK_PITCH;
+        let mut blasts = vec![];
+        let mut next = vec![i];
+        while let Some(i) = next.pop() {
+            let br = &mut self.bricks[i];
+            if !br.active {
+                continue;
+            }
+            br.active = false;
+            self.score += 100 * br.max_hp;
+            let row_y = br.y;
+            let (x, y) = br.center();
+            if br.kind == BrickKind::Ornament {
+                blasts.push((x, y));
+                for (j, other) in self.bricks.iter().enumerate() {
+                    let (ox, oy) = other.center();
+                    if other.active && other.is_breakable() && (ox - x).hypot(oy - y) < blast_radius {
+                        next.push(j);
+                    }
+                }
+            }
+            // That was the last one left in its row
+            if !self.bricks.iter().any(|b| b.active && b.is_breakable() && (b.y - row_y).abs() < 0.5) {
+                self.cleared_rows += 1;
+            }
+            if let Some(kind) = BreakerPower::roll_drop(&mut self.rng) {
+                self.capsules.push(BreakerCapsule { x, y, kind });
+            }
+        }
+        if !blasts.is_empty() {
+            self.shake = 2 + blasts.len().min(4);
+        }
+    }
+
+    // Bricks still standing between the player and the next level
+    pub fn bricks_left(&self) -> usize {
+        self.bricks.iter().filter(|b| b.active && b.is_breakable()).count()
+    }
+
+    // Brick size and the gap around it
+    fn brick_size(&self) -> (f32, f32) {
+        let pitch = self.pitch();
+        let size = pitch * 18.0 / BRICK_PITCH;
+        (size, pitch - size)
+    }
+
+    // Lays out a level's bricks from the pack
+    pub fn build_level(&mut self, level: i32) {
+        self.bricks.clear();
+        let pitch = self.pitch();
+        let (size, gap) = self.brick_size();
+        let level = BreakerLevel::from_pack(level);
+        // Centered on the widest row; a full row spans the screen as before
+        let cols = level.width();
+        let start_x = self.left + (self.width() - cols as f32 * pitch) / 2.0 + gap / 2.0;
+
+        // Start Y higher (smaller bricks also helps)
+        // 8 rows at most: 8 * (18+5) = 184. Y_bottom = 224.
+        // Screen Height 288. Gap to paddle(~258) = 34. Tight but playable.
+        for (r, row) in level.rows.iter().enumerate() {
+            // Sliding rows go in alternate directions
+            let row_vx = match (row.slides, r % 2) {
+                (false, _) => 0.0,
+                (true, 0) => 0.5,
+                (true, _) => -0.5,
+            };
+            for (c, cell) in row.cells.iter().enumerate() {
+                let Some(spec) = cell else { continue };
+                let col = if self.mirrored { cols - 1 - c } else { c };
+                let mut brick = BreakerBrick::new(
+                    start_x + col as f32 * pitch,
+                    WALL_TOP + r as f32 * pitch,
+                    size, spec.color, spec.kind, spec.hp,
+                );
+                brick.vx = if self.mirrored { -row_vx } else { row_vx };
+                self.bricks.push(brick);
+            }
+        }
+    }
+
+    // On to the next level with the paddles where they are
+    fn next_level(&mut self, level: i32) {
+        self.ball_speed = (self.ball_speed + 1.0).min(MAX_BALL_SPEED);
+        self.build_level(level);
+        self.capsules.clear();
+        self.shots.clear();
+        self.reset_balls();
+    }
+
+    // Versus: a full row of grey bricks with a gap or two, under the rest of the wall
+    pub fn add_garbage_row(&mut self) {
+        let pitch = self.pitch();
+        let (size, gap) = self.brick_size();
+        let y = self.bricks.iter()
+            .filter(|b| b.active)
+            .map(|b| b.y + pitch)
+            .fold(WALL_TOP, f32::max);
+        let start_x = self.left + (self.width() - LEVEL_COLS as f32 * pitch) / 2.0 + gap / 2.0;
+        let holes = [self.rng.u32() as usize % LEVEL_COLS, self.rng.u32() as usize % LEVEL_COLS];
+        for c in (0..LEVEL_COLS).filter(|c| !holes.contains(c)) {
+            self.bricks.push(BreakerBrick::new(start_x + c as f32 * pitch, y, size, GARBAGE_COLOR, BrickKind::Gift, 1));
+        }
+        self.shake = self.shake.max(2);
+    }
+
+    // Rows emptied since last asked
+    pub fn take_cleared_rows(&mut self) -> u32 {
+        std::mem::take(&mut self.cleared_rows)
+    }
+
+    // Versus: the wall has grown down too close to the paddle to play
+    pub fn buried(&self) -> bool {
+        self.bricks.iter().any(|b| b.active && b.y + b.h > BURY_LINE)
+    }
+
+    // One frame of play. Lives run out here; clearing the wall is up to the game.
+    pub fn update(&mut self, input: &Input) {
+        // Paddles, each on its own pad; co-op paddles can't pass each other
+        for i in 0..self.paddles.len() {
+            let p = &self.paddles[i];
+            let gp = input.pad(p.pad);
+            let mut x = p.x;
+            if gp.left.pressed() { x -= p.speed; }
+            if gp.right.pressed() { x += p.speed; }
+            for (j, other) in self.paddles.iter().enumerate() {
+                if j == i {
+                    continue;
+                }
+                if other.x >= p.x {
+                    x = x.min(other.x - p.w);
+                } else {
+                    x = x.max(other.x + other.w);
+                }
+            }
+            self.paddles[i].x = x.clamp(self.left, self.right - p.w);
+        }
+
+        // Power-ups
+        for kind in self.powers.tick() {
+            self.end_power(kind);
+        }
+
+        // Laser: A fires from both ends of the paddle
+        let laser = self.powers.is_active(BreakerPower::Laser);
+        for p in &mut self.paddles {
+            p.cooldown = p.cooldown.saturating_sub(1);
+            if laser && input.pad(p.pad).a.pressed() && p.cooldown == 0 {
+                self.shots.push(BreakerShot { x: p.x + 3.0, y: p.y - 6.0 });
+                self.shots.push(BreakerShot { x: p.x + p.w - 5.0, y: p.y - 6.0 });
+                p.cooldown = LASER_DELAY;
+            }
+        }
+
+        // Moving Bricks
+        for br in &mut self.bricks {
+            br.slide();
+        }
+
+        // Ball Logic: each ball launches with its own paddle's player
+        // (A only: Start during play opens the pause menu)
+        let launches: Vec<bool> = self.paddles.iter()
+            .map(|p| input.pad(p.pad).a.just_pressed())
+            .collect();
+        for i in 0..self.balls.len() {
+            let launch = launches[self.balls[i].paddle];
+            self.update_ball(i, launch);
+        }
+
+        // Floor
+        self.balls.retain(|b| b.y - b.r <= 288.0);
+        if self.balls.is_empty() && self.lives > 0 {
+            self.lives -= 1;
+            self.shake = 3;
+            self.reset_powers();
+            if self.lives > 0 {
+                self.reset_balls();
+            }
+        }
+
+        // Capsules
+        self.capsules.retain_mut(|c| c.update());
+        let caught = self.capsules.iter().position(|c| self.paddles.iter().any(|p| {
+            c.x + CAPSULE_W / 2.0 > p.x && c.x - CAPSULE_W / 2.0 < p.x + p.w &&
+            c.y + CAPSULE_H / 2.0 > p.y && c.y - CAPSULE_H / 2.0 < p.y + p.h
+        }));
+        if let Some(i) = caught {
+            let kind = self.capsules.remove(i).kind;
+            self.grant_power(kind);
+        }
+
+        // Laser Shots
+        for shot in &mut self.shots {
+            shot.y -= 6.0;
+        }
+        self.shots.retain(|s| s.y > -8.0);
+        for j in (0..self.shots.len()).rev() {
+            let s = &self.shots[j];
+            let hit = self.bricks.iter().position(|br| {
+                br.active && s.x + 2.0 > br.x && s.x < br.x + br.w && s.y < br.y + br.h && s.y + 6.0 > br.y
+            });
+            if let Some(i) = hit {
+                self.shots.remove(j);
+                self.hit_brick(i);
+            }
+        }
+    }
+
+    fn update_ball(&mut self, i: usize, launch: bool) {
+        if !self.balls[i].active {
+            let b = &mut self.balls[i];
+            let p = &self.paddles[b.paddle];
+            b.x = p.x + p.w / 2.0 + b.offset;
+            b.y = p.y - b.r - 2.0;
+
+            if launch {
+                self.launch_ball(i);
+            }
+            return;
+        }
+
+        // Move Ball, swept against the bricks and paddles so nothing is skipped
+        // at speed; it can bounce several times in one frame
+        let step = self.powers.ball_step();
+        let mut left = 1.0;
+        for _ in 0..MAX_BOUNCES {
+            let b = &self.balls[i];
+            let (pos, vel) = ((b.x, b.y), (b.dx * step * left, b.dy * step * left));
+
+            let mut first: Option<(Touch, SweepHit)> = None;
+            let bricks = self.bricks.iter().enumerate()
+                .filter(|(_, br)| br.active)
+                .map(|(j, br)| (Touch::Brick(j), (br.x, br.y, br.w, br.h)));
+            let paddles = self.paddles.iter().enumerate()
+                .map(|(j, p)| (Touch::Paddle(j), (p.x, p.y, p.w, p.h)));
+            for (touch, rect) in bricks.chain(paddles) {
+                if let Some(hit) = sweep_ball(pos, vel, b.r, rect)
+                    && first.as_ref().is_none_or(|(_, f)| hit.t < f.t)
+                {
+                    first = Some((touch, hit));
+                }
+            }
+
+            let b = &mut self.balls[i];
+            let Some((touch, hit)) = first else {
+                b.x += vel.0;
+                b.y += vel.1;
+                break;
+            };
+            // Up to the touching point, and a hair off the surface
+            b.x += vel.0 * hit.t + hit.normal.0 * 0.01;
+            b.y += vel.1 * hit.t + hit.normal.1 * 0.01;
+            left *= 1.0 - hit.t;
+            match touch {
+                Touch::Brick(j) => {
+                    (b.dx, b.dy) = reflect((b.dx, b.dy), hit.normal);
+                    self.hit_brick(j);
+                },
+                Touch::Paddle(j) => {
+                    if self.bounce_off_paddle(i, j, hit.normal) {
+                        break;
+                    }
+                },
+            }
+        }
+
+        // Walls
+        let b = &mut self.balls[i];
+        if b.x + b.r > self.right {
+            b.x = self.right - b.r;
+            b.dx = -b.dx.abs();
+        }
+        if b.x - b.r < self.left {
+            b.x = self.left + b.r;
+            b.dx = b.dx.abs();
+        }
+        if b.y - b.r < 0.0 {
+            b.y = b.r;
+            b.dy = b.dy.abs();
+        }
+    }
+
+    // Off the top of the paddle the ball is steered by where it landed; off
+    // the ends it just bounces. True if it stuck (sticky) and stops here.
+    fn bounce_off_paddle(&mut self, i: usize, paddle: usize, normal: (f32, f32)) -> bool {
+        let p = &self.paddles[paddle];
+        let b = &mut self.balls[i];
+        if normal.1 >= 0.0 {
+            (b.dx, b.dy) = reflect((b.dx, b.dy), normal);
+            return false;
+        }
+
+        // Angular bounce
+        b.paddle = paddle;
+        let hit_point = b.x - (p.x + p.w / 2.0);
+        (b.dx, b.dy) = paddle_bounce(hit_point, self.ball_speed, b.dx);
+
+        // Sticky: held where it landed until A
+        if self.powers.is_active(BreakerPower::Sticky) {
+            b.active = false;
+            // Never exactly 0, which means "launch at random"
+            let offset = hit_point.clamp(-p.w / 2.0, p.w / 2.0);
+            b.offset = if offset == 0.0 { 0.1 } else { offset };
+            return true;
+        }
+        false
+    }
+
+    // Everything in the field, without the score line
+    pub fn draw(&self) {
+        // Paddles
+        for p in &self.paddles {
+            let (px, py, pw) = (p.x as i32, p.y as i32, p.w as u32);
+            rect!(x=px, y=py, w=pw, h=p.h as u32, color=PADDLE_COLORS[p.pad.min(1)]);
+            let trim = if self.powers.is_active(BreakerPower::Sticky) { 0x2ECC71FF } else { 0xF1C40FFF };
+            rect!(x=px, y=py + 2, w=pw, h=2, color=trim);
+            // Laser cannons on each end
+            if self.powers.is_active(BreakerPower::Laser) {
+                rect!(x=px + 1, y=py - 4, w=6, h=4, color=0x7F8C8DFF);
+                rect!(x=px + pw as i32 - 7, y=py - 4, w=6, h=4, color=0x7F8C8DFF);
+            }
+        }
+
+        // Balls (Santa Face)
+        for ball in &self.balls {
+            let bx = ball.x as i32;
+            let by = ball.y as i32;
+            // let br = ball.r as i32; // Unused
+
+            // Ensure size is big enough for face. Radius 5 is diameter 10.
+            // Let's draw a slightly larger sprite centered at bx, by.
+            // Original logic checks collision with r=5.
+            // We will draw a 12x12 sprite centered.
+
+            let sx = bx - 6;
+            let sy = by - 6;
+
+            // Head
+            rect!(x=sx+2, y=sy+4, w=8, h=6, color=0xFFCCBCFF);
+            // Beard
+            rect!(x=sx+1, y=sy+8, w=10, h=4, color=0xFFFFFFFF);
+            // Hat
+            rect!(x=sx, y=sy, w=12, h=4, color=0xD32F2FFF);
+            rect!(x=sx+10, y=sy+1, w=2, h=2, color=0xFFFFFFFF); // Pom
+
+            // Debug/Hitbox (Optional, commented out)
+            // circ!(x=bx - br, y=by - br, d=(br*2) as u32, color=0xFF000044);
+        }
+
+        // Laser Shots
+        for s in &self.shots {
+            rect!(x=s.x as i32, y=s.y as i32, w=2, h=6, color=0xFF5252FF);
+        }
+
+        // Capsules
+        for c in &self.capsules {
+            let (cx, cy) = ((c.x - CAPSULE_W / 2.0) as i32, (c.y - CAPSULE_H / 2.0) as i32);
+            rect!(x=cx, y=cy, w=CAPSULE_W as u32, h=CAPSULE_H as u32, border_size=1, border_color=0xFFFFFFFF, color=c.kind.color());
+            text!(c.kind.icon(), x=cx + 7, y=cy + 1, font="small", color=0x000000FF);
+        }
+
+        // Bricks
+        for b in &self.bricks {
+            if !b.active { continue; }
+            draw_brick(b);
+        }
+
+        // Power timers along the bottom, one draining bar each
+        let fits = ((self.width() - 10.0) / 84.0) as usize;
+        for (i, kind) in self.powers.running().into_iter().take(fits).enumerate() {
+            let x = self.left as i32 + 10 + i as i32 * 84;
+            let fill = 40 * self.powers.left(kind) / kind.duration();
+            text!(kind.name(), x=x, y=276, font="small", color=kind.color());
+            rect!(x=x + 38, y=277, w=40, h=4, color=0x333333FF);
+            rect!(x=x + 38, y=277, w=fill, h=4, color=kind.color());
+        }
+
+        if self.lives > 0 && self.balls.iter().any(|b| !b.active && b.offset == 0.0) {
+            let cx = ((self.left + self.right) / 2.0) as i32;
+            text!("PRESS A", x=cx - 29, y=200, font="small", color=0xAAAAAAFF);
+        }
+    }
+}
+
+#[turbo::serialize]
+pub struct BreakerGame {
     pub level: i32,
     pub state: BreakerState,
     pub menu_selection: u8, // 0=Easy, 1=Medium, 2=Hard, 3=VeryHard
-    pub paddle: BreakerPaddle,
-    pub ball: BreakerBall,
-    pub bricks: Vec<BreakerBrick>,
+    pub players: BreakerPlayers,
+    // Pack levels that can be started from (1..=unlocked), and the one picked
+    pub unlocked: i32,
+    pub level_selection: i32,
+    // One field, or one per player in versus
+    pub fields: Vec<BreakerField>,
+    // Versus: who won (None for a draw)
+    pub winner: Option<usize>,
     pub snow: Vec<BreakerSnow>,
+    pub rng: Rng,
+    // Screen shake requested this frame (pixels); GameState applies and clears it
+    pub shake: usize,
+
+    // Records
+    pub result_recorded: bool,
+    pub record_rank: Option<usize>,
 }
 
 impl BreakerGame {
-    pub fn new() -> Self {
+    pub fn new(seed: u32) -> Self {
         let mut game = Self {
-            score: 0,
-            lives: 3,
             level: 1,
             state: BreakerState::Menu,
             menu_selection: 1, // Default Medium
-            paddle: BreakerPaddle {
-                x: 0.0, // Set in reset_paddle
-                y: 0.0,
-                w: 60.0,
-                h: 10.0,
-                speed: 6.0,
-            },
-            ball: BreakerBall {
-                x: 0.0,
-                y: 0.0,
-                r: 5.0,
-                dx: 0.0,
-                dy: 0.0,
-                speed: 4.0,
-                active: false,
-            },
-            bricks: vec![],
+            players: BreakerPlayers::Solo,
+            unlocked: 1,
+            level_selection: 1,
+            fields: vec![BreakerField::new(0.0, 512.0, &[0], seed)],
+            winner: None,
             snow: vec![],
+            rng: Rng::new(seed),
+            shake: 0,
+            result_recorded: false,
+            record_rank: None,
         };
-        
+
         // Init Snow
         for _ in 0..50 {
             game.snow.push(BreakerSnow {
-                x: (rand() % 512) as f32,
-                y: (rand() % 288) as f32,
-                r: (rand() % 2 + 1) as f32,
-                v: (rand() % 2 + 1) as f32,
+                x: (game.rng.u32() % 512) as f32,
+                y: (game.rng.u32() % 288) as f32,
+                r: (game.rng.u32() % 2 + 1) as f32,
+                v: (game.rng.u32() % 2 + 1) as f32,
             });
         }
 
-        game.reset_paddle();
-        game.reset_ball();
-        // game.build_level(); // Build level when game starts
-        
         game
     }
 
-    fn reset_paddle(&mut self) {
-        self.paddle.x = (512.0 - self.paddle.w) / 2.0;
-        self.paddle.y = 288.0 - 30.0;
-    }
-
-    fn reset_ball(&mut self) {
-        self.ball.active = false;
-        self.ball.x = self.paddle.x + self.paddle.w / 2.0;
-        self.ball.y = self.paddle.y - self.ball.r - 2.0;
-    }
-
-    fn launch_ball(&mut self) {
-        self.ball.active = true;
-        self.ball.dy = -self.ball.speed;
-        // Random angle
-        let r = (rand() % 100) as f32 / 100.0; // 0.0 - 1.0
-        self.ball.dx = (r - 0.5) * 4.0;
-    }
-
-    fn build_level(&mut self) {
-        self.bricks.clear();
-        let size = 18.0; // Reduced from 25.0
-        let gap = 5.0;
-        let cols = ((512.0 - 40.0) / (size + gap)) as i32;
-        let start_x = (512.0 - (cols as f32 * (size + gap))) / 2.0 + gap / 2.0;
-        let rows = 4 + self.level;
-        
-        // Start Y higher (smaller bricks also helps)
-        // Original Y=40.0. Level Y=40.0
-        // Total height with 5 rows: 5 * (18+5) = 115. Y_bottom = 155.
-        // Screen Height 288. Gap to paddle(~258) = 100. Good.
-        let y_start = 40.0;
-        
-        let colors = [0xE74C3CFF, 0x2ECC71FF, 0x3498DBFF, 0x9B59B6FF, 0xF1C40FFF];
-
-        for r in 0..rows {
-            for c in 0..cols {
-                if (r+c) % 6 == 0 { continue; } // Pattern
-
-                self.bricks.push(BreakerBrick {
-                    x: start_x + c as f32 * (size + gap),
-                    y: y_start + r as f32 * (size + gap),
-                    w: size,
-                    h: size,
-                    color: colors[(r as usize) % colors.len()],
-                    active: true,
-                });
-            }
+    fn start_game(&mut self, level: i32) {
+        let lives = match self.menu_selection {
+            0 => 6, // Easy
+            1 => 3, // Medium
+            2 => 2, // Hard
+            3 => 1, // Very Hard
+            _ => 3,
+        };
+        self.fields = match self.players {
+            BreakerPlayers::Solo => vec![BreakerField::new(0.0, 512.0, &[0], self.rng.u32())],
+            BreakerPlayers::Coop => vec![BreakerField::new(0.0, 512.0, &[0, 1], self.rng.u32())],
+            BreakerPlayers::Versus => {
+                let mut right = BreakerField::new(256.0, 512.0, &[1], self.rng.u32());
+                right.mirrored = true;
+                vec![BreakerField::new(0.0, 256.0, &[0], self.rng.u32()), right]
+            },
+        };
+        for field in &mut self.fields {
+            field.lives = lives;
+            // Later starting levels start faster
+            field.ball_speed = (4.0 + (level - 1) as f32).min(MAX_BALL_SPEED);
+            field.build_level(level);
         }
+        self.level = level;
+        self.winner = None;
+        self.result_recorded = false;
+        self.record_rank = None;
+        self.state = BreakerState::Playing;
     }
 
-    pub fn update(&mut self) {
-        let gp = gamepad::get(0);
+    // Ball in play (pausable); false on the difficulty menu and game over
+    pub fn update(&mut self, input: &Input) {
+        let gp = input.pad(0);
 
         if self.state == BreakerState::Menu {
             if gp.up.just_pressed() && self.menu_selection > 0 {
@@ -165,22 +835,39 @@ impl BreakerGame {
             if gp.down.just_pressed() && self.menu_selection < 3 {
                 self.menu_selection += 1;
             }
-            
+            // Left/Right picks who's playing
+            let i = BreakerPlayers::ALL.iter().position(|p| *p == self.players).unwrap_or(0);
+            if gp.left.just_pressed() && i > 0 {
+                self.players = BreakerPlayers::ALL[i - 1];
+            }
+            if gp.right.just_pressed() && i + 1 < BreakerPlayers::ALL.len() {
+                self.players = BreakerPlayers::ALL[i + 1];
+            }
+
             if gp.a.just_pressed() || gp.start.just_pressed() {
-                self.lives = match self.menu_selection {
-                    0 => 6, // Easy
-                    1 => 3, // Medium
-                    2 => 2, // Hard
-                    3 => 1, // Very Hard
-                    _ => 3,
-                };
-                self.score = 0;
-                self.level = 1;
-                self.build_level();
-                self.reset_paddle();
-                self.reset_ball();
-                self.ball.speed = 4.0; // Reset speed
-                self.state = BreakerState::Playing;
+                // Nothing to pick until level 2 is unlocked
+                if self.unlocked > 1 {
+                    self.level_selection = self.unlocked;
+                    self.state = BreakerState::LevelSelect;
+                } else {
+                    self.start_game(1);
+                }
+            }
+            return;
+        }
+
+        if self.state == BreakerState::LevelSelect {
+            // 5 levels to a row
+            let mut pick = self.level_selection;
+            if gp.left.just_pressed() { pick -= 1; }
+            if gp.right.just_pressed() { pick += 1; }
+            if gp.up.just_pressed() { pick -= 5; }
+            if gp.down.just_pressed() { pick += 5; }
+            if (1..=self.unlocked).contains(&pick) {
+                self.level_selection = pick;
+            }
+            if gp.a.just_pressed() || gp.start.just_pressed() {
+                self.start_game(self.level_selection);
             }
             return;
         }
@@ -200,91 +887,48 @@ impl BreakerGame {
             if s.y > 288.0 { s.y = -5.0; }
         }
 
-        // Paddle
-        if gp.left.pressed() && self.paddle.x > 0.0 { self.paddle.x -= self.paddle.speed; }
-        if gp.right.pressed() && self.paddle.x + self.paddle.w < 512.0 { self.paddle.x += self.paddle.speed; }
-
-        // Ball Logic
-        if !self.ball.active {
-            self.ball.x = self.paddle.x + self.paddle.w / 2.0;
-            self.ball.y = self.paddle.y - self.ball.r - 2.0;
-            
-            if gp.a.just_pressed() || gp.start.just_pressed() {
-                self.launch_ball();
-            }
-        } else {
-            // Move Ball
-            self.ball.x += self.ball.dx;
-            self.ball.y += self.ball.dy;
-
-            // Walls
-            if self.ball.x + self.ball.r > 512.0 { 
-                self.ball.x = 512.0 - self.ball.r; 
-                self.ball.dx *= -1.0; 
-            }
-            if self.ball.x - self.ball.r < 0.0 { 
-                self.ball.x = self.ball.r; 
-                self.ball.dx *= -1.0; 
-            }
-            if self.ball.y - self.ball.r < 0.0 { 
-                self.ball.y = self.ball.r; 
-                self.ball.dy *= -1.0; 
-            }
+        for field in &mut self.fields {
+            field.update(input);
+            self.shake = self.shake.max(std::mem::take(&mut field.shake));
+        }
 
-            // Floor
-            if self.ball.y - self.ball.r > 288.0 {
-                self.lives -= 1;
-                if self.lives <= 0 {
-                    self.state = BreakerState::GameOver;
-                } else {
-                    self.reset_ball();
-                }
-            }
+        if self.players == BreakerPlayers::Versus {
+            self.update_versus();
+            return;
+        }
 
-            // Paddle Collision
-            let b = &mut self.ball; // Mutable borrow ball
-            let p = &self.paddle;
-            if b.y + b.r >= p.y && b.y - b.r <= p.y + p.h &&
-               b.x >= p.x && b.x <= p.x + p.w {
-                
-                b.dy = -b.dy.abs(); // Bounce Up
-                
-                // Angular bounce
-                let hit_point = b.x - (p.x + p.w / 2.0);
-                b.dx = hit_point * 0.2;
-            }
+        let field = &mut self.fields[0];
+        if field.lives <= 0 {
+            self.state = BreakerState::GameOver;
+        } else if field.bricks_left() == 0 {
+            // Level clear (steel doesn't count)
+            self.level += 1;
+            self.unlocked = self.unlocked.max(self.level.min(PACK.len() as i32));
+            field.next_level(self.level);
+        }
+    }
 
-            // Brick Collision
-            let b_x = self.ball.x;
-            let b_y = self.ball.y;
-            let b_r = self.ball.r;
-            
-            let mut hit_idx = None;
-            for (i, br) in self.bricks.iter().enumerate() {
-                if !br.active { continue; }
-                
-                if b_x + b_r > br.x && b_x - b_r < br.x + br.w &&
-                   b_y + b_r > br.y && b_y - b_r < br.y + br.h {
-                       hit_idx = Some(i);
-                       break;
-                   }
-            }
-            
-            if let Some(i) = hit_idx {
-                self.bricks[i].active = false;
-                self.ball.dy *= -1.0;
-                self.score += 100;
-                
-                // Check Level Clear
-                let remaining = self.bricks.iter().filter(|b| b.active).count();
-                if remaining == 0 {
-                    self.level += 1;
-                    self.ball.speed += 1.0;
-                    self.build_level();
-                    self.reset_ball();
-                }
+    // Garbage goes across, then the match ends once a player runs out of
+    // lives, is buried, or clears their whole wall
+    fn update_versus(&mut self) {
+        let sent = [self.fields[0].take_cleared_rows(), self.fields[1].take_cleared_rows()];
+        for (to, rows) in [(1, sent[0]), (0, sent[1])] {
+            for _ in 0..rows {
+                self.fields[to].add_garbage_row();
             }
         }
+
+        let out = |f: &BreakerField| f.lives <= 0 || f.buried();
+        let cleared = |f: &BreakerField| f.bricks_left() == 0;
+        let (a, b) = (&self.fields[0], &self.fields[1]);
+        let lost = [out(a) || cleared(b), out(b) || cleared(a)];
+        self.winner = match lost {
+            [false, false] => return,
+            [true, false] => Some(1),
+            [false, true] => Some(0),
+            [true, true] => None,
+        };
+        self.state = BreakerState::GameOver;
     }
 
     pub fn draw(&self) {
@@ -295,6 +939,8 @@ impl BreakerGame {
         if self.state == BreakerState::Menu {
             let title = "SANTA BREAKER";
             text!(title, x=180, y=50, font="large", color=0xE74C3CFF);
+            let players = format!("< {} >", self.players.name());
+            text!(&players, x=256 - players.len() as i32 * 6 / 2, y=76, font="medium", color=0x2ECC71FF);
             text!("Select Difficulty:", x=180, y=100, font="medium", color=0xFFFFFFFF);
 
             let options = ["EASY (6 Lives)", "MEDIUM (3 Lives)", "HARD (2 Lives)", "VERY HARD (1 Life)"];
@@ -307,7 +953,12 @@ impl BreakerGame {
                 }
                 text!(opt, x=160, y=y, font="medium", color=color);
             }
-            text!("Press Start/A", x=200, y=260, font="small", color=0x888888FF);
+            text!("Left/Right: Players   Press Start/A", x=256 - 35 * 5 / 2, y=260, font="small", color=0x888888FF);
+            return;
+        }
+
+        if self.state == BreakerState::LevelSelect {
+            self.draw_level_select();
             return;
         }
 
@@ -317,69 +968,692 @@ impl BreakerGame {
             circ!(x=s.x as i32, y=s.y as i32, d=(s.r * 2.0) as u32, color=0xFFFFFF80);
         }
 
-        // Paddle
-        rect!(x=self.paddle.x as i32, y=self.paddle.y as i32, w=self.paddle.w as u32, h=self.paddle.h as u32, color=0xC0392BFF);
-        rect!(x=self.paddle.x as i32, y=self.paddle.y as i32 + 2, w=self.paddle.w as u32, h=2, color=0xF1C40FFF);
-
-        // Ball (Santa Face)
-        let bx = self.ball.x as i32;
-        let by = self.ball.y as i32;
-        // let br = self.ball.r as i32; // Unused
-        
-        // Ensure size is big enough for face. Radius 5 is diameter 10.
-        // Let's draw a slightly larger sprite centered at bx, by.
-        // Original logic checks collision with r=5. 
-        // We will draw a 12x12 sprite centered.
-        
-        let sx = bx - 6;
-        let sy = by - 6;
-        
-        // Head
-        rect!(x=sx+2, y=sy+4, w=8, h=6, color=0xFFCCBCFF); 
-        // Beard
-        rect!(x=sx+1, y=sy+8, w=10, h=4, color=0xFFFFFFFF);
-        // Hat
-        rect!(x=sx, y=sy, w=12, h=4, color=0xD32F2FFF);
-        rect!(x=sx+10, y=sy+1, w=2, h=2, color=0xFFFFFFFF); // Pom
-        
-        // Debug/Hitbox (Optional, commented out)
-        // circ!(x=bx - br, y=by - br, d=(br*2) as u32, color=0xFF000044);
-
-        // Bricks
-        for b in &self.bricks {
-            if !b.active { continue; }
-            rect!(x=b.x as i32, y=b.y as i32, w=b.w as u32, h=b.h as u32, color=b.color);
-            // Ribbon
-            rect!(x=b.x as i32 + b.w as i32 / 2 - 2, y=b.y as i32, w=4, h=b.h as u32, color=0xFFFFFF66);
-            rect!(x=b.x as i32, y=b.y as i32 + b.h as i32 / 2 - 2, w=b.w as u32, h=4, color=0xFFFFFF66);
+        for field in &self.fields {
+            field.draw();
         }
 
         // HUD
-        let score_txt = format!("SCORE: {}", self.score);
-        text!(&score_txt, x=10, y=10, font="medium", color=0xFFFFFFFF);
-        let lives_txt = format!("LIVES: {}", self.lives);
-        text!(&lives_txt, x=440, y=10, font="medium", color=0xFFFFFFFF);
-        
-        if self.state == BreakerState::Playing && !self.ball.active {
-             text!("PRESS START", x=210, y=200, font="small", color=0xAAAAAAFF);
+        let level_txt = format!("LEVEL {} - {}", self.level, BreakerLevel::name_in_pack(self.level));
+        if self.players == BreakerPlayers::Versus {
+            rect!(x=255, y=0, w=2, h=288, color=0x555555FF);
+            for (i, field) in self.fields.iter().enumerate() {
+                let x = field.left as i32;
+                let score_txt = format!("P{} {}", i + 1, field.score);
+                text!(&score_txt, x=x + 10, y=10, font="medium", color=PADDLE_COLORS[i]);
+                let lives_txt = format!("LIVES: {}", field.lives);
+                text!(&lives_txt, x=x + 186, y=10, font="small", color=0xFFFFFFFF);
+            }
+            text!(&level_txt, x=256 - level_txt.len() as i32 * 5 / 2, y=26, font="small", color=0xAAAAAAFF);
+        } else {
+            let field = &self.fields[0];
+            let score_txt = format!("SCORE: {}", field.score);
+            text!(&score_txt, x=10, y=10, font="medium", color=0xFFFFFFFF);
+            let lives_txt = format!("LIVES: {}", field.lives);
+            text!(&lives_txt, x=440, y=10, font="medium", color=0xFFFFFFFF);
+            text!(&level_txt, x=256 - level_txt.len() as i32 * 5 / 2, y=12, font="small", color=0xAAAAAAFF);
         }
 
         // Game Over
         if self.state == BreakerState::GameOver {
             rect!(x=156, y=94, w=200, h=100, color=0x000000EE);
             rect!(x=156, y=94, w=200, h=100, border_size=2, border_color=0xFF0000FF, color=0x00000000);
-            text!("GAME OVER", x=210, y=110, font="large", color=0xFF0000FF);
-            let final_score_txt = format!("Score: {}", self.score);
+            let title = match (self.players, self.winner) {
+                (BreakerPlayers::Versus, Some(i)) => format!("PLAYER {} WINS", i + 1),
+                (BreakerPlayers::Versus, None) => "DRAW".to_string(),
+                _ => "GAME OVER".to_string(),
+            };
+            text!(&title, x=256 - title.len() as i32 * 8 / 2, y=110, font="large", color=0xFF0000FF);
+            let final_score_txt = match &self.fields[..] {
+                [a, b] => format!("P1: {}  P2: {}", a.score, b.score),
+                fields => format!("Score: {}", fields[0].score),
+            };
             text!(&final_score_txt, x=200, y=140, font="medium", color=0xFFFFFFFF);
+            if let Some(rank) = self.record_rank {
+                let record_txt = format!("NEW RECORD! #{}", rank + 1);
+                text!(&record_txt, x=200, y=156, font="small", color=0xFFD700FF);
+            }
             text!("Press START", x=210, y=170, font="small", color=0xAAAAAAFF);
+            let seed_txt = format!("Seed: {}", self.rng.seed);
+            text!(&seed_txt, x=200, y=182, font="small", color=0x666666FF);
+        }
+    }
+}
+
+impl BreakerGame {
+    // The pack as a 5x4 grid of thumbnails; locked levels stay dark
+    fn draw_level_select(&self) {
+        text!("SELECT LEVEL", x=256 - 12 * 8 / 2, y=14, font="large", color=0xE74C3CFF);
+        let (tile_w, tile_h, gap) = (88, 44, 6);
+        let x0 = (512 - (5 * tile_w + 4 * gap)) / 2;
+        for i in 0..PACK.len() as i32 {
+            let n = i + 1;
+            let (x, y) = (x0 + (i % 5) * (tile_w + gap), 40 + (i / 5) * (tile_h + gap));
+            let selected = n == self.level_selection;
+            let border = if selected { 0xFFFF00FF } else { 0x555555FF };
+            rect!(x=x, y=y, w=tile_w as u32, h=tile_h as u32, border_size=1, border_color=border, color=0x111122FF);
+            if n > self.unlocked {
+                text!("LOCKED", x=x + 29, y=y + 19, font="small", color=0x555555FF);
+                continue;
+            }
+            // One dot per brick
+            let level = BreakerLevel::from_pack(n);
+            let px = x + (tile_w - LEVEL_COLS as i32 * 4) / 2 + (LEVEL_COLS - level.width()) as i32 * 2;
+            for (r, row) in level.rows.iter().enumerate() {
+                for (c, cell) in row.cells.iter().enumerate() {
+                    if let Some(spec) = cell {
+                        rect!(x=px + c as i32 * 4, y=y + 10 + r as i32 * 4, w=3, h=3, color=spec.color);
+                    }
+                }
+            }
+            let num = format!("{}", n);
+            text!(&num, x=x + 3, y=y + 2, font="small", color=if selected { 0xFFFF00FF } else { 0xFFFFFFFF });
+        }
+
+        let name = BreakerLevel::name_in_pack(self.level_selection);
+        text!(&name, x=256 - name.len() as i32 * 6 / 2, y=246, font="medium", color=0xFFFF00FF);
+        text!("Arrows: Pick   Start/A: Play", x=256 - 28 * 5 / 2, y=266, font="small", color=0x888888FF);
+    }
+}
+
+fn draw_brick(b: &BreakerBrick) {
+    let (x, y, w, h) = (b.x as i32, b.y as i32, b.w as u32, b.h as u32);
+    match b.kind {
+        BrickKind::Gift => {
+            rect!(x=x, y=y, w=w, h=h, color=b.color);
+            // Ribbon
+            rect!(x=x + w as i32 / 2 - 2, y=y, w=4, h=h, color=0xFFFFFF66);
+            rect!(x=x, y=y + h as i32 / 2 - 2, w=w, h=4, color=0xFFFFFF66);
+            // Gold edge on the tough ones
+            if b.max_hp > 1 {
+                rect!(x=x, y=y, w=w, h=h, border_size=1, border_color=0xFFD700FF, color=0x00000000);
+            }
+        },
+        BrickKind::Steel => {
+            rect!(x=x, y=y, w=w, h=h, color=b.color);
+            rect!(x=x, y=y, w=w, h=2, color=0xECF0F1FF);
+            // Rivets
+            for (rx, ry) in [(3, 4), (w as i32 - 5, 4), (3, h as i32 - 5), (w as i32 - 5, h as i32 - 5)] {
+                rect!(x=x + rx, y=y + ry, w=2, h=2, color=0x5D6D7EFF);
+            }
+        },
+        BrickKind::Ornament => {
+            // Bauble with a cap and a shine
+            circ!(x=x + 1, y=y + 2, d=w - 2, color=b.color);
+            rect!(x=x + w as i32 / 2 - 3, y=y, w=6, h=3, color=0xF1C40FFF);
+            rect!(x=x + 5, y=y + 6, w=3, h=3, color=0xFFFFFFAA);
+        },
+    }
+
+    // Cracks, one more per hit taken (laid out for an 18px brick)
+    let scale = b.w / 18.0;
+    let cracks = [
+        [(4, 3), (6, 5), (7, 8), (9, 10)],
+        [(13, 4), (11, 7), (12, 10), (10, 13)],
+    ];
+    for crack in cracks.iter().take(b.damage() as usize) {
+        for (cx, cy) in crack {
+            rect!(x=x + (*cx as f32 * scale) as i32, y=y + (*cy as f32 * scale) as i32, w=2, h=2, color=0x00000099);
+        }
+    }
+}
+
+impl GameMode for BreakerGame {
+    // The level select offers what was unlocked when the session started
+    fn from_replay(replay: &Replay) -> Self {
+        let mut game = BreakerGame::new(replay.seed);
+        game.unlocked = (replay.breaker_unlocked as i32).clamp(1, PACK.len() as i32);
+        game
+    }
+
+    fn update(&mut self, input: &Input) {
+        BreakerGame::update(self, input)
+    }
+
+    fn draw(&self, _settings: &Settings) {
+        BreakerGame::draw(self)
+    }
+
+    fn is_running(&self) -> bool {
+        self.state == BreakerState::Playing
+    }
+
+    fn take_shake(&mut self) -> usize {
+        std::mem::take(&mut self.shake)
+    }
+
+    // Only solo runs go in the records
+    fn submit_record(&mut self, save: &mut SaveData, name: &str) {
+        if self.state == BreakerState::GameOver && !self.result_recorded && self.players == BreakerPlayers::Solo {
+            self.result_recorded = true;
+            let kind = RecordKind::breaker(self.menu_selection);
+            self.record_rank = save.submit_record(kind, name, self.fields[0].score);
         }
     }
+
+    fn save_progress(&mut self, save: &mut SaveData) {
+        if self.unlocked as u32 > save.breaker_unlocked {
+            save.breaker_unlocked = self.unlocked as u32;
+            save.save();
+        }
+    }
+
+    fn draw_records(&self, save: &SaveData) {
+        if self.state == BreakerState::GameOver && self.players == BreakerPlayers::Solo {
+            save.records.draw_table(RecordKind::breaker(self.menu_selection), 364, 80, self.record_rank);
+        }
+    }
+
+    fn instructions(&self) -> &'static [&'static str] {
+        &[
+            "Santa Breaker:",
+            "- Left/Right: Move Paddle",
+            "- A: Launch Ball (and fire the laser)",
+            "- Start / Esc: Pause Menu",
+            "- Menu Left/Right: 2P co-op or versus",
+            "Catch capsules. Don't lose the ball!",
+        ]
+    }
 }
 
-fn rand() -> u32 {
-    unsafe {
-        static mut SEED: u32 = 54321;
-        SEED = SEED.wrapping_mul(1103515245).wrapping_add(12345);
-        SEED
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::input::{InputFeed, BTN_A, BTN_LEFT, BTN_RIGHT, BTN_UP};
+    use crate::model::breaker_physics::{MAX_BOUNCE_ANGLE, MIN_BOUNCE_ANGLE};
+
+    // Starts a game from the difficulty menu (default Medium)
+    fn start_playing(seed: u32) -> BreakerGame {
+        let mut game = BreakerGame::new(seed);
+        let mut feed = InputFeed::default();
+        game.update(&feed.next([BTN_A, 0]));
+        assert!(game.state == BreakerState::Playing);
+        game
+    }
+
+    // Places the active ball just under a brick, moving up into it
+    fn aim_at(game: &mut BreakerGame, brick: usize) {
+        let field = &mut game.fields[0];
+        let br = &field.bricks[brick];
+        let ball = &mut field.balls[0];
+        ball.active = true;
+        ball.x = br.x + br.w / 2.0;
+        ball.y = br.y + br.h + ball.r + 1.0;
+        ball.dx = 0.0;
+        ball.dy = -4.0;
+    }
+
+    #[test]
+    fn difficulty_sets_lives() {
+        let mut game = BreakerGame::new(1);
+        let mut feed = InputFeed::default();
+        game.update(&feed.next([BTN_UP, 0]));
+        game.update(&feed.next([0, 0]));
+        game.update(&feed.next([BTN_A, 0]));
+
+        assert!(game.state == BreakerState::Playing);
+        assert_eq!(game.menu_selection, 0);
+        assert_eq!(game.fields[0].lives, 6);
+        assert!(!game.fields[0].bricks.is_empty());
+    }
+
+    #[test]
+    fn brick_hit_scores_and_bounces() {
+        let mut game = start_playing(1);
+        let last = game.fields[0].bricks.len() - 1;
+        aim_at(&mut game, last);
+
+        game.update(&InputFeed::default().next([0, 0]));
+
+        assert!(!game.fields[0].bricks[last].active);
+        assert_eq!(game.fields[0].score, 100);
+        assert!(game.fields[0].balls[0].dy > 0.0);
+    }
+
+    #[test]
+    fn clearing_last_brick_advances_level() {
+        let mut game = start_playing(1);
+        let last = game.fields[0].bricks.len() - 1;
+        for br in game.fields[0].bricks.iter_mut().take(last) {
+            br.active = false;
+        }
+        let speed = game.fields[0].ball_speed;
+        aim_at(&mut game, last);
+
+        game.update(&InputFeed::default().next([0, 0]));
+
+        assert_eq!(game.level, 2);
+        assert_eq!(game.fields[0].ball_speed, speed + 1.0);
+        assert!(!game.fields[0].balls[0].active);
+        assert!(game.fields[0].bricks.iter().all(|b| b.active));
+    }
+
+    #[test]
+    fn losing_last_life_ends_game() {
+        let mut game = start_playing(1);
+        game.fields[0].lives = 1;
+        let ball = &mut game.fields[0].balls[0];
+        ball.active = true;
+        ball.x = 20.0;
+        ball.y = 300.0;
+        ball.dx = 0.0;
+        ball.dy = 4.0;
+
+        game.update(&InputFeed::default().next([0, 0]));
+
+        assert_eq!(game.fields[0].lives, 0);
+        assert!(game.state == BreakerState::GameOver);
+    }
+
+    #[test]
+    fn caught_capsule_widens_paddle_until_it_runs_out() {
+        let mut game = start_playing(1);
+        let field = &mut game.fields[0];
+        let p = &field.paddles[0];
+        field.capsules.push(BreakerCapsule { x: p.x + p.w / 2.0, y: p.y, kind: BreakerPower::Wide });
+        let center = game.fields[0].paddles[0].x + game.fields[0].paddles[0].w / 2.0;
+
+        let mut feed = InputFeed::default();
+        game.update(&feed.next([0, 0]));
+        assert!(game.fields[0].capsules.is_empty());
+        assert_eq!(game.fields[0].paddles[0].w, 90.0);
+        assert_eq!(game.fields[0].paddles[0].x + game.fields[0].paddles[0].w / 2.0, center);
+
+        for _ in 0..BreakerPower::Wide.duration() {
+            game.update(&feed.next([0, 0]));
+        }
+        assert_eq!(game.fields[0].paddles[0].w, 60.0);
+    }
+
+    #[test]
+    fn multiball_splits_then_melts_back_to_one() {
+        let mut game = start_playing(1);
+        game.fields[0].grant_power(BreakerPower::Multiball);
+        assert_eq!(game.fields[0].balls.len(), 3);
+        assert!(game.fields[0].balls.iter().filter(|b| b.active).count() == 2);
+
+        game.fields[0].end_power(BreakerPower::Multiball);
+        assert_eq!(game.fields[0].balls.len(), 1);
+    }
+
+    #[test]
+    fn losing_one_of_several_balls_keeps_the_life() {
+        let mut game = start_playing(1);
+        game.fields[0].grant_power(BreakerPower::Multiball);
+        let lives = game.fields[0].lives;
+        game.fields[0].balls[1].y = 300.0;
+
+        game.update(&InputFeed::default().next([0, 0]));
+
+        assert_eq!(game.fields[0].lives, lives);
+        assert_eq!(game.fields[0].balls.len(), 2);
+    }
+
+    #[test]
+    fn sticky_paddle_holds_the_ball_until_a() {
+        let mut game = start_playing(1);
+        game.fields[0].grant_power(BreakerPower::Sticky);
+        let field = &mut game.fields[0];
+        let p = &field.paddles[0];
+        let ball = &mut field.balls[0];
+        ball.active = true;
+        ball.x = p.x + p.w / 2.0 + 10.0;
+        ball.y = p.y - ball.r + 1.0;
+        ball.dx = 0.0;
+        ball.dy = 4.0;
+
+        let mut feed = InputFeed::default();
+        game.update(&feed.next([0, 0]));
+        assert!(!game.fields[0].balls[0].active);
+        game.update(&feed.next([0, 0]));
+        assert!(!game.fields[0].balls[0].active);
+
+        game.update(&feed.next([BTN_A, 0]));
+        assert!(game.fields[0].balls[0].active);
+        assert!(game.fields[0].balls[0].dx > 0.0 && game.fields[0].balls[0].dy < 0.0);
+    }
+
+    #[test]
+    fn laser_shots_break_bricks() {
+        let mut game = start_playing(1);
+        game.fields[0].grant_power(BreakerPower::Laser);
+        let mut feed = InputFeed::default();
+        game.update(&feed.next([BTN_A, 0]));
+        assert_eq!(game.fields[0].shots.len(), 2);
+        // Keep the ball out of it
+        game.fields[0].balls[0].active = false;
+
+        for _ in 0..60 {
+            game.update(&feed.next([0, 0]));
+        }
+        assert!(game.fields[0].shots.is_empty());
+        assert!(game.fields[0].score >= 100);
+    }
+
+    // A single row of bricks 23px apart, like build_level spaces them
+    fn row_of(game: &mut BreakerGame, kinds: &[(BrickKind, i32)]) {
+        game.fields[0].bricks = kinds.iter().enumerate()
+            .map(|(i, (kind, hp))| BreakerBrick::new(100.0 + i as f32 * 23.0, 60.0, 18.0, 0xE74C3CFF, *kind, *hp))
+            .collect();
+    }
+
+    #[test]
+    fn tough_bricks_crack_before_breaking() {
+        let mut game = start_playing(1);
+        row_of(&mut game, &[(BrickKind::Gift, 3), (BrickKind::Gift, 1)]);
+        game.fields[0].hit_brick(0);
+        game.fields[0].hit_brick(0);
+        assert!(game.fields[0].bricks[0].active);
+        assert_eq!(game.fields[0].bricks[0].damage(), 2);
+        game.fields[0].hit_brick(0);
+        assert!(!game.fields[0].bricks[0].active);
+        assert_eq!(game.fields[0].score, 300);
+    }
+
+    #[test]
+    fn steel_never_breaks_and_never_blocks_the_clear() {
+        let mut game = start_playing(1);
+        row_of(&mut game, &[(BrickKind::Steel, 1), (BrickKind::Gift, 1)]);
+        for _ in 0..5 {
+            game.fields[0].hit_brick(0);
+        }
+        assert!(game.fields[0].bricks[0].active);
+        assert_eq!(game.fields[0].bricks_left(), 1);
+
+        aim_at(&mut game, 1);
+        game.update(&InputFeed::default().next([0, 0]));
+        assert_eq!(game.level, 2);
+    }
+
+    #[test]
+    fn ornaments_chain_through_neighbors_but_not_steel() {
+        let mut game = start_playing(1);
+        row_of(&mut game, &[
+            (BrickKind::Steel, 1), (BrickKind::Gift, 3), (BrickKind::Ornament, 1),
+            (BrickKind::Ornament, 1), (BrickKind::Gift, 1), (BrickKind::Gift, 1),
+        ]);
+        game.fields[0].hit_brick(2);
+
+        let active: Vec<bool> = game.fields[0].bricks.iter().map(|b| b.active).collect();
+        assert_eq!(active, vec![true, false, false, false, false, true]);
+        assert!(game.fields[0].shake > 0);
+    }
+
+    #[test]
+    fn moving_bricks_slide_back_and_forth() {
+        let mut brick = BreakerBrick::new(100.0, 60.0, 18.0, 0xE74C3CFF, BrickKind::Gift, 1);
+        brick.vx = 0.5;
+        let (mut lo, mut hi) = (brick.x, brick.x);
+        for _ in 0..400 {
+            brick.slide();
+            lo = lo.min(brick.x);
+            hi = hi.max(brick.x);
+        }
+        assert!(hi - 100.0 <= SLIDE_RANGE + 0.5 && 100.0 - lo <= SLIDE_RANGE + 0.5);
+        assert!(hi > 110.0 && lo < 90.0);
+    }
+
+    #[test]
+    fn levels_come_from_the_pack() {
+        let mut game = start_playing(7);
+        assert_eq!(game.fields[0].bricks.len(), BreakerLevel::from_pack(1).rows.iter().flat_map(|r| r.cells.iter().flatten()).count());
+        game.level = 12;
+        game.fields[0].build_level(game.level);
+        assert_eq!(game.fields[0].bricks_left(), BreakerLevel::from_pack(12).breakable());
+        assert!(game.fields[0].bricks.iter().any(|b| b.vx != 0.0));
+        assert!(game.fields[0].bricks.iter().all(|b| b.x >= 0.0 && b.x + b.w <= 512.0 && b.y + b.h < 230.0));
+    }
+
+    #[test]
+    fn clearing_levels_unlocks_the_next_in_the_level_select() {
+        let mut game = start_playing(1);
+        game.level = 4;
+        for br in game.fields[0].bricks.iter_mut().skip(1) {
+            br.active = false;
+        }
+        aim_at(&mut game, 0);
+        game.update(&InputFeed::default().next([0, 0]));
+        assert_eq!(game.level, 5);
+        assert_eq!(game.unlocked, 5);
+
+        // Back on the menu, A now opens the level select on the newest level
+        game.state = BreakerState::Menu;
+        let mut feed = InputFeed::default();
+        game.update(&feed.next([BTN_A, 0]));
+        assert!(game.state == BreakerState::LevelSelect);
+        assert_eq!(game.level_selection, 5);
+        game.update(&feed.next([BTN_RIGHT, 0]));
+        assert_eq!(game.level_selection, 5);
+        game.update(&feed.next([BTN_UP, 0]));
+        assert_eq!(game.level_selection, 5);
+        game.update(&feed.next([0, 0]));
+        game.update(&feed.next([BTN_LEFT, 0]));
+        game.update(&feed.next([BTN_A, 0]));
+        assert!(game.state == BreakerState::Playing);
+        assert_eq!(game.level, 4);
+        assert_eq!(game.fields[0].ball_speed, 7.0);
+    }
+
+    // Where the ball starts outside the brick and would end past it
+    #[test]
+    fn fast_ball_cannot_tunnel_through_a_brick() {
+        let mut game = start_playing(1);
+        row_of(&mut game, &[(BrickKind::Gift, 1), (BrickKind::Gift, 1)]);
+        aim_at(&mut game, 0);
+        game.fields[0].balls[0].y = 150.0;
+        game.fields[0].balls[0].dy = -120.0;
+
+        game.update(&InputFeed::default().next([0, 0]));
+
+        assert!(!game.fields[0].bricks[0].active);
+        assert!(game.fields[0].balls[0].dy > 0.0);
+        assert!(game.fields[0].balls[0].y > 78.0);
+    }
+
+    #[test]
+    fn side_hits_bounce_sideways() {
+        let mut game = start_playing(1);
+        row_of(&mut game, &[(BrickKind::Gift, 1), (BrickKind::Gift, 1)]);
+        let ball = &mut game.fields[0].balls[0];
+        ball.active = true;
+        ball.x = 93.0;
+        ball.y = 69.0;
+        ball.dx = 4.0;
+        ball.dy = 1.0;
+
+        game.update(&InputFeed::default().next([0, 0]));
+
+        assert!(!game.fields[0].bricks[0].active);
+        assert!(game.fields[0].balls[0].dx < 0.0);
+        assert!(game.fields[0].balls[0].dy > 0.0);
+    }
+
+    #[test]
+    fn one_frame_can_bounce_between_two_bricks() {
+        let mut game = start_playing(1);
+        game.fields[0].bricks = [60.0, 100.0, 300.0].iter()
+            .map(|&x| BreakerBrick::new(x, 60.0, 18.0, 0xE74C3CFF, BrickKind::Gift, 1))
+            .collect();
+        let ball = &mut game.fields[0].balls[0];
+        ball.active = true;
+        ball.x = 89.0;
+        ball.y = 69.0;
+        ball.dx = 30.0;
+        ball.dy = 0.0;
+
+        game.update(&InputFeed::default().next([0, 0]));
+
+        assert!(!game.fields[0].bricks[0].active && !game.fields[0].bricks[1].active);
+        assert!(game.fields[0].bricks[2].active);
+        assert!(game.fields[0].balls[0].x > 78.0 && game.fields[0].balls[0].x < 100.0);
+        assert_eq!(game.fields[0].score, 200);
+    }
+
+    #[test]
+    fn paddle_never_sends_the_ball_flat_or_straight_up() {
+        let mut game = start_playing(1);
+        for offset in [-60.0f32, 0.0, 60.0] {
+            let (px, py, pw) = (game.fields[0].paddles[0].x, game.fields[0].paddles[0].y, game.fields[0].paddles[0].w);
+            let ball = &mut game.fields[0].balls[0];
+            ball.active = true;
+            ball.x = px + pw / 2.0 + offset.clamp(-pw / 2.0, pw / 2.0);
+            ball.y = py - ball.r - 2.0;
+            ball.dx = 0.0;
+            ball.dy = 4.0;
+
+            game.update(&InputFeed::default().next([0, 0]));
+
+            let ball = &game.fields[0].balls[0];
+            let angle = ball.dx.atan2(-ball.dy).abs();
+            assert!(ball.dy < 0.0);
+            assert!((MIN_BOUNCE_ANGLE - 1e-4..=MAX_BOUNCE_ANGLE + 1e-4).contains(&angle));
+        }
+    }
+
+    // Picks who's playing on the menu with Left/Right, then starts
+    fn start_with(players: BreakerPlayers) -> BreakerGame {
+        let mut game = BreakerGame::new(1);
+        let mut feed = InputFeed::default();
+        let steps = BreakerPlayers::ALL.iter().position(|p| *p == players).unwrap();
+        for _ in 0..steps {
+            game.update(&feed.next([BTN_RIGHT, 0]));
+            game.update(&feed.next([0, 0]));
+        }
+        game.update(&feed.next([BTN_A, 0]));
+        assert!(game.state == BreakerState::Playing);
+        assert!(game.players == players);
+        game
+    }
+
+    #[test]
+    fn menu_picks_coop_or_versus() {
+        let coop = start_with(BreakerPlayers::Coop);
+        assert_eq!(coop.fields.len(), 1);
+        assert_eq!(coop.fields[0].paddles.len(), 2);
+        assert_eq!(coop.fields[0].balls.len(), 2);
+
+        let versus = start_with(BreakerPlayers::Versus);
+        let (a, b) = (&versus.fields[0], &versus.fields[1]);
+        assert_eq!((a.left, a.right, b.left, b.right), (0.0, 256.0, 256.0, 512.0));
+        assert_eq!((a.lives, b.lives), (3, 3));
+        // Player 2's wall is player 1's, mirrored
+        assert_eq!(a.bricks.len(), b.bricks.len());
+        for br in &a.bricks {
+            let mirror_x = 512.0 - br.x - br.w;
+            assert!(b.bricks.iter().any(|o| (o.x - mirror_x).abs() < 0.01 && o.y == br.y && o.color == br.color));
+        }
+        assert!(a.bricks.iter().chain(&b.bricks).all(|br| br.x >= 0.0 && br.x + br.w <= 512.0));
+        assert!(a.bricks.iter().all(|br| br.x + br.w <= 256.0));
+    }
+
+    #[test]
+    fn coop_paddles_each_follow_their_pad_and_never_cross() {
+        let mut game = start_with(BreakerPlayers::Coop);
+        let mut feed = InputFeed::default();
+        let p1_x = game.fields[0].paddles[0].x;
+        for _ in 0..100 {
+            game.update(&feed.next([0, BTN_LEFT]));
+        }
+        let field = &game.fields[0];
+        assert_eq!(field.paddles[0].x, p1_x);
+        assert_eq!(field.paddles[1].x, p1_x + field.paddles[0].w);
+
+        // Player 2's A only launches player 2's ball
+        game.update(&feed.next([0, BTN_A]));
+        let balls = &game.fields[0].balls;
+        assert!(!balls[0].active && balls[1].active);
+    }
+
+    #[test]
+    fn coop_multiball_ending_leaves_each_player_a_ball() {
+        let mut game = start_with(BreakerPlayers::Coop);
+        let field = &mut game.fields[0];
+        // Player 1's ball is in the air, player 2 still holds theirs
+        field.launch_ball(0);
+        field.balls[0].y = 150.0;
+        field.split_balls();
+        assert!(field.balls.len() > 2);
+        field.balls[2].y = 200.0;
+
+        field.end_power(BreakerPower::Multiball);
+        assert_eq!(field.balls.len(), 2);
+        let p1 = field.balls.iter().find(|b| b.paddle == 0).unwrap();
+        let p2 = field.balls.iter().find(|b| b.paddle == 1).unwrap();
+        assert!(p1.active && p1.y == 200.0);
+        assert!(!p2.active);
+    }
+
+    #[test]
+    fn coop_loses_a_life_only_once_both_balls_are_gone() {
+        let mut game = start_with(BreakerPlayers::Coop);
+        let mut feed = InputFeed::default();
+        game.fields[0].balls[0].active = true;
+        game.fields[0].balls[0].y = 300.0;
+        game.update(&feed.next([0, 0]));
+        assert_eq!(game.fields[0].lives, 3);
+        assert_eq!(game.fields[0].balls.len(), 1);
+
+        game.fields[0].balls[0].active = true;
+        game.fields[0].balls[0].y = 300.0;
+        game.update(&feed.next([0, 0]));
+        assert_eq!(game.fields[0].lives, 2);
+        assert_eq!(game.fields[0].balls.len(), 2);
+    }
+
+    #[test]
+    fn versus_cleared_rows_send_garbage_across() {
+        let mut game = start_with(BreakerPlayers::Versus);
+        let size = game.fields[0].brick_size().0;
+        game.fields[0].bricks = vec![
+            BreakerBrick::new(20.0, 40.0, size, 0xE74C3CFF, BrickKind::Gift, 1),
+            BreakerBrick::new(20.0, 60.0, size, 0xE74C3CFF, BrickKind::Gift, 1),
+            BreakerBrick::new(40.0, 60.0, size, 0xE74C3CFF, BrickKind::Gift, 1),
+        ];
+        let before = game.fields[1].bricks.len();
+        let lowest = game.fields[1].bricks.iter().map(|b| b.y).fold(0.0, f32::max);
+
+        game.fields[0].hit_brick(1);
+        assert_eq!(game.fields[0].cleared_rows, 0);
+        game.fields[0].hit_brick(2);
+        game.update(&InputFeed::default().next([0, 0]));
+
+        let garbage: Vec<&BreakerBrick> = game.fields[1].bricks[before..].iter().collect();
+        assert!(garbage.len() >= LEVEL_COLS - 2 && garbage.len() < LEVEL_COLS);
+        assert!(garbage.iter().all(|b| b.color == GARBAGE_COLOR && b.y > lowest));
+        assert!(garbage.iter().all(|b| b.x >= 256.0 && b.x + b.w <= 512.0));
+        assert_eq!(game.fields[0].cleared_rows, 0);
+        assert!(game.state == BreakerState::Playing);
+    }
+
+    #[test]
+    fn versus_ends_when_a_wall_buries_its_player() {
+        let mut game = start_with(BreakerPlayers::Versus);
+        let mut feed = InputFeed::default();
+        let mut rows = 0;
+        while !game.fields[1].buried() {
+            game.fields[1].add_garbage_row();
+            rows += 1;
+        }
+        assert!(rows > 3);
+
+        game.update(&feed.next([0, 0]));
+        assert!(game.state == BreakerState::GameOver);
+        assert_eq!(game.winner, Some(0));
+    }
+
+    #[test]
+    fn versus_ends_when_a_player_clears_their_wall_or_runs_out_of_lives() {
+        let mut game = start_with(BreakerPlayers::Versus);
+        for br in &mut game.fields[1].bricks {
+            br.active = false;
+        }
+        game.update(&InputFeed::default().next([0, 0]));
+        assert_eq!(game.winner, Some(1));
+
+        let mut game = start_with(BreakerPlayers::Versus);
+        game.fields[0].lives = 0;
+        game.fields[1].lives = 0;
+        game.update(&InputFeed::default().next([0, 0]));
+        assert!(game.state == BreakerState::GameOver);
+        assert_eq!(game.winner, None);
     }
 }
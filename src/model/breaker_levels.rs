@@ -0,0 +1,195 @@
+use std::sync::OnceLock;
+use crate::model::breaker_game::BrickKind;
+
+// The wall is at most this many bricks across and this many rows deep
+pub const LEVEL_COLS: usize = 20;
+pub const LEVEL_ROWS: usize = 8;
+
+// The built-in Christmas pack, one file per level under levels/breaker/
+pub const PACK: [&str; 20] = [
+    include_str!("../../levels/breaker/01_tree.txt"),
+    include_str!("../../levels/breaker/02_candy_cane.txt"),
+    include_str!("../../levels/breaker/03_present.txt"),
+    include_str!("../../levels/breaker/04_stocking.txt"),
+    include_str!("../../levels/breaker/05_bell.txt"),
+    include_str!("../../levels/breaker/06_snowman.txt"),
+    include_str!("../../levels/breaker/07_ornaments.txt"),
+    include_str!("../../levels/breaker/08_holly.txt"),
+    include_str!("../../levels/breaker/09_star.txt"),
+    include_str!("../../levels/breaker/10_wreath.txt"),
+    include_str!("../../levels/breaker/11_gingerbread.txt"),
+    include_str!("../../levels/breaker/12_santa_hat.txt"),
+    include_str!("../../levels/breaker/13_mittens.txt"),
+    include_str!("../../levels/breaker/14_snowflake.txt"),
+    include_str!("../../levels/breaker/15_reindeer.txt"),
+    include_str!("../../levels/breaker/16_chimney.txt"),
+    include_str!("../../levels/breaker/17_sleigh.txt"),
+    include_str!("../../levels/breaker/18_noel.txt"),
+    include_str!("../../levels/breaker/19_north_pole.txt"),
+    include_str!("../../levels/breaker/20_workshop.txt"),
+];
+
+// One brick as the level file describes it
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct BrickSpec {
+    pub kind: BrickKind,
+    pub hp: i32,
+    pub color: u32,
+}
+
+impl BrickSpec {
+    // Lowercase letters are one-hit gifts in that color, capitals take two hits,
+    // * is a three-hit gold gift, S steel and O an ornament. `.` or a space is a gap.
+    pub fn from_char(c: char) -> Option<Option<BrickSpec>> {
+        let gift = |hp, color| Some(Some(BrickSpec { kind: BrickKind::Gift, hp, color }));
+        let color = match c.to_ascii_lowercase() {
+            'r' => 0xE74C3CFF,
+            'g' => 0x2ECC71FF,
+            'b' => 0x3498DBFF,
+            'p' => 0x9B59B6FF,
+            'y' => 0xF1C40FFF,
+            'w' => 0xECF0F1FF,
+            'n' => 0x8D6E63FF,
+            _ => 0,
+        };
+        match c {
+            '.' | ' ' => Some(None),
+            '*' => gift(3, 0xFFD700FF),
+            'S' => Some(Some(BrickSpec { kind: BrickKind::Steel, hp: 1, color: 0x95A5A6FF })),
+            'O' => Some(Some(BrickSpec { kind: BrickKind::Ornament, hp: 1, color: 0xE74C3CFF })),
+            _ if color == 0 => None,
+            _ if c.is_ascii_uppercase() => gift(2, color),
+            _ => gift(1, color),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct LevelRow {
+    pub cells: Vec<Option<BrickSpec>>,
+    // Rows ending in `~` slide back and forth
+    pub slides: bool,
+}
+
+// A parsed level file:
+//
+//   name: CHRISTMAS TREE
+//   ; comment
+//   ....gggg....
+//   ...gggggg...~
+//
+// Narrow levels are centered on the widest row.
+#[derive(Clone, PartialEq, Debug)]
+pub struct BreakerLevel {
+    pub name: String,
+    pub rows: Vec<LevelRow>,
+}
+
+impl BreakerLevel {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut name = "UNTITLED".to_string();
+        let mut rows = vec![];
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim_end();
+            if line.trim().is_empty() || line.starts_with(';') {
+                continue;
+            }
+            if let Some(value) = line.strip_prefix("name:") {
+                name = value.trim().to_uppercase();
+                continue;
+            }
+
+            let (grid, slides) = match line.strip_suffix('~') {
+                Some(grid) => (grid, true),
+                None => (line, false),
+            };
+            if grid.chars().count() > LEVEL_COLS {
+                return Err(format!("line {}: wider than {} bricks", n + 1, LEVEL_COLS));
+            }
+            let cells = grid.chars()
+                .map(|c| BrickSpec::from_char(c).ok_or_else(|| format!("line {}: unknown brick '{}'", n + 1, c)))
+                .collect::<Result<Vec<_>, _>>()?;
+            rows.push(LevelRow { cells, slides });
+        }
+
+        if rows.len() > LEVEL_ROWS {
+            return Err(format!("more than {} rows", LEVEL_ROWS));
+        }
+        let level = Self { name, rows };
+        if level.breakable() == 0 {
+            return Err("no bricks to break".to_string());
+        }
+        Ok(level)
+    }
+
+    // Level `n` (from 1) of the built-in pack; it loops after the last one.
+    // The whole pack is parsed the first time any of it is asked for.
+    pub fn from_pack(n: i32) -> &'static Self {
+        static LEVELS: OnceLock<Vec<BreakerLevel>> = OnceLock::new();
+        let levels = LEVELS.get_or_init(|| PACK.iter().map(|text| Self::parse(text).expect("built-in level")).collect());
+        &levels[(n - 1).rem_euclid(PACK.len() as i32) as usize]
+    }
+
+    pub fn name_in_pack(n: i32) -> &'static str {
+        &Self::from_pack(n).name
+    }
+
+    pub fn width(&self) -> usize {
+        self.rows.iter().map(|r| r.cells.len()).max().unwrap_or(0)
+    }
+
+    // Bricks that have to go before the level is clear
+    pub fn breakable(&self) -> usize {
+        self.rows.iter()
+            .flat_map(|r| r.cells.iter().flatten())
+            .filter(|b| b.kind != BrickKind::Steel)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_names_kinds_and_sliding_rows() {
+        let level = BreakerLevel::parse("name: test\n; comment\n\nrG*.\n.SO~\n").unwrap();
+        assert_eq!(level.name, "TEST");
+        assert_eq!(level.rows.len(), 2);
+        assert_eq!(level.width(), 4);
+        let first = &level.rows[0].cells;
+        assert_eq!(first[0].unwrap().hp, 1);
+        assert_eq!(first[1].unwrap().hp, 2);
+        assert_eq!(first[2].unwrap().hp, 3);
+        assert_eq!(first[3], None);
+        assert!(!level.rows[0].slides && level.rows[1].slides);
+        assert_eq!(level.rows[1].cells[1].unwrap().kind, BrickKind::Steel);
+        assert_eq!(level.rows[1].cells[2].unwrap().kind, BrickKind::Ornament);
+        assert_eq!(level.breakable(), 4);
+    }
+
+    #[test]
+    fn rejects_bad_levels() {
+        assert!(BreakerLevel::parse("rrx").unwrap_err().contains("unknown brick 'x'"));
+        assert!(BreakerLevel::parse(&"r".repeat(LEVEL_COLS + 1)).is_err());
+        assert!(BreakerLevel::parse(&"r\n".repeat(LEVEL_ROWS + 1)).is_err());
+        assert!(BreakerLevel::parse("SSS\n...").is_err());
+    }
+
+    #[test]
+    fn whole_pack_parses_and_gets_harder() {
+        let levels: Vec<BreakerLevel> = PACK.iter().map(|text| BreakerLevel::parse(text).unwrap()).collect();
+        let has = |level: &BreakerLevel, kind| level.rows.iter().flat_map(|r| r.cells.iter().flatten()).any(|b| b.kind == kind);
+        // The first level is one-hit gifts only
+        assert!(levels[0].rows.iter().flat_map(|r| r.cells.iter().flatten()).all(|b| b.kind == BrickKind::Gift && b.hp == 1));
+        assert!(levels.iter().any(|l| has(l, BrickKind::Steel)));
+        assert!(levels.iter().any(|l| has(l, BrickKind::Ornament)));
+        assert!(levels.iter().any(|l| l.rows.iter().any(|r| r.slides)));
+        let mut names: Vec<&str> = levels.iter().map(|l| l.name.as_str()).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), PACK.len());
+        assert_eq!(BreakerLevel::from_pack(21), &levels[0]);
+        assert!(std::ptr::eq(BreakerLevel::from_pack(3), BreakerLevel::from_pack(23)));
+    }
+}
@@ -0,0 +1,178 @@
+// Paddle bounces leave at least this far from straight up (radians), so the
+// ball never loops up and down forever...
+pub const MIN_BOUNCE_ANGLE: f32 = 0.2;
+// ...and at most this far, so it never skims along flat
+pub const MAX_BOUNCE_ANGLE: f32 = 1.05;
+
+// Where a moving ball first touches a brick
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct SweepHit {
+    // Share of the move made before touching, 0..=1
+    pub t: f32,
+    // Unit vector out of the brick at the touching point
+    pub normal: (f32, f32),
+}
+
+// Sweeps a ball of radius `r` from `pos` by `vel` against the box `rect`
+// (x, y, w, h). Faces are tested on the box grown by `r`, and the grown box's
+// corners are rounded so corner hits bounce off at the right angle.
+// Moving away from a box it already overlaps isn't a hit.
+pub fn sweep_ball(pos: (f32, f32), vel: (f32, f32), r: f32, rect: (f32, f32, f32, f32)) -> Option<SweepHit> {
+    let (left, top, right, bottom) = (rect.0, rect.1, rect.0 + rect.2, rect.1 + rect.3);
+
+    // Slab test against the grown box
+    let mut t_enter = f32::NEG_INFINITY;
+    let mut t_exit = f32::INFINITY;
+    let mut normal = (0.0, 0.0);
+    for (p, v, lo, hi, axis) in [(pos.0, vel.0, left - r, right + r, 0), (pos.1, vel.1, top - r, bottom + r, 1)] {
+        if v == 0.0 {
+            if p <= lo || p >= hi {
+                return None;
+            }
+            continue;
+        }
+        let (t0, t1) = if v > 0.0 { ((lo - p) / v, (hi - p) / v) } else { ((hi - p) / v, (lo - p) / v) };
+        if t0 > t_enter {
+            t_enter = t0;
+            normal = if axis == 0 { (-v.signum(), 0.0) } else { (0.0, -v.signum()) };
+        }
+        t_exit = t_exit.min(t1);
+    }
+    if t_enter > t_exit || t_exit <= 0.0 || t_enter > 1.0 {
+        return None;
+    }
+
+    // Starting inside, the way out is through the nearest face
+    if t_enter < 0.0 {
+        let faces = [
+            (pos.0 - (left - r), (-1.0, 0.0)),
+            (right + r - pos.0, (1.0, 0.0)),
+            (pos.1 - (top - r), (0.0, -1.0)),
+            (bottom + r - pos.1, (0.0, 1.0)),
+        ];
+        normal = faces.iter().min_by(|a, b| a.0.total_cmp(&b.0)).map(|f| f.1).unwrap_or(normal);
+    }
+
+    // Touching point on the grown box; beside a corner it has to meet the rounded part
+    let t = t_enter.max(0.0);
+    let (hx, hy) = (pos.0 + vel.0 * t, pos.1 + vel.1 * t);
+    let cx = if hx < left { Some(left) } else if hx > right { Some(right) } else { None };
+    let cy = if hy < top { Some(top) } else if hy > bottom { Some(bottom) } else { None };
+    if let (Some(cx), Some(cy)) = (cx, cy) {
+        return sweep_corner(pos, vel, r, (cx, cy));
+    }
+
+    // Already inside counts only while still moving in
+    if vel.0 * normal.0 + vel.1 * normal.1 >= 0.0 {
+        return None;
+    }
+    Some(SweepHit { t, normal })
+}
+
+// The ball against a single corner point: a ray against a circle of radius `r`
+fn sweep_corner(pos: (f32, f32), vel: (f32, f32), r: f32, corner: (f32, f32)) -> Option<SweepHit> {
+    let (ox, oy) = (pos.0 - corner.0, pos.1 - corner.1);
+    let a = vel.0 * vel.0 + vel.1 * vel.1;
+    let b = 2.0 * (ox * vel.0 + oy * vel.1);
+    let c = ox * ox + oy * oy - r * r;
+    if a == 0.0 {
+        return None;
+    }
+    let t = if c <= 0.0 {
+        // Overlapping already
+        0.0
+    } else {
+        let disc = b * b - 4.0 * a * c;
+        if disc < 0.0 {
+            return None;
+        }
+        (-b - disc.sqrt()) / (2.0 * a)
+    };
+    if !(0.0..=1.0).contains(&t) {
+        return None;
+    }
+    let (nx, ny) = (ox + vel.0 * t, oy + vel.1 * t);
+    let len = (nx * nx + ny * ny).sqrt();
+    if len == 0.0 {
+        return None;
+    }
+    let normal = (nx / len, ny / len);
+    if vel.0 * normal.0 + vel.1 * normal.1 >= 0.0 {
+        return None;
+    }
+    Some(SweepHit { t, normal })
+}
+
+// Mirrors a velocity off a surface with the given unit normal
+pub fn reflect(vel: (f32, f32), normal: (f32, f32)) -> (f32, f32) {
+    let dot = vel.0 * normal.0 + vel.1 * normal.1;
+    (vel.0 - 2.0 * dot * normal.0, vel.1 - 2.0 * dot * normal.1)
+}
+
+// Paddle bounce at `speed`: `hit_point` (from the paddle's middle) * 0.2 steers it
+// as before, within MIN/MAX_BOUNCE_ANGLE. A dead-center hit keeps its old side.
+pub fn paddle_bounce(hit_point: f32, speed: f32, old_dx: f32) -> (f32, f32) {
+    let steer = hit_point * 0.2;
+    let side = if steer != 0.0 { steer.signum() } else if old_dx < 0.0 { -1.0 } else { 1.0 };
+    let angle = steer.atan2(speed).abs().clamp(MIN_BOUNCE_ANGLE, MAX_BOUNCE_ANGLE);
+    (side * angle.sin() * speed, -angle.cos() * speed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BRICK: (f32, f32, f32, f32) = (100.0, 100.0, 18.0, 18.0);
+
+    #[test]
+    fn hits_each_face_with_its_own_normal() {
+        let from_below = sweep_ball((109.0, 130.0), (0.0, -10.0), 5.0, BRICK).unwrap();
+        assert_eq!(from_below.normal, (0.0, 1.0));
+        assert!((from_below.t - 0.7).abs() < 1e-4);
+        assert_eq!(sweep_ball((109.0, 90.0), (0.0, 10.0), 5.0, BRICK).unwrap().normal, (0.0, -1.0));
+        assert_eq!(sweep_ball((90.0, 109.0), (10.0, 1.0), 5.0, BRICK).unwrap().normal, (-1.0, 0.0));
+        assert_eq!(sweep_ball((128.0, 109.0), (-10.0, 0.0), 5.0, BRICK).unwrap().normal, (1.0, 0.0));
+    }
+
+    #[test]
+    fn fast_balls_cannot_tunnel_through() {
+        // Starts and ends clear of the brick, crossing it in between
+        let hit = sweep_ball((109.0, 140.0), (0.0, -60.0), 5.0, BRICK).unwrap();
+        assert_eq!(hit.normal, (0.0, 1.0));
+        assert!(sweep_ball((140.0, 140.0), (0.0, -60.0), 5.0, BRICK).is_none());
+    }
+
+    #[test]
+    fn corners_bounce_diagonally_and_near_misses_miss() {
+        // Heading straight for the bottom-right corner
+        let hit = sweep_ball((130.0, 130.0), (-10.0, -10.0), 5.0, BRICK).unwrap();
+        let diag = std::f32::consts::FRAC_1_SQRT_2;
+        assert!((hit.normal.0 - diag).abs() < 1e-3 && (hit.normal.1 - diag).abs() < 1e-3);
+        let out = reflect((-10.0, -10.0), hit.normal);
+        assert!(out.0 > 9.9 && out.1 > 9.9);
+
+        // Inside the grown box's square corner, but clear of the rounded one
+        assert!(sweep_ball((124.0, 130.0), (0.0, -10.0), 5.0, BRICK).is_none());
+    }
+
+    #[test]
+    fn moving_away_is_not_a_hit() {
+        assert!(sweep_ball((109.0, 120.0), (0.0, 4.0), 5.0, BRICK).is_none());
+        assert!(sweep_ball((109.0, 120.0), (0.0, -4.0), 5.0, BRICK).is_some());
+    }
+
+    #[test]
+    fn paddle_angles_stay_within_limits() {
+        for hit_point in [-45.0, -10.0, -0.5, 0.0, 0.5, 10.0, 45.0] {
+            let (dx, dy) = paddle_bounce(hit_point, 5.0, 1.0);
+            let angle = dx.atan2(-dy).abs();
+            assert!(dy < 0.0);
+            assert!((MIN_BOUNCE_ANGLE - 1e-4..=MAX_BOUNCE_ANGLE + 1e-4).contains(&angle), "{}", hit_point);
+            assert!(((dx * dx + dy * dy).sqrt() - 5.0).abs() < 1e-4);
+            if hit_point != 0.0 {
+                assert_eq!(dx.signum(), hit_point.signum());
+            }
+        }
+        assert!(paddle_bounce(0.0, 5.0, -2.0).0 < 0.0);
+    }
+}
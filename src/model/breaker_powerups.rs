@@ -0,0 +1,241 @@
+use crate::model::rng::Rng;
+
+// Chance (out of 100) that a broken brick drops a capsule
+pub const CAPSULE_CHANCE: u32 = 15;
+pub const CAPSULE_W: f32 = 18.0;
+pub const CAPSULE_H: f32 = 8.0;
+// Most balls in play at once, however many multiballs are caught
+pub const MAX_BALLS: usize = 12;
+
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub enum BreakerPower {
+    // Every ball splits in three; the extras melt when it runs out
+    Multiball,
+    Wide,
+    Narrow,
+    // The paddle catches balls until A lets them go
+    Sticky,
+    // A fires from both ends of the paddle
+    Laser,
+    Slow,
+}
+
+impl BreakerPower {
+    pub const ALL: [BreakerPower; 6] = [
+        BreakerPower::Multiball, BreakerPower::Wide, BreakerPower::Narrow,
+        BreakerPower::Sticky, BreakerPower::Laser, BreakerPower::Slow,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BreakerPower::Multiball => "MULTI",
+            BreakerPower::Wide => "WIDE",
+            BreakerPower::Narrow => "NARROW",
+            BreakerPower::Sticky => "STICKY",
+            BreakerPower::Laser => "LASER",
+            BreakerPower::Slow => "SLOW",
+        }
+    }
+
+    // Letter on the capsule
+    pub fn icon(&self) -> &'static str {
+        match self {
+            BreakerPower::Multiball => "M",
+            BreakerPower::Wide => "W",
+            BreakerPower::Narrow => "N",
+            BreakerPower::Sticky => "C",
+            BreakerPower::Laser => "L",
+            BreakerPower::Slow => "S",
+        }
+    }
+
+    pub fn color(&self) -> u32 {
+        match self {
+            BreakerPower::Multiball => 0x3498DBFF,
+            BreakerPower::Wide => 0x2ECC71FF,
+            BreakerPower::Narrow => 0x95A5A6FF,
+            BreakerPower::Sticky => 0xF1C40FFF,
+            BreakerPower::Laser => 0xE74C3CFF,
+            BreakerPower::Slow => 0x9B59B6FF,
+        }
+    }
+
+    // Frames it lasts once caught
+    pub fn duration(&self) -> u32 {
+        match self {
+            BreakerPower::Multiball => 900,
+            BreakerPower::Wide | BreakerPower::Narrow => 720,
+            BreakerPower::Sticky => 600,
+            BreakerPower::Laser => 480,
+            BreakerPower::Slow => 600,
+        }
+    }
+
+    // Relative drop weights; the narrow paddle is the one to dodge
+    fn weight(&self) -> u32 {
+        match self {
+            BreakerPower::Multiball => 20,
+            BreakerPower::Wide => 20,
+            BreakerPower::Narrow => 10,
+            BreakerPower::Sticky => 15,
+            BreakerPower::Laser => 15,
+            BreakerPower::Slow => 20,
+        }
+    }
+
+    pub fn roll_drop(rng: &mut Rng) -> Option<BreakerPower> {
+        if rng.u32() % 100 >= CAPSULE_CHANCE {
+            return None;
+        }
+        let total: u32 = Self::ALL.iter().map(|k| k.weight()).sum();
+        let mut roll = rng.u32() % total;
+        for kind in Self::ALL {
+            if roll < kind.weight() {
+                return Some(kind);
+            }
+            roll -= kind.weight();
+        }
+        None
+    }
+}
+
+// A capsule falling from a broken brick; x, y is its center
+#[turbo::serialize]
+pub struct BreakerCapsule {
+    pub x: f32,
+    pub y: f32,
+    pub kind: BreakerPower,
+}
+
+impl BreakerCapsule {
+    // False once it has fallen past the paddle
+    pub fn update(&mut self) -> bool {
+        self.y += 1.5;
+        self.y - CAPSULE_H / 2.0 < 288.0
+    }
+}
+
+// Frames left on each power, indexed like BreakerPower::ALL
+#[turbo::serialize]
+#[derive(PartialEq)]
+pub struct BreakerPowers {
+    pub timers: [u32; 6],
+}
+
+impl Default for BreakerPowers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BreakerPowers {
+    pub fn new() -> Self {
+        Self { timers: [0; 6] }
+    }
+
+    pub fn left(&self, kind: BreakerPower) -> u32 {
+        self.timers[kind as usize]
+    }
+
+    pub fn is_active(&self, kind: BreakerPower) -> bool {
+        self.left(kind) > 0
+    }
+
+    // Starts (or restarts) a power; wide and narrow cancel each other
+    pub fn grant(&mut self, kind: BreakerPower) {
+        match kind {
+            BreakerPower::Wide => self.timers[BreakerPower::Narrow as usize] = 0,
+            BreakerPower::Narrow => self.timers[BreakerPower::Wide as usize] = 0,
+            _ => {},
+        }
+        self.timers[kind as usize] = kind.duration();
+    }
+
+    // Counts every timer down; returns the powers that just ran out
+    pub fn tick(&mut self) -> Vec<BreakerPower> {
+        let mut ended = vec![];
+        for kind in BreakerPower::ALL {
+            let timer = &mut self.timers[kind as usize];
+            if *timer > 0 {
+                *timer -= 1;
+                if *timer == 0 {
+                    ended.push(kind);
+                }
+            }
+        }
+        ended
+    }
+
+    // Powers still running, for the HUD
+    pub fn running(&self) -> Vec<BreakerPower> {
+        BreakerPower::ALL.into_iter().filter(|k| self.is_active(*k)).collect()
+    }
+
+    pub fn paddle_width(&self) -> f32 {
+        if self.is_active(BreakerPower::Wide) {
+            90.0
+        } else if self.is_active(BreakerPower::Narrow) {
+            40.0
+        } else {
+            60.0
+        }
+    }
+
+    // How far the balls move each frame, as a share of their speed
+    pub fn ball_step(&self) -> f32 {
+        if self.is_active(BreakerPower::Slow) { 0.6 } else { 1.0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capsules_drop_sometimes_and_every_kind_turns_up() {
+        let mut rng = Rng::new(4);
+        let drops: Vec<BreakerPower> = (0..4000).filter_map(|_| BreakerPower::roll_drop(&mut rng)).collect();
+        assert!(drops.len() > 450 && drops.len() < 750, "{}", drops.len());
+        for kind in BreakerPower::ALL {
+            assert!(drops.contains(&kind), "{}", kind.name());
+        }
+    }
+
+    #[test]
+    fn wide_and_narrow_cancel_each_other() {
+        let mut powers = BreakerPowers::new();
+        powers.grant(BreakerPower::Wide);
+        assert_eq!(powers.paddle_width(), 90.0);
+        powers.grant(BreakerPower::Narrow);
+        assert!(!powers.is_active(BreakerPower::Wide));
+        assert_eq!(powers.paddle_width(), 40.0);
+    }
+
+    #[test]
+    fn timers_report_when_they_run_out() {
+        let mut powers = BreakerPowers::new();
+        powers.grant(BreakerPower::Laser);
+        powers.grant(BreakerPower::Slow);
+        assert_eq!(powers.running(), vec![BreakerPower::Laser, BreakerPower::Slow]);
+        assert_eq!(powers.ball_step(), 0.6);
+
+        let mut ended = vec![];
+        for _ in 0..BreakerPower::Slow.duration() {
+            ended.extend(powers.tick());
+        }
+        assert_eq!(ended, vec![BreakerPower::Laser, BreakerPower::Slow]);
+        assert!(powers.running().is_empty());
+        assert_eq!(powers.ball_step(), 1.0);
+    }
+
+    #[test]
+    fn capsules_fall_until_past_the_paddle() {
+        let mut capsule = BreakerCapsule { x: 100.0, y: 100.0, kind: BreakerPower::Wide };
+        let mut frames = 0;
+        while capsule.update() {
+            frames += 1;
+        }
+        assert!(capsule.y > 288.0 && frames < 200);
+    }
+}
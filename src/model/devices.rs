@@ -0,0 +1,231 @@
+use turbo::*;
+use crate::model::input::{Pad, MAX_PLAYERS};
+
+// Where a player's buttons come from. Turbo maps both WASD and the arrows onto
+// gamepad 1 (and I/J/K/L onto gamepad 2's up/down/left/right), so the keyboard layouts read their keys directly instead; that way
+// two players can share one keyboard. Their action keys avoid the ones Turbo
+// already gives to the gamepads.
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub enum InputDevice {
+    Gamepad(u8),
+    Arrows,
+    Wasd,
+    Ijkl,
+}
+
+impl InputDevice {
+    pub const ALL: [InputDevice; 7] = [
+        InputDevice::Gamepad(0),
+        InputDevice::Gamepad(1),
+        InputDevice::Gamepad(2),
+        InputDevice::Gamepad(3),
+        InputDevice::Arrows,
+        InputDevice::Wasd,
+        InputDevice::Ijkl,
+    ];
+
+    // Single player, and multiplayer until someone joins with something else
+    pub const DEFAULT: [InputDevice; MAX_PLAYERS] = [
+        InputDevice::Gamepad(0),
+        InputDevice::Gamepad(1),
+        InputDevice::Gamepad(2),
+        InputDevice::Gamepad(3),
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            InputDevice::Gamepad(0) => "GAMEPAD 1",
+            InputDevice::Gamepad(1) => "GAMEPAD 2",
+            InputDevice::Gamepad(2) => "GAMEPAD 3",
+            InputDevice::Gamepad(_) => "GAMEPAD 4",
+            InputDevice::Arrows => "ARROW KEYS",
+            InputDevice::Wasd => "WASD KEYS",
+            InputDevice::Ijkl => "IJKL KEYS",
+        }
+    }
+
+    // Two devices one key press can drive: the same device, or a gamepad and
+    // a keyboard layout Turbo also maps onto it
+    pub fn shares_keys(&self, other: InputDevice) -> bool {
+        use InputDevice::*;
+        *self == other
+            || matches!(
+                (*self, other),
+                (Gamepad(0), Arrows | Wasd) | (Arrows | Wasd, Gamepad(0)) | (Gamepad(1), Ijkl) | (Ijkl, Gamepad(1))
+            )
+    }
+
+    // Keys for the join screen and instructions
+    pub fn legend(&self) -> &'static str {
+        match self {
+            InputDevice::Gamepad(0) => "Stick or Arrows/WASD, A: Z, B: X, Start: Space",
+            InputDevice::Gamepad(1) => "Stick or IJKL (Up Down Left Right), A: M, B: Comma, Start: [",
+            InputDevice::Gamepad(_) => "Stick and pad buttons",
+            InputDevice::Arrows => "Arrows, A: Right Ctrl, B: Right Alt, Start: \\",
+            InputDevice::Wasd => "W A S D, A: F, B: G, Start: Q",
+            InputDevice::Ijkl => "I J K L, A: O, B: P, Start: U",
+        }
+    }
+
+    // Buttons currently held, as replay bits
+    pub fn poll(&self) -> u16 {
+        let kb = keyboard::get();
+        match self {
+            InputDevice::Gamepad(index) => Pad::poll(*index as usize),
+            InputDevice::Arrows => key_bits([
+                kb.arrow_up().pressed(), kb.arrow_down().pressed(), kb.arrow_left().pressed(), kb.arrow_right().pressed(),
+                kb.control_right().pressed(), kb.alt_right().pressed(), kb.delete().pressed(), kb.end().pressed(),
+                kb.backslash().pressed(), kb.page_down().pressed(),
+            ]),
+            InputDevice::Wasd => key_bits([
+                kb.key_w().pressed(), kb.key_s().pressed(), kb.key_a().pressed(), kb.key_d().pressed(),
+                kb.key_f().pressed(), kb.key_g().pressed(), kb.key_r().pressed(), kb.key_t().pressed(),
+                kb.key_q().pressed(), kb.key_e().pressed(),
+            ]),
+            InputDevice::Ijkl => key_bits([
+                kb.key_i().pressed(), kb.key_k().pressed(), kb.key_j().pressed(), kb.key_l().pressed(),
+                kb.key_o().pressed(), kb.key_p().pressed(), kb.key_y().pressed(), kb.key_h().pressed(),
+                kb.key_u().pressed(), kb.key_n().pressed(),
+            ]),
+        }
+    }
+}
+
+// Held flags in button-bit order (up, down, left, right, a, b, x, y, start, select)
+pub fn key_bits(held: [bool; 10]) -> u16 {
+    held.iter()
+        .enumerate()
+        .filter(|(_, down)| **down)
+        .fold(0, |bits, (i, _)| bits | 1 << i)
+}
+
+// The "press A to join" step: each device claims the next free player slot
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub struct JoinState {
+    pub slots: [Option<InputDevice>; MAX_PLAYERS],
+    // Slots in play, 2-4
+    pub players: usize,
+}
+
+impl Default for JoinState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JoinState {
+    pub fn new() -> Self {
+        Self::for_players(2)
+    }
+
+    pub fn for_players(players: usize) -> Self {
+        Self { slots: [None; MAX_PLAYERS], players: players.clamp(2, MAX_PLAYERS) }
+    }
+
+    // Slot the device took, or None if it (or one sharing its keys) had already
+    // joined or every slot is taken
+    pub fn join(&mut self, device: InputDevice) -> Option<usize> {
+        if self.slots.iter().flatten().any(|d| d.shares_keys(device)) {
+            return None;
+        }
+        let slot = self.slots[..self.players].iter().position(|s| s.is_none())?;
+        self.slots[slot] = Some(device);
+        Some(slot)
+    }
+
+    // Frees the device's slot; false if it hadn't joined
+    pub fn leave(&mut self, device: InputDevice) -> bool {
+        match self.slots.iter().position(|s| *s == Some(device)) {
+            Some(slot) => {
+                self.slots[slot] = None;
+                true
+            },
+            None => false,
+        }
+    }
+
+    // Every player's device, once everyone has joined
+    pub fn devices(&self) -> Option<Vec<InputDevice>> {
+        self.slots[..self.players].iter().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::input::{BTN_A, BTN_LEFT, BTN_UP, BTN_SELECT};
+
+    #[test]
+    fn key_bits_follow_button_order() {
+        let mut held = [false; 10];
+        held[0] = true;
+        held[2] = true;
+        held[4] = true;
+        held[9] = true;
+        assert_eq!(key_bits(held), BTN_UP | BTN_LEFT | BTN_A | BTN_SELECT);
+    }
+
+    #[test]
+    fn devices_join_in_order_once() {
+        let mut join = JoinState::new();
+        assert_eq!(join.join(InputDevice::Arrows), Some(0));
+        assert_eq!(join.join(InputDevice::Arrows), None);
+        assert!(join.devices().is_none());
+        assert_eq!(join.join(InputDevice::Wasd), Some(1));
+        assert_eq!(join.join(InputDevice::Gamepad(1)), None);
+        assert_eq!(join.devices(), Some(vec![InputDevice::Arrows, InputDevice::Wasd]));
+    }
+
+    #[test]
+    fn gamepad_one_cannot_join_with_the_keys_it_reads() {
+        let mut join = JoinState::for_players(4);
+        assert_eq!(join.join(InputDevice::Arrows), Some(0));
+        assert_eq!(join.join(InputDevice::Gamepad(0)), None);
+        assert_eq!(join.join(InputDevice::Wasd), Some(1));
+        assert_eq!(join.join(InputDevice::Ijkl), Some(2));
+
+        let mut join = JoinState::new();
+        assert_eq!(join.join(InputDevice::Gamepad(0)), Some(0));
+        assert_eq!(join.join(InputDevice::Wasd), None);
+        assert_eq!(join.join(InputDevice::Arrows), None);
+        assert_eq!(join.join(InputDevice::Gamepad(1)), Some(1));
+    }
+
+    #[test]
+    fn gamepad_two_cannot_join_with_ijkl() {
+        let mut join = JoinState::for_players(4);
+        assert_eq!(join.join(InputDevice::Ijkl), Some(0));
+        assert_eq!(join.join(InputDevice::Gamepad(1)), None);
+        assert_eq!(join.join(InputDevice::Gamepad(0)), Some(1));
+
+        let mut join = JoinState::new();
+        assert_eq!(join.join(InputDevice::Gamepad(1)), Some(0));
+        assert_eq!(join.join(InputDevice::Ijkl), None);
+        assert_eq!(join.join(InputDevice::Wasd), Some(1));
+    }
+
+    #[test]
+    fn leaving_frees_the_slot() {
+        let mut join = JoinState::new();
+        join.join(InputDevice::Gamepad(0));
+        join.join(InputDevice::Ijkl);
+        assert!(join.leave(InputDevice::Gamepad(0)));
+        assert!(!join.leave(InputDevice::Wasd));
+        assert_eq!(join.join(InputDevice::Wasd), Some(0));
+        assert_eq!(join.devices(), Some(vec![InputDevice::Wasd, InputDevice::Ijkl]));
+    }
+
+    #[test]
+    fn four_players_fill_four_slots() {
+        let mut join = JoinState::for_players(4);
+        for (slot, device) in [InputDevice::Wasd, InputDevice::Arrows, InputDevice::Gamepad(2)].into_iter().enumerate() {
+            assert_eq!(join.join(device), Some(slot));
+        }
+        assert!(join.devices().is_none());
+        assert_eq!(join.join(InputDevice::Ijkl), Some(3));
+        assert_eq!(join.join(InputDevice::Gamepad(0)), None);
+        assert_eq!(join.devices().map(|d| d.len()), Some(4));
+    }
+}
@@ -1,4 +1,11 @@
 use turbo::*;
+use crate::model::rng::Rng;
+use crate::model::input::Input;
+use crate::model::game_mode::GameMode;
+use crate::model::replay::Replay;
+use crate::model::records::RecordKind;
+use crate::model::storage::SaveData;
+use crate::model::settings::Settings;
 
 #[turbo::serialize]
 #[derive(Copy)] // Keep Copy if needed, remove conflicting ones
@@ -63,11 +70,16 @@ pub struct FactoryGame {
     
     pub spawn_timer: u32,
     pub belt_anim_offset: f32,
+    pub rng: Rng,
+
+    // Records
+    pub result_recorded: bool,
+    pub record_rank: Option<usize>,
 }
 
 impl FactoryGame {
 
-    pub fn new() -> Self {
+    pub fn new(seed: u32) -> Self {
         
         // Init Snow
 
@@ -88,25 +100,29 @@ impl FactoryGame {
             messages: vec![],
             spawn_timer: 0,
             belt_anim_offset: 0.0,
+            rng: Rng::new(seed),
             snow: vec![],
+            result_recorded: false,
+            record_rank: None,
         };
         
         for _ in 0..50 {
              game.snow.push(FactorySnow {
-                x: (rand() % 512) as f32,
-                y: (rand() % 288) as f32,
-                r: (rand() % 2 + 1) as f32,
-                v: (rand() % 2 + 1) as f32,
+                x: (game.rng.u32() % 512) as f32,
+                y: (game.rng.u32() % 288) as f32,
+                r: (game.rng.u32() % 2 + 1) as f32,
+                v: (game.rng.u32() % 2 + 1) as f32,
              });
         }
         
         game
     }
 
-    pub fn update(&mut self) {
+    // Still playing (pausable); false on the game over screen
+    pub fn update(&mut self, input: &Input) {
         if self.game_over {
-            if gamepad::get(0).start.just_pressed() || gamepad::get(0).a.just_pressed() {
-                *self = Self::new(); // Restart
+            if input.pad(0).start.just_pressed() || input.pad(0).a.just_pressed() {
+                *self = Self::new(self.rng.u32()); // Restart
             }
             return;
         }
@@ -120,7 +136,7 @@ impl FactoryGame {
 
         // Player Movement
         let speed = 4.0;
-        let gp = gamepad::get(0);
+        let gp = input.pad(0);
         
         if gp.left.pressed() { self.player_x -= speed; }
         if gp.right.pressed() { self.player_x += speed; }
@@ -172,13 +188,13 @@ impl FactoryGame {
         // Snow
         for s in &mut self.snow {
             s.y += s.v;
-            if s.y > 288.0 { s.y = -5.0; s.x = (rand() % 512) as f32; }
+            if s.y > 288.0 { s.y = -5.0; s.x = (self.rng.u32() % 512) as f32; }
         }
     }
 
     fn spawn_gift(&mut self) {
         // Types: 1=Blue, 2=Green, 3=Purple
-        let t = (rand() % 3) + 1;
+        let t = (self.rng.u32() % 3) + 1;
         let color = match t {
             1 => 0x3498DBFF, // Blue
             2 => 0x2ECC71FF, // Green
@@ -272,15 +288,15 @@ impl FactoryGame {
 
     fn spawn_particles(&mut self, x: f32, y: f32, color: u32, count: usize) {
         for _ in 0..count {
-             let vx = ((rand() % 100) as f32 - 50.0) / 10.0;
-             let vy = ((rand() % 100) as f32 - 50.0) / 10.0;
+             let vx = ((self.rng.u32() % 100) as f32 - 50.0) / 10.0;
+             let vy = ((self.rng.u32() % 100) as f32 - 50.0) / 10.0;
              self.particles.push(FactoryParticle {
                  x, y, vx, vy, life: 1.0, color
              });
         }
     }
 
-    pub fn draw(&self) {
+    pub fn draw(&self, settings: &Settings) {
         // Clear Black
         rect!(w=512, h=288, color=0x000000FF);
         
@@ -375,7 +391,7 @@ impl FactoryGame {
         rect!(x=px+2, y=py+14, w=4, h=4, color=0x000000FF);
 
         // Particles
-        for p in &self.particles {
+        for p in self.particles.iter().step_by(settings.particle_step()) {
             rect!(x=p.x as i32, y=p.y as i32, w=2, h=2, color=p.color);
         }
         
@@ -397,15 +413,151 @@ impl FactoryGame {
             text!("TIME'S UP!", x=210, y=110, font="large", color=0xFF0000FF);
             let final_score_txt = format!("Final Score: {}", self.score);
             text!(&final_score_txt, x=180, y=140, font="medium", color=0xFFFFFFFF);
+            if let Some(rank) = self.record_rank {
+                let record_txt = format!("NEW RECORD! #{}", rank + 1);
+                text!(&record_txt, x=180, y=156, font="small", color=0xFFD700FF);
+            }
             text!("Press START to Retry", x=180, y=170, font="small", color=0xAAAAAAFF);
+            let seed_txt = format!("Seed: {}", self.rng.seed);
+            text!(&seed_txt, x=180, y=182, font="small", color=0x666666FF);
+        }
+    }
+}
+
+impl GameMode for FactoryGame {
+    fn from_replay(replay: &Replay) -> Self {
+        FactoryGame::new(replay.seed)
+    }
+
+    fn update(&mut self, input: &Input) {
+        FactoryGame::update(self, input)
+    }
+
+    fn draw(&self, settings: &Settings) {
+        FactoryGame::draw(self, settings)
+    }
+
+    fn is_running(&self) -> bool {
+        !self.game_over
+    }
+
+    fn submit_record(&mut self, save: &mut SaveData, name: &str) {
+        if self.game_over && !self.result_recorded {
+            self.result_recorded = true;
+            self.record_rank = save.submit_record(RecordKind::GiftPacking, name, self.score);
         }
     }
+
+    fn draw_records(&self, save: &SaveData) {
+        if self.game_over {
+            save.records.draw_table(RecordKind::GiftPacking, 364, 80, self.record_rank);
+        }
+    }
+
+    fn instructions(&self) -> &'static [&'static str] {
+        &[
+            "Gift Packing (Factory):",
+            "- Left/Right: Move Conveyor",
+            "- A: Grab/Drop Gift",
+            "- Start / Esc: Pause Menu",
+            "Goal: Sort gifts into correct bins.",
+        ]
+    }
 }
 
-fn rand() -> u32 {
-    unsafe {
-        static mut SEED: u32 = 12345;
-        SEED = SEED.wrapping_mul(1103515245).wrapping_add(12345);
-        SEED
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::input::{InputFeed, BTN_A};
+
+    fn press_a(game: &mut FactoryGame, feed: &mut InputFeed) {
+        game.update(&feed.next([BTN_A, 0]));
+        game.update(&feed.next([0, 0]));
+    }
+
+    #[test]
+    fn correct_bin_scores_100() {
+        let mut game = FactoryGame::new(1);
+        let mut feed = InputFeed::default();
+        game.held_gift_type = 2; // Green
+        game.player_x = 256.0;
+        game.player_y = 220.0;
+
+        press_a(&mut game, &mut feed);
+
+        assert_eq!(game.score, 100);
+        assert_eq!(game.held_gift_type, 0);
+    }
+
+    #[test]
+    fn wrong_bin_costs_50() {
+        let mut game = FactoryGame::new(1);
+        let mut feed = InputFeed::default();
+        game.held_gift_type = 1; // Blue into the purple bin
+        game.player_x = 412.0;
+        game.player_y = 220.0;
+
+        press_a(&mut game, &mut feed);
+
+        assert_eq!(game.score, -50);
+        assert_eq!(game.held_gift_type, 0);
+    }
+
+    #[test]
+    fn drop_outside_bins_keeps_gift() {
+        let mut game = FactoryGame::new(1);
+        let mut feed = InputFeed::default();
+        game.held_gift_type = 3;
+        game.player_x = 180.0;
+        game.player_y = 220.0;
+
+        press_a(&mut game, &mut feed);
+
+        assert_eq!(game.score, 0);
+        assert_eq!(game.held_gift_type, 3);
+    }
+
+    #[test]
+    fn grab_takes_nearby_gift() {
+        let mut game = FactoryGame::new(1);
+        let mut feed = InputFeed::default();
+        game.gifts.clear();
+        game.gifts.push(FactoryGift { x: 200.0, y: 40.0, w: 24.0, h: 24.0, kind: GiftType::Purple, color: 0x9B59B6FF });
+        game.player_x = 200.0;
+        game.player_y = 60.0;
+
+        press_a(&mut game, &mut feed);
+
+        assert_eq!(game.held_gift_type, 3);
+        assert!(game.gifts.is_empty());
+    }
+
+    #[test]
+    fn timer_ends_game() {
+        let mut game = FactoryGame::new(1);
+        let mut feed = InputFeed::default();
+        for _ in 0..(61 * 60) {
+            game.update(&feed.next([0, 0]));
+        }
+        assert!(game.game_over);
+    }
+
+    #[test]
+    fn same_seed_and_input_replay_identically() {
+        let script = |game: &mut FactoryGame| {
+            let mut feed = InputFeed::default();
+            for frame in 0..600u32 {
+                let bits = if frame % 40 < 20 { BTN_A } else { 0 };
+                game.update(&feed.next([bits, 0]));
+            }
+        };
+        let mut a = FactoryGame::new(42);
+        let mut b = FactoryGame::new(42);
+        script(&mut a);
+        script(&mut b);
+
+        assert_eq!(a.score, b.score);
+        assert_eq!(a.gifts.len(), b.gifts.len());
+        assert_eq!(a.rng, b.rng);
     }
 }
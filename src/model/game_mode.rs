@@ -0,0 +1,40 @@
+use crate::model::input::Input;
+use crate::model::replay::Replay;
+use crate::model::settings::Settings;
+use crate::model::storage::SaveData;
+
+// Everything the shared play scene needs from a game. Recording, replays,
+// pausing, restarting, sound and screen shake are handled there once.
+pub trait GameMode {
+    // Fresh run from a replay header (seed and multiplayer setup)
+    fn from_replay(replay: &Replay) -> Self;
+
+    fn update(&mut self, input: &Input);
+
+    fn draw(&self, settings: &Settings);
+
+    // A run is in progress: Start pauses, and B does not leave
+    fn is_running(&self) -> bool;
+
+    // Screen shake (pixels) requested since the last call
+    fn take_shake(&mut self) -> usize {
+        0
+    }
+
+    // Sound effects raised since the last call
+    fn take_sounds(&mut self) -> Vec<String> {
+        vec![]
+    }
+
+    // Submits a finished run to the records, once
+    fn submit_record(&mut self, _save: &mut SaveData, _name: &str) {}
+
+    // Unlocks and other progress that outlive the run
+    fn save_progress(&mut self, _save: &mut SaveData) {}
+
+    // Record table beside the results screen
+    fn draw_records(&self, _save: &SaveData) {}
+
+    // Lines for the instructions overlay
+    fn instructions(&self) -> &'static [&'static str];
+}
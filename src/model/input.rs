@@ -0,0 +1,135 @@
+use turbo::*;
+
+// Button bits, as packed into recorded replay frames
+pub const BTN_UP: u16 = 1 << 0;
+pub const BTN_DOWN: u16 = 1 << 1;
+pub const BTN_LEFT: u16 = 1 << 2;
+pub const BTN_RIGHT: u16 = 1 << 3;
+pub const BTN_A: u16 = 1 << 4;
+pub const BTN_B: u16 = 1 << 5;
+pub const BTN_X: u16 = 1 << 6;
+pub const BTN_Y: u16 = 1 << 7;
+pub const BTN_START: u16 = 1 << 8;
+pub const BTN_SELECT: u16 = 1 << 9;
+
+// Pads carried by every frame of input; single player only reads the first
+pub const MAX_PLAYERS: usize = 4;
+
+// Widens the pads given to every pad slot, the rest idle
+pub fn all_pads<const N: usize>(bits: [u16; N]) -> [u16; MAX_PLAYERS] {
+    let mut out = [0; MAX_PLAYERS];
+    out[..N].copy_from_slice(&bits);
+    out
+}
+
+#[turbo::serialize]
+#[derive(Copy, PartialEq, Default)]
+pub struct Btn {
+    pub down: bool,
+    pub was_down: bool,
+}
+
+impl Btn {
+    fn from_bits(bits: u16, prev: u16, mask: u16) -> Self {
+        Self { down: bits & mask != 0, was_down: prev & mask != 0 }
+    }
+
+    pub fn pressed(&self) -> bool {
+        self.down
+    }
+
+    pub fn just_pressed(&self) -> bool {
+        self.down && !self.was_down
+    }
+}
+
+// Same shape as a turbo gamepad, but built from bits so the
+// source can be a live pad or a replay
+#[turbo::serialize]
+#[derive(Copy, PartialEq, Default)]
+pub struct Pad {
+    pub up: Btn,
+    pub down: Btn,
+    pub left: Btn,
+    pub right: Btn,
+    pub a: Btn,
+    pub b: Btn,
+    pub x: Btn,
+    pub y: Btn,
+    pub start: Btn,
+    pub select: Btn,
+}
+
+impl Pad {
+    pub fn from_bits(bits: u16, prev: u16) -> Self {
+        Self {
+            up: Btn::from_bits(bits, prev, BTN_UP),
+            down: Btn::from_bits(bits, prev, BTN_DOWN),
+            left: Btn::from_bits(bits, prev, BTN_LEFT),
+            right: Btn::from_bits(bits, prev, BTN_RIGHT),
+            a: Btn::from_bits(bits, prev, BTN_A),
+            b: Btn::from_bits(bits, prev, BTN_B),
+            x: Btn::from_bits(bits, prev, BTN_X),
+            y: Btn::from_bits(bits, prev, BTN_Y),
+            start: Btn::from_bits(bits, prev, BTN_START),
+            select: Btn::from_bits(bits, prev, BTN_SELECT),
+        }
+    }
+
+    // Buttons currently held on a physical gamepad
+    pub fn poll(index: usize) -> u16 {
+        let gp = gamepad::get(index);
+        let mut bits = 0;
+        if gp.up.pressed() { bits |= BTN_UP; }
+        if gp.down.pressed() { bits |= BTN_DOWN; }
+        if gp.left.pressed() { bits |= BTN_LEFT; }
+        if gp.right.pressed() { bits |= BTN_RIGHT; }
+        if gp.a.pressed() { bits |= BTN_A; }
+        if gp.b.pressed() { bits |= BTN_B; }
+        if gp.x.pressed() { bits |= BTN_X; }
+        if gp.y.pressed() { bits |= BTN_Y; }
+        if gp.start.pressed() { bits |= BTN_START; }
+        if gp.select.pressed() { bits |= BTN_SELECT; }
+        bits
+    }
+}
+
+// Per-frame input handed to the game modes instead of reading gamepad::get directly
+#[turbo::serialize]
+#[derive(Copy, PartialEq, Default)]
+pub struct Input {
+    pub pads: [Pad; MAX_PLAYERS],
+}
+
+impl Input {
+    pub fn new(bits: [u16; MAX_PLAYERS], prev: [u16; MAX_PLAYERS]) -> Self {
+        Self {
+            pads: std::array::from_fn(|i| Pad::from_bits(bits[i], prev[i])),
+        }
+    }
+
+    pub fn pad(&self, index: usize) -> Pad {
+        self.pads[index]
+    }
+}
+
+// Turns a stream of raw button states into Inputs, remembering the
+// previous frame so just_pressed works the same live, in replays and in tests
+#[turbo::serialize]
+#[derive(Copy, PartialEq, Default)]
+pub struct InputFeed {
+    pub prev: [u16; MAX_PLAYERS],
+}
+
+impl InputFeed {
+    pub fn new<const N: usize>(held: [u16; N]) -> Self {
+        Self { prev: all_pads(held) }
+    }
+
+    pub fn next<const N: usize>(&mut self, bits: [u16; N]) -> Input {
+        let bits = all_pads(bits);
+        let input = Input::new(bits, self.prev);
+        self.prev = bits;
+        input
+    }
+}
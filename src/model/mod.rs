@@ -9,20 +9,77 @@ pub use world::*;
 mod snow;
 pub use snow::*;
 
+mod rng;
+pub use rng::*;
+
+mod input;
+pub use input::*;
+
+mod devices;
+pub use devices::*;
+
 mod title;
 pub use title::*;
 
 pub mod multiplayer;
 pub use multiplayer::*;
 
+mod series;
+pub use series::*;
+
+mod arena;
+pub use arena::*;
+
+mod rival_ai;
+pub use rival_ai::*;
+
+mod online;
+pub use online::*;
+
 mod factory_game;
 pub use factory_game::*;
 
 mod sleigh_game;
 pub use sleigh_game::*;
 
+mod sleigh_waves;
+pub use sleigh_waves::*;
+
+mod sleigh_powerups;
+pub use sleigh_powerups::*;
+
 mod breaker_game;
 pub use breaker_game::*;
 
+mod breaker_powerups;
+pub use breaker_powerups::*;
+
+mod breaker_levels;
+pub use breaker_levels::*;
+
+mod breaker_physics;
+pub use breaker_physics::*;
+
 mod stealth_game;
 pub use stealth_game::*;
+
+mod stealth_house;
+pub use stealth_house::*;
+
+mod rooftop_game;
+pub use rooftop_game::*;
+
+mod game_mode;
+pub use game_mode::*;
+
+mod records;
+pub use records::*;
+
+mod replay;
+pub use replay::*;
+
+mod storage;
+pub use storage::*;
+
+mod settings;
+pub use settings::*;
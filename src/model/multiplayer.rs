@@ -1,4 +1,12 @@
 use turbo::*;
+use crate::model::rng::Rng;
+use crate::model::input::{Input, MAX_PLAYERS};
+use crate::model::game_mode::GameMode;
+use crate::model::replay::Replay;
+use crate::model::settings::Settings;
+use crate::model::rival_ai::RivalAi;
+use crate::model::series::{Series, SeriesKind};
+use crate::model::arena::{in_any, Arena, ArenaRules, RiverLayout, Zone};
 
 
 #[turbo::serialize]
@@ -28,7 +36,7 @@ pub struct House {
     pub last_collected_by: Option<u8>,
     pub last_collection_time: u32, // in ticks
     pub is_high_value: bool, // Level 3: Unused. Level 4: True for Power House.
-    pub team: u8, // 0 = Neutral, 1 = Red, 2 = Blue
+    pub team: u8, // 0 = Neutral, otherwise the owning team (see team_of)
     pub gift_timer: u32, // Level 4: Timer for spawning risky gifts
 }
 
@@ -91,6 +99,47 @@ pub struct MParticle {
     pub color: u32,
 }
 
+// Spawn corner and colors for each player slot; teams borrow their first player's
+pub struct PlayerSlot {
+    pub spawn: (f32, f32),
+    pub color: u32,
+    pub suit: u32,
+    pub trail: u32,
+    pub hud: u32,
+    pub wall: u32,
+    pub roof: u32,
+    pub pop: u32,
+}
+
+pub const SLOTS: [PlayerSlot; MAX_PLAYERS] = [
+    // Red (Santa)
+    PlayerSlot { spawn: (50.0, 50.0), color: 0xFF0000FF, suit: 0xD32F2FFF, trail: 0xD32F2F44, hud: 0xB71C1CFF, wall: 0xB71C1CFF, roof: 0xFFEBEEFF, pop: 0xFFCDD2FF },
+    // Blue (Rival)
+    PlayerSlot { spawn: (462.0, 238.0), color: 0x0000FFFF, suit: 0x1976D2FF, trail: 0x1976D244, hud: 0x0D47A1FF, wall: 0x0277BDFF, roof: 0xE1F5FEFF, pop: 0xBBDEFBFF },
+    // Green
+    PlayerSlot { spawn: (462.0, 50.0), color: 0x00C853FF, suit: 0x388E3CFF, trail: 0x388E3C44, hud: 0x1B5E20FF, wall: 0x2E7D32FF, roof: 0xE8F5E9FF, pop: 0x69F0AEFF },
+    // Orange
+    PlayerSlot { spawn: (50.0, 238.0), color: 0xFF6D00FF, suit: 0xEF6C00FF, trail: 0xEF6C0044, hud: 0xE65100FF, wall: 0xE65100FF, roof: 0xFFF3E0FF, pop: 0xFFE0B2FF },
+];
+
+// Free-for-all gives every player their own team; pairs put 1+3 against 2+4
+pub fn team_of(id: u8, pairs: bool) -> u8 {
+    if pairs { (id - 1) % 2 + 1 } else { id }
+}
+
+// Frames the between-rounds screen of a series stays up
+const INTERMISSION: u32 = 6 * 60;
+
+// A line of the results screen: a player, or a pair with their summed score
+#[derive(Clone, PartialEq, Debug)]
+pub struct Standing {
+    pub name: String,
+    pub score: u32,
+    // Rounds won, for series tallies
+    pub wins: u32,
+    pub team: u8,
+}
+
 #[turbo::serialize]
 #[derive(PartialEq)]
 pub struct MultiplayerGame {
@@ -108,10 +157,20 @@ pub struct MultiplayerGame {
     pub frame_count: u32,
     pub next_level_timer: u32,
     pub last_pickup_pos: (f32, f32), // Track last pickup to avoid respawn nearby
-    pub p1_name: String,
-    pub p2_name: String,
+    // One per player, in pad order
+    pub names: Vec<String>,
+    pub pairs: bool,
+    // Rounds played so far; a single match is a one-round series
+    pub series: Series,
     pub max_time_minutes: u32,
     pub current_level: u32,
+    // Hand-made layout in place of the level's generated one; its rules set current_level
+    pub arena: Option<Arena>,
+    // Mechanics in play: the level's preset or the arena's own
+    pub rules: ArenaRules,
+    // River strips, and the bridges that keep parts of them dry
+    pub water: Vec<Zone>,
+    pub bridges: Vec<Zone>,
     // Level 2 Specifics
     pub shuffle_timer: u32,
     pub is_shuffling: bool,
@@ -121,10 +180,23 @@ pub struct MultiplayerGame {
     pub dog_target: Option<u8>, // Player ID
     pub dog_state: u8, // 0 = In Cage, 1 = Chasing
     pub cage_pos: (f32, f32),
+    pub rng: Rng,
+    // Sound effects raised during update; GameState plays and clears them
+    pub sounds: Vec<String>,
+    // Screen shake requested this frame (pixels); GameState applies and clears it
+    pub shake: usize,
+    // CPU at player 2's controls, if nobody is there
+    pub rival_ai: Option<RivalAi>,
 }
 
 impl MultiplayerGame {
-    pub fn new(p1: String, p2: String, minutes: u32, level: u32) -> Self {
+    pub fn new(p1: String, p2: String, minutes: u32, level: u32, seed: u32) -> Self {
+        Self::with_players(vec![p1, p2], false, minutes, level, seed)
+    }
+
+    // 2-4 players; pairs only applies to four
+    pub fn with_players(names: Vec<String>, pairs: bool, minutes: u32, level: u32, seed: u32) -> Self {
+        let mut rng = Rng::new(seed);
         let mut game = Self {
             players: vec![],
             houses: vec![],
@@ -132,10 +204,10 @@ impl MultiplayerGame {
             powerups: vec![],
             particles: vec![],
             env_snow: (0..60).map(|_| EnvSnow {
-                x: (random::u32() % 512) as f32,
-                y: (random::u32() % 288) as f32,
-                speed: (random::u32() % 10) as f32 / 20.0 + 0.5, // Faster on bright bg
-                size: (random::u32() % 2) + 2,
+                x: (rng.u32() % 512) as f32,
+                y: (rng.u32() % 288) as f32,
+                speed: (rng.u32() % 10) as f32 / 20.0 + 0.5, // Faster on bright bg
+                size: (rng.u32() % 2) + 2,
             }).collect(),
             decors: vec![], // Init in init_level
             floating_texts: vec![],
@@ -145,10 +217,15 @@ impl MultiplayerGame {
             frame_count: 0,
             next_level_timer: 0,
             last_pickup_pos: (0.0, 0.0),
-            p1_name: p1,
-            p2_name: p2,
+            pairs: pairs && names.len() == MAX_PLAYERS,
+            series: Series::new(SeriesKind::Single, level, names.len()),
+            names,
             max_time_minutes: minutes,
             current_level: level,
+            arena: None,
+            rules: ArenaRules::level(level),
+            water: vec![],
+            bridges: vec![],
             shuffle_timer: 0,
             is_shuffling: false,
             shuffle_pause_timer: 0,
@@ -156,6 +233,10 @@ impl MultiplayerGame {
             dog_target: None,
             dog_state: 0,
             cage_pos: (30.0, 260.0),
+            rng,
+            sounds: vec![],
+            shake: 0,
+            rival_ai: None,
         };
         game.init_level(level);
         game
@@ -163,43 +244,59 @@ impl MultiplayerGame {
 
     fn init_level(&mut self, _level: u32) {
         // Reset players
-        self.players = vec![
-            MPlayer {
-                x: 50.0,
-                y: 50.0,
-                color: 0xFF0000FF, // Red (Santa)
-                id: 1,
-                score: 0,
-                radius: 8.0,
-                boost_timer: 0,
-                invuln_timer: 0,
-                name: self.p1_name.clone(),
-                shadow_trail: vec![],
-                slow_timer: 0,
-            },
-            MPlayer {
-                x: 462.0,
-                y: 238.0,
-                color: 0x0000FFFF, // Blue (Rival)
-                id: 2,
-                score: 0,
-                radius: 8.0,
-                boost_timer: 0,
-                invuln_timer: 0,
-                name: self.p2_name.clone(),
-                shadow_trail: vec![],
-                slow_timer: 0,
-            },
-        ];
+        self.players = self.names.iter().zip(&SLOTS).enumerate().map(|(i, (name, slot))| MPlayer {
+            x: slot.spawn.0,
+            y: slot.spawn.1,
+            color: slot.color,
+            id: i as u8 + 1,
+            score: 0,
+            radius: 8.0,
+            boost_timer: 0,
+            invuln_timer: 0,
+            name: name.clone(),
+            shadow_trail: vec![],
+            slow_timer: 0,
+        }).collect();
+
+        // Rules, terrain and layout: the arena's, or the level's own
+        if let Some(arena) = self.arena.clone() {
+            self.rules = arena.rules;
+            self.lay_out_arena(&arena);
+        } else {
+            self.rules = ArenaRules::level(_level);
+            self.water = self.rules.river.water();
+            self.bridges = self.rules.river.bridges();
+            self.generate_layout();
+        }
 
+        // Dynamic Shuffle Init
+        if self.rules.shuffle {
+            self.shuffle_timer = 15 * 60; // 15 seconds
+        } else {
+            self.shuffle_timer = 0;
+        }
+        self.is_shuffling = false;
+        self.shuffle_pause_timer = 0;
+
+        // Reset particles and texts
+        self.particles = vec![];
+        self.floating_texts = vec![];
+        
+        // Timer reset
+        self.timer = self.max_time_minutes * 60;
+        self.game_over = false;
+    }
+
+    fn generate_layout(&mut self) {
+        let rules = self.rules;
         // Initialize Lists
         self.houses = vec![];
         self.obstacles = vec![];
         
         // 1. Static Obstacles (Bridges / Barriers)
-        if _level >= 3 {
+        if rules.river != RiverLayout::None {
              // Level 5: 4 Bridges Logic
-             if _level == 5 {
+             if rules.river == RiverLayout::Cross {
                  // We don't need obstacle objects for the bridges themselves as they are just "safe zones" in the river code.
                  // But we can add decorative posts or small blockers nearby if we want.
                  // For now, clean map.
@@ -217,30 +314,24 @@ impl MultiplayerGame {
         
         // 2. Houses
         // 2. Houses
-        if _level >= 3 {
-             // Level 3 & 4: 5 Red (Team 1), 5 Blue (Team 2)
-             // Level 4: +1 Power House (Team 0)
+        if rules.teams {
+             // Level 3 & 4: 5 Red (Team 1), 5 Blue (Team 2), at least 3 each with more teams
+             // Level 4 & 5: +1 Power House (Team 0)
              
              // Manually generate to assign teams
-             let mut rng = random::u32();
+             let teams = self.team_count();
+             let team_houses = rules.houses.max(teams * 3);
              let mut placed_count = 0;
-             let target = if _level == 5 { 9 } else if _level >= 4 { 11 } else { 10 };
+             let target = if rules.power_house() { team_houses + 1 } else { team_houses };
              let mut attempts = 0;
              
              while placed_count < target && attempts < 1000 {
                  attempts += 1;
-                 rng = (rng.wrapping_mul(1103515245).wrapping_add(12345)) % 2147483648;
-                 let hx = 40.0 + (rng % 432) as f32;
-                 rng = (rng.wrapping_mul(1103515245).wrapping_add(12345)) % 2147483648;
-                 let hy = 40.0 + (rng % 180) as f32; // Keep somewhat upper for start?
+                 let hx = 40.0 + (self.rng.u32() % 432) as f32;
+                 let hy = 40.0 + (self.rng.u32() % 180) as f32; // Keep somewhat upper for start?
                  
                  // L3/L4/L5 Checks (River)
-                 if _level >= 3 {
-                     // Vertical River
-                     if hx > 236.0 && hx < 276.0 { continue; } 
-                     // Level 5 Horizontal River (124..164)
-                     if _level == 5 && hy > 124.0 && hy < 164.0 { continue; }
-                 }
+                 if self.on_river(hx, hy) { continue; }
                  
                  let mut safe = true;
                  for o in &self.obstacles {
@@ -257,23 +348,14 @@ impl MultiplayerGame {
                      let mut is_pow = false;
                      let mut g_timer = 0;
                      
-                     if _level == 5 && placed_count == 8 {
-                         // 9th House is Power House in Level 5
-                         team = 0;
-                         is_pow = true;
-                         g_timer = 15 * 60;
-                     } else if _level == 4 && placed_count == 10 {
-                         // 11th House is Power House in Level 4
+                     if rules.power_house() && placed_count == team_houses {
+                         // Last House is the Power House in Level 4 & 5
                          team = 0; 
                          is_pow = true;
                          g_timer = 15 * 60; // 15s initial timer
                      } else {
-                         // Teams
-                         if _level == 5 {
-                             team = if placed_count < 4 { 1 } else { 2 };
-                         } else {
-                             team = if placed_count < 5 { 1 } else { 2 };
-                         }
+                         // Teams, in equal runs
+                         team = (placed_count * teams / team_houses) as u8 + 1;
                      }
 
                      self.houses.push(House {
@@ -289,49 +371,39 @@ impl MultiplayerGame {
              
         } else {
             // Level 1 & 2
-            let count = if _level == 2 { 14 } else { 10 };
-            self.generate_random_houses(count, false);
+            self.generate_random_houses(rules.houses as usize, false);
         }
         
         // 3. Random Bombs (Level 2 & 3)
-        if _level >= 2 {
+        if rules.bombs > 0 {
             // Level 2 & 3 have Bombs
-            let bomb_count = if _level == 3 { 6 } else { 8 };
-            
+
             // Level 4: No bombs mentioned in doc? "Existing bombs... remain". 
             // So Level 4 keeps L3 obstacles (Wood + Bombs?)
             // "obstacles: Existing bombs, trees, and wooden blocks remain" -> Yes.
             // Level 4 Bomb count = Same as L3 (6)? Or less to make room for Snowmen?
             // Let's assume 6 Bombs.
             
-            let mut rng = random::u32();
             let mut attempts = 0;
             // Target Limit: L3 had ~9 obstacles (3 wood + 6 bombs)
             // L4 adds Snowmen later.
-            let bomb_target = if _level >= 3 { 6 } else { 8 };
+            let bomb_target = rules.bombs as usize;
             
             let current_obs_count = self.obstacles.len();
 
             while (self.obstacles.len() - current_obs_count) < bomb_target && attempts < 1000 {
                 attempts += 1;
-                rng = (rng.wrapping_mul(1103515245).wrapping_add(12345)) % 2147483648;
-                let ox = 60.0 + (rng % 392) as f32; 
-                rng = (rng.wrapping_mul(1103515245).wrapping_add(12345)) % 2147483648;
-                let oy = 60.0 + (rng % 168) as f32;
+                let ox = 60.0 + (self.rng.u32() % 392) as f32; 
+                let oy = 60.0 + (self.rng.u32() % 168) as f32;
                 
                 // Avoid overlap with houses
                 let mut safe = true;
                 for h in &self.houses {
                    if ((h.x - ox).powi(2) + (h.y - oy).powi(2)).sqrt() < 60.0 { safe = false; break; }
                 }
-                // Avoid level 3 water strip (ENTIRE STRIP banned for bombs)
-                if _level >= 3 {
-                     if ox > 236.0 && ox < 276.0 {
-                         safe = false; // Block bridge too
-                     }
-                     if _level == 5 && oy > 124.0 && oy < 164.0 {
-                         safe = false;
-                     }
+                // Avoid level 3 water strip (ENTIRE STRIP banned for bombs, bridges too)
+                if self.on_river(ox, oy) {
+                     safe = false;
                 }
                 
                 // Avoid overlap with existing obstacles (Wood & Bombs)
@@ -343,30 +415,25 @@ impl MultiplayerGame {
                 
                 if safe {
                     // Respawn timer: Random 5s to 15s (300-900 frames) so they don't all move at once
-                    let timer = if _level >= 2 { (random::u32() % 600) + 300 } else { 0 };
+                    let timer = if rules.hazards { (self.rng.u32() % 600) + 300 } else { 0 };
                     self.obstacles.push(Obstacle { x: ox, y: oy, w: 24.0, h: 24.0, respawn_timer: timer, kind: 0 }); // Kind 0 = Bomb
                 }
             }
         }
         
         // 4. Snowmen (Level 4 & 5)
-        if _level >= 4 {
-             let snowman_count = 5;
-             let mut rng = random::u32();
+        if rules.snowmen > 0 {
+             let snowman_count = rules.snowmen as usize;
              let mut attempts = 0;
              let start_count = self.obstacles.len();
              
              while (self.obstacles.len() - start_count) < snowman_count && attempts < 100 {
                  attempts += 1;
-                 rng = (rng.wrapping_mul(1103515245).wrapping_add(12345)) % 2147483648;
-                 let sx = 40.0 + (rng % 432) as f32;
-                 rng = (rng.wrapping_mul(1103515245).wrapping_add(12345)) % 2147483648;
-                 let sy = 40.0 + (rng % 208) as f32;
+                 let sx = 40.0 + (self.rng.u32() % 432) as f32;
+                 let sy = 40.0 + (self.rng.u32() % 208) as f32;
                  
                  // River Check
-                 if sx > 236.0 && sx < 276.0 { continue; }
-                 // L5 Horizontal
-                 if _level == 5 && sy > 124.0 && sy < 164.0 { continue; }
+                 if self.on_river(sx, sy) { continue; }
                  
                  let mut safe = true;
                  for h in &self.houses {
@@ -385,15 +452,6 @@ impl MultiplayerGame {
              }
         }
         
-        // Level 2: Dynamic Shuffle Init
-        if _level == 2 {
-            self.shuffle_timer = 15 * 60; // 15 seconds
-        } else {
-            self.shuffle_timer = 0;
-        }
-        self.is_shuffling = false;
-        self.shuffle_pause_timer = 0;
-
         // Generate Decor (Trees and Piles)
         // Generate Decor (Trees and Piles)
         self.decors = vec![];
@@ -401,14 +459,11 @@ impl MultiplayerGame {
         // Trees
         while self.decors.len() < 20 && decor_attempts < 500 {
             decor_attempts += 1;
-            let dx = (random::u32() % 500 + 10) as f32;
-            let dy = (random::u32() % 260 + 10) as f32;
+            let dx = (self.rng.u32() % 500 + 10) as f32;
+            let dy = (self.rng.u32() % 260 + 10) as f32;
             
             // Level 3 Check: No trees in River Strip
-            if self.current_level >= 3 {
-                 if dx > 236.0 && dx < 276.0 { continue; }
-                 if self.current_level == 5 && dy > 124.0 && dy < 164.0 { continue; }
-            }
+            if self.on_river(dx, dy) { continue; }
             
             let mut safe = true;
             // Check Houses
@@ -437,14 +492,11 @@ impl MultiplayerGame {
         decor_attempts = 0;
         while self.decors.len() < 30 && decor_attempts < 200 { 
              decor_attempts += 1;
-             if random::u32() % 2 == 0 {
-                let dx = (random::u32() % 500 + 10) as f32;
-                let dy = (random::u32() % 260 + 10) as f32;
+             if self.rng.u32() % 2 == 0 {
+                let dx = (self.rng.u32() % 500 + 10) as f32;
+                let dy = (self.rng.u32() % 260 + 10) as f32;
                 
-                if self.current_level >= 3 {
-                     if dx > 236.0 && dx < 276.0 { continue; }
-                     if self.current_level == 5 && dy > 124.0 && dy < 164.0 { continue; }
-                }
+                if self.on_river(dx, dy) { continue; }
                 
                 let mut safe = true;
                 // Check Houses
@@ -469,25 +521,97 @@ impl MultiplayerGame {
                 }
              }
         }
-        
-        // Reset particles and texts
-        self.particles = vec![];
-        self.floating_texts = vec![];
-        
-        // Timer reset
-        self.timer = self.max_time_minutes * 60;
-        self.game_over = false;
     }
 
-    pub fn update(&mut self) {
+    fn lay_out_arena(&mut self, arena: &Arena) {
+        self.water = arena.water.clone();
+        self.bridges = arena.bridges.clone();
+        let teams = self.team_count() as u8;
+        self.houses = arena.houses.iter().map(|h| House {
+            x: h.x, y: h.y, points: if h.power { 0 } else { 5 }, cooldown: 0,
+            last_collected_by: None, last_collection_time: 0,
+            is_high_value: h.power,
+            // Houses of teams nobody is playing are up for grabs
+            team: if h.team > teams { 0 } else { h.team },
+            gift_timer: if h.power { 15 * 60 } else { 0 },
+        }).collect();
+        self.obstacles = arena.obstacles.iter().map(|o| {
+            let (w, h) = o.size();
+            // Bombs wander off from their spot like on the built-in levels
+            let timer = if o.kind == 0 && self.rules.hazards { (self.rng.u32() % 600) + 300 } else { 0 };
+            Obstacle { x: o.x, y: o.y, w, h, respawn_timer: timer, kind: o.kind }
+        }).collect();
+        self.decors = arena.decors.clone();
+        self.cage_pos = arena.cage;
+        self.dog_pos = arena.cage;
+    }
+
+    // Plays on a hand-made arena from here on, under its rules
+    pub fn load_arena(&mut self, arena: Arena) {
+        self.current_level = arena.rules.level;
+        self.series.level = arena.rules.level;
+        self.arena = Some(arena);
+        self.init_level(self.current_level);
+    }
+
+    // Plays the match as a series of rounds from here on
+    pub fn start_series(&mut self, kind: SeriesKind) {
+        self.series = Series::new(kind, self.series.level, self.names.len());
+        // A campaign walks the built-in levels instead
+        if kind == SeriesKind::Campaign && self.arena.take().is_some() {
+            self.current_level = 0;
+        }
+        if self.series.next_level() != self.current_level {
+            self.start_round();
+        }
+    }
+
+    // Fresh level for the series' next round; scores start from zero
+    fn start_round(&mut self) {
+        self.current_level = self.series.next_level();
+        self.next_level_timer = 0;
+        self.powerups.clear();
+        self.dog_pos = self.cage_pos;
+        self.dog_target = None;
+        self.dog_state = 0;
+        self.init_level(self.current_level);
+    }
+
+    // Match in progress (pausable)
+    pub fn update(&mut self, input: &Input) {
         if self.game_over {
             // Wait for input to restart
-            if gamepad::get(0).start.just_pressed() || gamepad::get(0).a.just_pressed() {
-                 self.init_level(self.current_level);
+            if input.pad(0).start.just_pressed() || input.pad(0).a.just_pressed() {
+                 if self.series.is_single() {
+                     self.init_level(self.current_level);
+                 } else {
+                     // The whole series again, from its first round
+                     self.series = Series::new(self.series.kind, self.series.level, self.names.len());
+                     self.start_round();
+                 }
             }
             return;
         }
 
+        // Between series rounds: show the tally, then on to the next level
+        if self.next_level_timer > 0 {
+            self.next_level_timer -= 1;
+            let skip = self.next_level_timer < INTERMISSION - 60
+                && (input.pad(0).start.just_pressed() || input.pad(0).a.just_pressed());
+            if self.next_level_timer == 0 || skip {
+                self.start_round();
+            }
+            return;
+        }
+
+        // The CPU rival's buttons replace whatever is on pad 2
+        let mut input = *input;
+        if let Some(mut ai) = self.rival_ai.take() {
+            input.pads[1] = ai.next_pad(self);
+            self.rival_ai = Some(ai);
+        }
+        let input = &input;
+
         self.frame_count += 1;
         if self.frame_count % 60 == 0 && self.timer > 0 {
             self.timer -= 1;
@@ -495,13 +619,9 @@ impl MultiplayerGame {
         if self.timer == 0 {
             self.end_game();
         }
-
-        if self.timer == 0 {
-            self.end_game();
-        }
         
         // Dynamic Bomb Logic (Level 2 & 3 & 4)
-        if self.current_level >= 2 {
+        if self.rules.hazards {
             let mut bombs_to_move = vec![];
             for (i, o) in self.obstacles.iter_mut().enumerate() {
                 if o.kind == 0 { // Bomb
@@ -511,22 +631,19 @@ impl MultiplayerGame {
                     if o.respawn_timer == 0 {
                         bombs_to_move.push(i);
                         // Reset timer immediately to avoid double add (will be overwritten on success)
-                         o.respawn_timer = (random::u32() % 600) + 300;
+                         o.respawn_timer = (self.rng.u32() % 600) + 300;
                     }
                 }
             }
             
             for i in bombs_to_move {
-                 let mut rng = random::u32();
                  let mut attempts = 0;
                  let mut placed = false;
                  
                  while !placed && attempts < 50 {
                      attempts += 1;
-                     rng = (rng.wrapping_mul(1103515245).wrapping_add(12345)) % 2147483648;
-                     let nx = 60.0 + (rng % 392) as f32;
-                     rng = (rng.wrapping_mul(1103515245).wrapping_add(12345)) % 2147483648;
-                     let ny = 60.0 + (rng % 168) as f32;
+                     let nx = 60.0 + (self.rng.u32() % 392) as f32;
+                     let ny = 60.0 + (self.rng.u32() % 168) as f32;
                      
                      // Safety Checks
                      let mut safe = true;
@@ -550,15 +667,12 @@ impl MultiplayerGame {
                          }
                      }
                      // 4. River (L3/L5)
-                     if safe && self.current_level >= 3 {
-                         if nx > 236.0 && nx < 276.0 { safe = false; }
-                         if self.current_level == 5 && ny > 124.0 && ny < 164.0 { safe = false; }
-                     }
+                     if safe && self.on_river(nx, ny) { safe = false; }
                      
                      if safe {
                          self.obstacles[i].x = nx;
                          self.obstacles[i].y = ny;
-                         self.obstacles[i].respawn_timer = (random::u32() % 600) + 300; // Reset Timer
+                         self.obstacles[i].respawn_timer = (self.rng.u32() % 600) + 300; // Reset Timer
                          placed = true;
                          // Removed "Poof" particle effect to avoid confusion with explosion
                      }
@@ -587,8 +701,8 @@ impl MultiplayerGame {
                  
                  while !safe && attempts < 50 {
                      attempts += 1;
-                     px = 40.0 + (random::u32() % 432) as f32;
-                     py = 40.0 + (random::u32() % 208) as f32;
+                     px = 40.0 + (self.rng.u32() % 432) as f32;
+                     py = 40.0 + (self.rng.u32() % 208) as f32;
                      
                      safe = true;
                      
@@ -614,10 +728,7 @@ impl MultiplayerGame {
                      }
                      
                      // Level 3 River Strip
-                     if safe && self.current_level >= 3 {
-                         if px > 236.0 && px < 276.0 { safe = false; }
-                         if self.current_level == 5 && py > 124.0 && py < 164.0 { safe = false; }
-                     }
+                     if safe && self.on_river(px, py) { safe = false; }
                      
                      // 4. Not on Obstacles
                      if safe {
@@ -650,7 +761,7 @@ impl MultiplayerGame {
         // Freeze movement if shuffling (Level 2 pause)
         if !self.is_shuffling {
             for i in 0..self.players.len() {
-                let (dx, dy) = self.get_input(i, self.players[i].id);
+                let (dx, dy) = self.get_input(input, i, self.players[i].id);
             let mut speed = if self.players[i].boost_timer > 0 { 3.0 } else { 2.0 };
             
             // Level 3 Slow
@@ -669,7 +780,7 @@ impl MultiplayerGame {
                 let next_y = self.players[i].y + dy * speed;
 
                 // Level 3 Water Collision
-                if self.current_level >= 3 && self.is_in_water(next_x, next_y) {
+                if self.is_in_water(next_x, next_y) {
                     // Block movement - sliding logic?
                     // Simple: Don't update if in water. Check X and Y separately for slide.
                     if !self.is_in_water(next_x, self.players[i].y) {
@@ -721,10 +832,9 @@ impl MultiplayerGame {
                  // Power House (High Value) cannot be collected/captured
                  if dist < (player.radius + 12.0) && house.cooldown == 0 && !house.is_high_value {
                         // Check Team (Level 3)
-                        let is_wrong_team = self.current_level >= 3 && (
-                            (house.team == 1 && player.id != 1) || 
-                            (house.team == 2 && player.id != 2)
-                        );
+                        let is_wrong_team = self.rules.teams
+                            && house.team != 0
+                            && house.team != team_of(player.id, self.pairs);
                         
                         if is_wrong_team {
                              // PENALTY
@@ -738,7 +848,7 @@ impl MultiplayerGame {
                                 color: 0xFF0000FF, // Red Warning
                                 life: 60,
                              });
-                             turbo::audio::play("coin"); 
+                             self.sounds.push("coin".to_string()); 
                              
                              // Apply Cooldown (1s) instead of moving
                              house.cooldown = 60; 
@@ -752,11 +862,11 @@ impl MultiplayerGame {
                                 x: house.x,
                                 y: house.y - 20.0,
                                 text: format!("+{}", house.points),
-                                color: if house.team == 1 { 0xFFCDD2FF } else if house.team == 2 { 0xBBDEFBFF } else { 0xE0F7FAFF }, // Team Color
+                                color: if house.team == 0 { 0xE0F7FAFF } else { SLOTS[house.team as usize - 1].pop }, // Team Color
                                 life: 60,
                              });
 
-                             if self.current_level >= 3 {
+                             if self.rules.teams {
                                  house.cooldown = 300; // Disable first. Only enable if successfully moved.
                                  house_reshuffle_indices.push(i); 
                              } else {
@@ -768,7 +878,7 @@ impl MultiplayerGame {
                              house.last_collected_by = Some(player.id);
                              house.last_collection_time = current_tick;
                              
-                             turbo::audio::play("coin");
+                             self.sounds.push("coin".to_string());
                              sparkle_reqs.push((house.x, house.y));
                         }
                  }
@@ -784,7 +894,6 @@ impl MultiplayerGame {
         let decor_pos: Vec<(f32, f32)> = self.decors.iter().map(|d| (d.x, d.y)).collect();
         
         for idx in house_reshuffle_indices {
-             let mut rng = random::u32();
              let mut attempts = 0;
              let mut placed = false;
              
@@ -797,21 +906,19 @@ impl MultiplayerGame {
              
              while !placed && attempts < 1000 {
                  attempts += 1;
-                 rng = (rng.wrapping_mul(1103515245).wrapping_add(12345)) % 2147483648;
                  
                  if target_right {
                       // Target: 276..472
-                      new_x = 280.0 + (rng % 190) as f32;
+                      new_x = 280.0 + (self.rng.u32() % 190) as f32;
                  } else {
                       // Target: 40..236
-                      new_x = 40.0 + (rng % 190) as f32;
+                      new_x = 40.0 + (self.rng.u32() % 190) as f32;
                  }
                  
-                 rng = (rng.wrapping_mul(1103515245).wrapping_add(12345)) % 2147483648;
-                 new_y = 40.0 + (rng % 180) as f32; 
+                 new_y = 40.0 + (self.rng.u32() % 180) as f32; 
                  
                  // Water Strip Check (Redundant if logic correct, but safe)
-                 if new_x > 236.0 && new_x < 276.0 { continue; }
+                 if in_any(&self.water, new_x, new_y) { continue; }
                  
                  let mut safe = true;
                  
@@ -855,7 +962,7 @@ impl MultiplayerGame {
                          let ty = gy as f32;
                          
                          // Water Check
-                         if tx > 236.0 && tx < 276.0 { continue; }
+                         if in_any(&self.water, tx, ty) { continue; }
                          
                          let mut safe = true;
                          // Check Obstacles
@@ -911,8 +1018,8 @@ impl MultiplayerGame {
         }
         self.floating_texts.retain(|t| t.life > 0);
         
-        // Level 4 & 5: Snowman Collision & Power House Logic
-        if self.current_level >= 4 {
+        // Snowman Collision
+        if self.rules.hazards {
              for p in self.players.iter_mut() {
                  if p.invuln_timer > 0 { continue; }
                  
@@ -923,7 +1030,7 @@ impl MultiplayerGame {
                              // Hit Snowman!
                              p.score = p.score.saturating_sub(10);
                              p.invuln_timer = 60; // 1s invuln
-                             turbo::audio::play("hit");
+                             self.sounds.push("hit".to_string());
                              
                              self.floating_texts.push(FloatingText {
                                 x: p.x,
@@ -937,7 +1044,10 @@ impl MultiplayerGame {
                  }
              }
              
-             // Power House Logic
+        }
+
+        // Power House Logic
+        if self.rules.power_house() {
              let mut new_house_pos = None;
              let mut house_idx = 0;
              
@@ -948,11 +1058,11 @@ impl MultiplayerGame {
                      } else {
                          // Spawn Risky Gift!
                          // Level 5: 8s, Level 4: 15s
-                         let timer_reset = if self.current_level == 5 { 8 * 60 } else { 15 * 60 };
+                         let timer_reset = self.rules.risky_gift_secs * 60;
                          h.gift_timer = timer_reset;
                          
                          // Determine Effect (+60 or -60)
-                         let is_good = (random::u32() % 2) == 0;
+                         let is_good = (self.rng.u32() % 2) == 0;
                          let val = if is_good { 60 } else { -60 };
                          
                          // Spawn Powerup near house
@@ -974,20 +1084,16 @@ impl MultiplayerGame {
              
              // Handle Teleportation outside the loop to avoid borrow checker issues
              if let Some(_) = new_house_pos {
-                 let mut rng = random::u32();
                  let mut attempts = 0;
                  let mut placed = false;
                  
                  while !placed && attempts < 100 {
                      attempts += 1;
-                     rng = (rng.wrapping_mul(1103515245).wrapping_add(12345)) % 2147483648;
-                     let nx = 40.0 + (rng % 432) as f32;
-                     rng = (rng.wrapping_mul(1103515245).wrapping_add(12345)) % 2147483648;
-                     let ny = 40.0 + (rng % 208) as f32;
+                     let nx = 40.0 + (self.rng.u32() % 432) as f32;
+                     let ny = 40.0 + (self.rng.u32() % 208) as f32;
                      
                      // River Check
-                      if nx > 236.0 && nx < 276.0 { continue; }
-                      if self.current_level == 5 && ny > 124.0 && ny < 164.0 { continue; }
+                      if self.on_river(nx, ny) { continue; }
                      
                      let mut safe = true;
                      for o in &self.obstacles {
@@ -1010,8 +1116,8 @@ impl MultiplayerGame {
                          for _ in 0..10 {
                               self.particles.push(MParticle {
                                   x: nx, y: ny,
-                                  vx: ((random::u32() % 10) as f32 - 5.0) * 0.5,
-                                  vy: ((random::u32() % 10) as f32 - 5.0) * 0.5,
+                                  vx: ((self.rng.u32() % 10) as f32 - 5.0) * 0.5,
+                                  vy: ((self.rng.u32() % 10) as f32 - 5.0) * 0.5,
                                   life: 30,
                                   color: 0xFFD700FF,
                               });
@@ -1023,7 +1129,7 @@ impl MultiplayerGame {
 
 
          // Level 5: Dog Logic
-         if self.current_level == 5 {
+         if self.rules.dog {
              if let Some(target_id) = self.dog_target {
                  self.dog_state = 1; // Chasing
                  
@@ -1070,7 +1176,7 @@ impl MultiplayerGame {
                                // Visuals & Sound
                                self.floating_texts.push(FloatingText { x: p.x, y: p.y - 30.0, text: "-100".to_string(), color: 0xFF0000FF, life: 60 });
                                self.floating_texts.push(FloatingText { x: p.x, y: p.y - 45.0, text: "CHOMP!".to_string(), color: 0xFF0000FF, life: 60 });
-                               turbo::audio::play("hit"); 
+                               self.sounds.push("hit".to_string()); 
                                
                                // Reset Dog
                                self.dog_target = None;
@@ -1139,7 +1245,7 @@ impl MultiplayerGame {
                              text_str = format!("+{}", val);
                              
                              // Bonus: Stop Dog if being chased
-                             if self.current_level == 5 && self.dog_target == Some(player.id) {
+                             if self.rules.dog && self.dog_target == Some(player.id) {
                                  self.dog_target = None;
                                  self.floating_texts.push(FloatingText { x: player.x, y: player.y - 30.0, text: "SAFE!".to_string(), color: 0x00FF00FF, life: 60 });
                              }
@@ -1151,10 +1257,10 @@ impl MultiplayerGame {
                              text_str = format!("{}", val);
                              
                              // Penalty: Start Dog Chase (Level 5)
-                             if self.current_level == 5 {
+                             if self.rules.dog {
                                  self.dog_target = Some(player.id);
                                  self.floating_texts.push(FloatingText { x: player.x, y: player.y - 30.0, text: "RUN!".to_string(), color: 0xFF0000FF, life: 60 });
-                                 turbo::audio::play("sleigh_bells"); // Alert sound
+                                 self.sounds.push("sleigh_bells".to_string()); // Alert sound
                              }
                          }
                          
@@ -1167,7 +1273,7 @@ impl MultiplayerGame {
                         });
                      }
                      sparkle_reqs.push((pu.x, pu.y));
-                     turbo::audio::play("coin");
+                     self.sounds.push("coin".to_string());
                 }
              }
          }
@@ -1177,36 +1283,19 @@ impl MultiplayerGame {
             self.spawn_sparkles(sx, sy);
         }
 
-        // Update Particles
-        for p in self.particles.iter_mut() {
-            p.x += p.vx;
-            p.y += p.vy;
-            if p.life > 0 { p.life -= 1; }
-        }
-        self.particles.retain(|p| p.life > 0);
-        
-        // Update Env Snow
-        for s in self.env_snow.iter_mut() {
-            s.y += s.speed;
-            if s.y > 288.0 {
-                s.y = -5.0;
-                s.x = (random::u32() % 512) as f32;
-            }
-        }
+        self.update_effects();
 
          self.powerups.retain(|p| !p.collected);
          
-         // Level 2 Mechanics
-         if self.current_level == 2 {
-             self.update_level2();
+         if self.rules.shuffle {
+             self.update_shuffle();
          }
-         // Level 3 Mechanics
-         if self.current_level >= 3 {
-             self.update_level3();
+         if self.rules.trails {
+             self.update_trails();
          }
-         
-         // Generic Obstacle Collision (Levels 2+)
-         if self.current_level >= 2 {
+         if self.rules.hazards {
+             self.update_obstacles();
+             // Generic Obstacle Collision
              self.check_obstacle_collisions();
          }
     }
@@ -1217,25 +1306,17 @@ impl MultiplayerGame {
         // Wait, normally generate_houses replaced all. 
         // Let's assume this pushes new houses.
         
-        let mut rng = random::u32();
         let mut attempts = 0;
         let target_len = self.houses.len() + count;
         
         while self.houses.len() < target_len && attempts < 200 {
             attempts += 1;
-            rng = (rng.wrapping_mul(1103515245).wrapping_add(12345)) % 2147483648;
-            let hx = 40.0 + (rng % 432) as f32; 
-            rng = (rng.wrapping_mul(1103515245).wrapping_add(12345)) % 2147483648;
-            let hy = 40.0 + (rng % 180) as f32; 
+            let hx = 40.0 + (self.rng.u32() % 432) as f32; 
+            let hy = 40.0 + (self.rng.u32() % 180) as f32; 
             
-            // Avoid Water Strip (Level 3)
-            if self.current_level == 3 {
-                 // Vertical Strip 236-276
-                 if hx > 236.0 && hx < 276.0 {
-                     // Check Safe Bridge (130-158)
-                     // But usually we don't put houses on bridge if it's narrow
-                     continue; 
-                 }
+            // Avoid Water Strip (bridges included, they're narrow)
+            if self.on_river(hx, hy) {
+                 continue; 
             }
             
             // Check overlap
@@ -1283,7 +1364,7 @@ impl MultiplayerGame {
         });
     }
 
-    fn update_level3(&mut self) {
+    fn update_trails(&mut self) {
         // 1. Shadow Trail Logic
         if self.frame_count % 5 == 0 {
             // Record Trail
@@ -1313,23 +1394,26 @@ impl MultiplayerGame {
             .map(|p| p.shadow_trail.iter().map(|t| (t.0, t.1)).collect())
             .collect();
             
+        let teams: Vec<u8> = self.players.iter().map(|p| team_of(p.id, self.pairs)).collect();
+            
         for (i, p) in self.players.iter_mut().enumerate() {
             if p.invuln_timer == 0 {
-                // Check against opponent trail usually (just other player for now)
-                let opponent_idx = if i == 0 { 1 } else { 0 };
-                if opponent_idx < trails.len() {
-                    for (tx, ty) in &trails[opponent_idx] {
-                         if ((p.x - tx).powi(2) + (p.y - ty).powi(2)).sqrt() < 10.0 {
-                            p.slow_timer = 30; // 0.5s slow
-                            break;
-                        }
-                    }
+                // Every opponent's trail slows; a teammate's doesn't
+                let touching = (0..trails.len())
+                    .filter(|&j| teams[j] != teams[i])
+                    .flat_map(|j| &trails[j])
+                    .any(|(tx, ty)| ((p.x - tx).powi(2) + (p.y - ty).powi(2)).sqrt() < 10.0);
+                if touching {
+                    p.slow_timer = 30; // 0.5s slow
                 }
             }
         }
         
-        // 2. Obstacle Logic (Wood: Static, Bomb: Respawning)
-        // Reuse Level 2 Bomb Logic for kind==0, Add Wood logic for kind==1
+    }
+
+    // Bombs respawning after going off, and bombs and wood hurting on contact
+    fn update_obstacles(&mut self) {
+        // Obstacle Logic (Wood: Static, Bomb: Respawning)
         let mut penalties = vec![];
         let mut explosions = vec![];
         
@@ -1339,30 +1423,19 @@ impl MultiplayerGame {
         
         for (i, o) in self.obstacles.iter_mut().enumerate() {
             if o.kind == 0 { // Bomb
-                 // ... Copy Level 2 Bomb Logic ...
-                 // (omitted duplicate code for brevity, will insert full logic if tool allows large block)
-                 // Actually, reusing update_level2 is tricky because it assumes ALL are bombs.
-                 // We should probably share the logic or duplicate it.
-                 // Let's duplicate the Bomb logic here for clarity and safety.
                  if o.respawn_timer > 0 {
                     o.respawn_timer -= 1;
                     if o.respawn_timer == 0 {
                          // Respawn Logic
-                         let mut rng = random::u32();
                          let mut attempts = 0;
                          let mut placed = false;
                          while !placed && attempts < 50 {
                              attempts += 1;
-                             rng = (rng.wrapping_mul(1103515245).wrapping_add(12345)) % 2147483648;
-                             let ox = 60.0 + (rng % 392) as f32; 
-                             rng = (rng.wrapping_mul(1103515245).wrapping_add(12345)) % 2147483648;
-                             let oy = 60.0 + (rng % 168) as f32;
+                             let ox = 60.0 + (self.rng.u32() % 392) as f32; 
+                             let oy = 60.0 + (self.rng.u32() % 168) as f32;
                              let mut safe = true;
                              // Level 3 Check: Water (Strict Ban on entire strip including bridges)
-                             if self.current_level >= 3 {
-                                 if ox > 236.0 && ox < 276.0 { safe = false; } // Ban entire strip
-                                 if self.current_level == 5 && oy > 124.0 && oy < 164.0 { safe = false; }
-                             }
+                             if in_any(&self.water, ox, oy) { safe = false; } // Ban entire strip
                              
                              if safe {
                                  for h in &self.houses { if ((h.x - ox).powi(2) + (h.y - oy).powi(2)).sqrt() < 60.0 { safe = false; break; } }
@@ -1380,7 +1453,7 @@ impl MultiplayerGame {
                               if safe { o.x = ox; o.y = oy; placed = true; }
                          }
                          // Reset timer on success or failure just to keep it moving
-                         o.respawn_timer = (random::u32() % 600) + 300;
+                         o.respawn_timer = (self.rng.u32() % 600) + 300;
                     }
                     continue;
                  }
@@ -1407,7 +1480,7 @@ impl MultiplayerGame {
                          if p.score >= 10 { p.score -= 10; } else { p.score = 0; }
                          penalties.push((p.x, p.y, 10)); // -10
                          // No respawn for wood
-                         turbo::audio::play("hit"); 
+                         self.sounds.push("hit".to_string()); 
                      }
                 }
              }
@@ -1417,7 +1490,7 @@ impl MultiplayerGame {
             self.floating_texts.push(FloatingText { x: px, y: py - 20.0, text: format!("-{}", amount), color: 0xFF0000FF, life: 60 });
         }
         for (ex, ey) in explosions {
-             turbo::audio::play("projectile_hit"); 
+             self.sounds.push("projectile_hit".to_string()); 
              self.spawn_explosion(ex, ey);
         }
     }
@@ -1451,7 +1524,7 @@ impl MultiplayerGame {
                          if p.score >= 10 { p.score -= 10; } else { p.score = 0; }
                          penalties.push((p.x, p.y, 10)); // -10
                          // No respawn for wood
-                         turbo::audio::play("hit"); 
+                         self.sounds.push("hit".to_string()); 
                      }
                      // Snowman (Kind 2) logic is separate in update/L4 block usually, or can merge here.
                      // Let's keep Snowman separate as it uses radius vs radius check in L4 block.
@@ -1463,51 +1536,22 @@ impl MultiplayerGame {
             self.floating_texts.push(FloatingText { x: px, y: py - 20.0, text: format!("-{}", amount), color: 0xFF0000FF, life: 60 });
         }
         for (ex, ey) in explosions {
-             turbo::audio::play("projectile_hit"); 
+             self.sounds.push("projectile_hit".to_string()); 
              self.spawn_explosion(ex, ey);
         }
     }
     
-    fn is_in_water(&self, x: f32, y: f32) -> bool {
-        // Vertical River (236..276)
-        if x > 236.0 && x < 276.0 {
-            // Level 3/4 Bridges
-            if self.current_level >= 3 && self.current_level < 5 {
-                if y > 86.0 && y < 114.0 { return false; }
-                if y > 186.0 && y < 214.0 { return false; }
-            }
-            // Level 5: 4 Strategic Bridges
-            if self.current_level == 5 {
-                // Vertical Crossing Bridges
-                // Top: y=50..78 (Safe 50-78)
-                if y > 50.0 && y < 78.0 { return false; }
-                // Bot: y=210..238 (Safe 210-238)
-                if y > 210.0 && y < 238.0 { return false; }
-            }
-            return true;
-        }
-        // Horizontal River (Level 5) (124..164)
-        if self.current_level == 5 {
-            if y > 124.0 && y < 164.0 {
-                 // Horizontal Crosisng Bridges
-                 // Left: x=100..128
-                 if x > 100.0 && x < 128.0 { return false; }
-                 // Right: x=380..408
-                 if x > 380.0 && x < 408.0 { return false; }
-                 
-                 return true;
-            }
-        }
-        false
-    }
-    
-    #[allow(dead_code)]
-    fn is_valid_bomb_pos(&self, x: f32, y: f32) -> bool {
-         if self.current_level == 3 && self.is_in_water(x, y) { return false; }
-         true
+    // Water you can't walk on: the river, less its bridges
+    pub fn is_in_water(&self, x: f32, y: f32) -> bool {
+        in_any(&self.water, x, y) && !in_any(&self.bridges, x, y)
     }
 
-    fn update_level2(&mut self) {
+    // Anywhere along a river, bridges included; nothing gets placed there
+    pub fn on_river(&self, x: f32, y: f32) -> bool {
+        in_any(&self.water, x, y)
+    }
+    
+    fn update_shuffle(&mut self) {
         // Shuffle Logic
         if self.is_shuffling {
             if self.shuffle_pause_timer > 0 {
@@ -1544,117 +1588,18 @@ impl MultiplayerGame {
                 self.shuffle_pause_timer = 60; // 1 sec pause
             }
         }
-        
-        // Obstacle Collision & Respawn
-        let mut penalties = vec![];
-        let mut explosions = vec![];
-        
-        // Snapshot existing positions to check for overlaps during respawn
-        let obstacle_positions: Vec<(f32, f32)> = self.obstacles.iter().map(|o| (o.x, o.y)).collect();
-        
-        // Handle Respawning Obstacles
-        for (i, o) in self.obstacles.iter_mut().enumerate() {
-            if o.respawn_timer > 0 {
-                o.respawn_timer -= 1;
-                if o.respawn_timer == 0 {
-                     // Respawn Logic
-                     let mut rng = random::u32();
-                     let mut attempts = 0;
-                     let mut placed = false;
-                     while !placed && attempts < 50 {
-                         attempts += 1;
-                         rng = (rng.wrapping_mul(1103515245).wrapping_add(12345)) % 2147483648;
-                         let ox = 60.0 + (rng % 392) as f32; 
-                         rng = (rng.wrapping_mul(1103515245).wrapping_add(12345)) % 2147483648;
-                         let oy = 60.0 + (rng % 168) as f32;
-                         
-                         let mut safe = true;
-                         // Check houses
-                         for h in &self.houses {
-                            if ((h.x - ox).powi(2) + (h.y - oy).powi(2)).sqrt() < 50.0 { safe = false; break; }
-                         }
-                         
-                         // Level 3 River Strip
-                         if self.current_level == 3 && ox > 236.0 && ox < 276.0 { safe = false; }
-
-                         // Check players (don't spawn on top)
-                         if safe {
-                             for p in &self.players {
-                                 if ((p.x - ox).powi(2) + (p.y - oy).powi(2)).sqrt() < 60.0 { safe = false; break; }
-                             }
-                         }
-                         // Check other obstacles
-                         if safe {
-                             for (idx, (ex, ey)) in obstacle_positions.iter().enumerate() {
-                                 if i != idx { // Don't check against self (old pos)
-                                     if ((ex - ox).powi(2) + (ey - oy).powi(2)).sqrt() < 40.0 { safe = false; break; }
-                                 }
-                             }
-                         }
-                         
-                         if safe {
-                             o.x = ox;
-                             o.y = oy;
-                             placed = true;
-                         }
-                     }
-                }
-                continue; // Don't collide if respawning
-            }
-            
-            // Collision Check
-            for p in self.players.iter_mut() {
-                if p.invuln_timer > 0 { continue; }
-
-                // Circle collision for Bomb (radius vs radius)
-                // Bomb radius approx w/2 = 12
-                let bomb_radius = 12.0;
-                let dx = p.x - (o.x + o.w/2.0);
-                let dy = p.y - (o.y + o.h/2.0);
-                let dist = (dx*dx + dy*dy).sqrt();
-
-                if dist < (p.radius + bomb_radius) {
-                    // Hit!
-                    if p.score >= 20 { p.score -= 20; } else { p.score = 0; }
-                    p.invuln_timer = 60; // 1s invuln
-                    
-                    penalties.push((p.x, p.y));
-                    explosions.push((o.x + o.w/2.0, o.y + o.h/2.0));
-                    
-                    // Trigger Respawn
-                    o.respawn_timer = 30; // 0.5s delay
-                    o.x = -1000.0; // Hide
-                }
-            }
-        }
-        
-        // Apply penalties (Sound & Text)
-        for (px, py) in penalties {
-            self.floating_texts.push(FloatingText {
-                x: px,
-                y: py - 20.0,
-                text: "-20".to_string(),
-                color: 0xFF0000FF, // Red
-                life: 60,
-            });
-        }
-        
-        // Apply Explosions
-        for (ex, ey) in explosions {
-             turbo::audio::play("projectile_hit"); 
-             self.spawn_explosion(ex, ey);
-        }
     }
 
     fn spawn_explosion(&mut self, x: f32, y: f32) {
+        self.shake = 3;
         for _ in 0..12 {
-            let angle = (random::u32() % 360) as f32 * 3.14 / 180.0;
-            let speed = (random::u32() % 30) as f32 / 10.0 + 2.0;
+            let angle = (self.rng.u32() % 360) as f32 * 3.14 / 180.0;
+            let speed = (self.rng.u32() % 30) as f32 / 10.0 + 2.0;
             self.particles.push(MParticle {
                 x, y,
                 vx: angle.cos() * speed,
                 vy: angle.sin() * speed,
-                life: 20 + (random::u32() % 15),
+                life: 20 + (self.rng.u32() % 15),
                 color: 0xFF5722FF, // Orange/Red Boom
             });
         }
@@ -1662,20 +1607,40 @@ impl MultiplayerGame {
 
     fn spawn_sparkles(&mut self, x: f32, y: f32) {
         for _ in 0..8 {
-            let angle = (random::u32() % 360) as f32 * 3.14 / 180.0;
-            let speed = (random::u32() % 20) as f32 / 10.0 + 1.0;
+            let angle = (self.rng.u32() % 360) as f32 * 3.14 / 180.0;
+            let speed = (self.rng.u32() % 20) as f32 / 10.0 + 1.0;
             self.particles.push(MParticle {
                 x, y,
                 vx: angle.cos() * speed,
                 vy: angle.sin() * speed,
-                life: 30 + (random::u32() % 20),
+                life: 30 + (self.rng.u32() % 20),
                 color: 0xFFFF00FF, // Yellow sparkles
             });
         }
     }
 
-    fn get_input(&self, index: usize, _id: u8) -> (f32, f32) {
-        let gp = gamepad::get(index);
+    // Particles and falling snow; online clients run just this between snapshots
+    pub fn update_effects(&mut self) {
+        // Update Particles
+        for p in self.particles.iter_mut() {
+            p.x += p.vx;
+            p.y += p.vy;
+            if p.life > 0 { p.life -= 1; }
+        }
+        self.particles.retain(|p| p.life > 0);
+
+        // Update Env Snow
+        for s in self.env_snow.iter_mut() {
+            s.y += s.speed;
+            if s.y > 288.0 {
+                s.y = -5.0;
+                s.x = (self.rng.u32() % 512) as f32;
+            }
+        }
+    }
+
+    fn get_input(&self, input: &Input, index: usize, _id: u8) -> (f32, f32) {
+        let gp = input.pad(index);
         let mut dx = 0.0f32;
         let mut dy = 0.0f32;
         
@@ -1693,108 +1658,109 @@ impl MultiplayerGame {
         (dx, dy)
     }
     
+    pub fn team_count(&self) -> u32 {
+        if self.pairs { 2 } else { self.names.len() as u32 }
+    }
+
+    // Players, or pairs, best score first
+    pub fn standings(&self) -> Vec<Standing> {
+        self.ranked(|i| (0, self.players[i].score))
+    }
+
+    // Series tally: most rounds won first, then most points over all rounds
+    pub fn series_standings(&self) -> Vec<Standing> {
+        self.ranked(|i| (self.series.wins[i], self.series.points[i]))
+    }
+
+    // Groups players into their teams and sorts them; `values` gives (wins, score) per player.
+    // Teammates share their wins, so a pair counts them once.
+    fn ranked(&self, values: impl Fn(usize) -> (u32, u32)) -> Vec<Standing> {
+        let mut standings: Vec<Standing> = vec![];
+        for (i, p) in self.players.iter().enumerate() {
+            let team = team_of(p.id, self.pairs);
+            let (wins, score) = values(i);
+            match standings.iter_mut().find(|s| s.team == team) {
+                Some(s) => {
+                    s.name = format!("{} & {}", s.name, p.name);
+                    s.score += score;
+                    s.wins = s.wins.max(wins);
+                },
+                None => standings.push(Standing { name: p.name.clone(), score, wins, team }),
+            }
+        }
+        standings.sort_by_key(|s| std::cmp::Reverse((s.wins, s.score)));
+        standings
+    }
+
+    // "X Wins!" for the top of the standings, or a draw when the top two are level
+    fn winner_line(&self, standings: &[Standing], draw: &str, suffix: &str) -> String {
+        let (top, next) = (&standings[0], &standings[1]);
+        if (top.wins, top.score) == (next.wins, next.score) {
+            draw.to_string()
+        } else if self.pairs {
+            format!("{} Win{}", top.name.to_uppercase(), suffix)
+        } else {
+            format!("{} Wins{}", top.name.to_uppercase(), suffix)
+        }
+    }
+
     fn end_game(&mut self) {
-        self.game_over = true;
-        let s1 = self.players[0].score;
-        let s2 = self.players[1].score;
-        if s1 > s2 {
-            let p = &self.players[0];
-            self.winner_text = format!("{} Wins!", p.name.to_uppercase());
-        } else if s2 > s1 {
-            let p = &self.players[1];
-            self.winner_text = format!("{} Wins!", p.name.to_uppercase());
+        // A series only scores each round once
+        if !self.series.is_single() && (self.game_over || self.next_level_timer > 0) {
+            return;
+        }
+        let standings = self.standings();
+        self.winner_text = self.winner_line(&standings, "MATCH DRAW!", "!");
+        if self.series.is_single() {
+            self.game_over = true;
+            return;
+        }
+
+        let scores: Vec<u32> = self.players.iter().map(|p| p.score).collect();
+        let winners: Vec<usize> = if standings[0].score == standings[1].score {
+            vec![]
         } else {
-            self.winner_text = "MATCH DRAW!".to_string();
+            (0..self.players.len()).filter(|&i| team_of(self.players[i].id, self.pairs) == standings[0].team).collect()
+        };
+        self.series.record(&scores, &winners);
+        if self.series.is_decided() {
+            self.game_over = true;
+        } else {
+            self.next_level_timer = INTERMISSION;
         }
     }
 
-    pub fn draw(&self) {
+    pub fn draw(&self, settings: &Settings) {
+        self.draw_field(settings);
+        self.draw_hud();
+    }
+
+    // The field and everything on it, without scores or overlays (the arena editor draws this too)
+    pub fn draw_field(&self, settings: &Settings) {
         // 1. Background (Light Green Winter - Mint/Pastel)
         rect!(w=512, h=288, color=0xC8E6C9FF); 
 
-        // 1b. Level 3 Terrain (Water/Bridges) - Draw FIRST
-        if self.current_level >= 3 {
-            // Vertical Water Strip
-            rect!(x=236, y=0, w=40, h=288, color=0x29B6F6FF); 
-            
-            // Level 5: Horizontal Water Strip
-            if self.current_level == 5 {
-                rect!(x=0, y=124, w=512, h=40, color=0x29B6F6FF); 
-                
-                // 4 Bridges (Top, Bottom, Left, Right of Center)
-                // Center is approx (256, 144)
-                // Vertical River: 236..276. Horizontal River: 124..164.
-                
-                // 1. Top Bridge (Crosses Horizontal River verticaly)
-                // Position: x=256(center of bridge w=24), y=hub centered? No, bridges crossing TO center or FROM center?
-                // User said: "Har side se sirf ek‑ek bridge add karo" (One bridge from each side).
-                // "Har quadrant ka sirf 1 bridge exit ho"
-                // This implies connecting the 4 quadrants to the center, or across?
-                // Let's place bridges connecting the quadrants across the rivers.
-                
-                // Let's do a Diamond formation around the center?
-                // Bridge 1: Connects Top-Left to Top-Right? No, vertical river.
-                // Let's place 4 bridges crossing the rivers near the center.
-                
-                // Top Bridge (Vertical, crossing Horizontal River) - Safe passage x=210, y=?
-                // Wait, rivers are + shape.
-                // Bridges need to cross the water.
-                
-                // Design:
-                // 1. Top Bridge (Crosses Horizontal River at x=150?) 
-                //    Connects Top-Left <-> Bot-Left? No that's vertical crossing.
-                //    Connects Top-Left <-> Top-Rigth? (Crosses Vertical River)
-                
-                // Let's place 4 Bridges crossing the rivers in a pattern.
-                // Top Bridge: Crosses Vertical River at y=60. (Connects Q1-Q2)
-                // Bot Bridge: Crosses Vertical River at y=220. (Connects Q3-Q4)
-                // Left Bridge: Crosses Horizontal River at x=120. (Connects Q1-Q3)
-                // Right Bridge: Crosses Horizontal River at x=390. (Connects Q2-Q4)
-                
-                // Draw Bridges
-                
-                // 1. Top Bridge (Vertical River Crossing)
-                rect!(x=236, y=50, w=40, h=28, color=0x8D6E63FF); 
-                rect!(x=236, y=48, w=40, h=2, color=0x5D4037FF); 
-                rect!(x=236, y=78, w=40, h=2, color=0x5D4037FF);
-                
-                // 2. Bottom Bridge (Vertical River Crossing)
-                rect!(x=236, y=210, w=40, h=28, color=0x8D6E63FF); 
-                rect!(x=236, y=208, w=40, h=2, color=0x5D4037FF); 
-                rect!(x=236, y=238, w=40, h=2, color=0x5D4037FF);
-                
-                // 3. Left Bridge (Horizontal River Crossing)
-                // Rotated Bridge? Rect w/h swapped.
-                // River is y=124..164 (h=40). Bridge needs to be h=40, w=28.
-                rect!(x=100, y=124, w=28, h=40, color=0x8D6E63FF);
-                rect!(x=98, y=124, w=2, h=40, color=0x5D4037FF);
-                rect!(x=128, y=124, w=2, h=40, color=0x5D4037FF);
-                
-                // 4. Right Bridge (Horizontal River Crossing)
-                rect!(x=380, y=124, w=28, h=40, color=0x8D6E63FF);
-                rect!(x=378, y=124, w=2, h=40, color=0x5D4037FF);
-                rect!(x=408, y=124, w=2, h=40, color=0x5D4037FF);
-            }
-            
-            // Bridge 1 (Top)
-            if self.current_level != 5 {
-                rect!(x=236, y=100, w=40, h=28, color=0x8D6E63FF); 
-                rect!(x=236, y=98, w=40, h=2, color=0x5D4037FF); 
-                rect!(x=236, y=128, w=40, h=2, color=0x5D4037FF);
-            }
-            
-            // Bridge 2 (Bot)
-            if self.current_level != 5 {
-                 rect!(x=236, y=200, w=40, h=28, color=0x8D6E63FF); 
-                 rect!(x=236, y=198, w=40, h=2, color=0x5D4037FF); 
-                 rect!(x=236, y=228, w=40, h=2, color=0x5D4037FF);
+        // 1b. Terrain (Water/Bridges) - Draw FIRST
+        for z in &self.water {
+            rect!(x=z.x as i32, y=z.y as i32, w=z.w as u32, h=z.h as u32, color=0x29B6F6FF);
+        }
+        // Wooden planks, railed along both sides of the crossing
+        for b in &self.bridges {
+            let (x, y, w, h) = (b.x as i32, b.y as i32, b.w as i32, b.h as i32);
+            rect!(x=x, y=y, w=w as u32, h=h as u32, color=0x8D6E63FF);
+            if b.crosses_vertically(&self.water) {
+                rect!(x=x-2, y=y, w=2, h=h as u32, color=0x5D4037FF);
+                rect!(x=x+w, y=y, w=2, h=h as u32, color=0x5D4037FF);
+            } else {
+                rect!(x=x, y=y-2, w=w as u32, h=2, color=0x5D4037FF);
+                rect!(x=x, y=y+h, w=w as u32, h=2, color=0x5D4037FF);
             }
         }
         
          // Draw Shadows (Level 3)
-        if self.current_level >= 3 {
+        if self.rules.trails {
              for (_i, p) in self.players.iter().enumerate() {
-                 let col = if p.id == 1 { 0xD32F2F44 } else { 0x1976D244 }; // Transparent Red/Blue
+                 let col = SLOTS[p.id as usize - 1].trail; // Transparent player color
                  for t in &p.shadow_trail {
                      circ!(x=t.0 as i32 - 4, y=t.1 as i32 - 4, d=8, color=col);
                  }
@@ -1919,12 +1885,11 @@ impl MultiplayerGame {
             } else {
                 // STANDARD HOUSE DESIGN (Existing)
                 let w = 24; let h_body = 18;
-                let (wall_c, roof_c) = if h.team == 1 {
-                     (0xB71C1CFF, 0xFFEBEEFF) // Red Wall, White Roof
-                } else if h.team == 2 {
-                     (0x0277BDFF, 0xE1F5FEFF) // Blue Wall, Cyan Roof
-                } else {
+                let (wall_c, roof_c) = if h.team == 0 {
                      (0xB22222FF, 0xFFFFFFFF) // Neutral
+                } else {
+                     let slot = &SLOTS[h.team as usize - 1];
+                     (slot.wall, slot.roof) // Team Wall, Light Roof
                 };
 
                 let wall_color = if active { wall_c } else { 0x444444FF };
@@ -2061,7 +2026,7 @@ impl MultiplayerGame {
         }
         
         // Particles
-        for p in &self.particles {
+        for p in self.particles.iter().step_by(settings.particle_step()) {
             rect!(x = p.x as i32, y = p.y as i32, w = 2, h = 2, color = p.color);
         }
         
@@ -2210,13 +2175,12 @@ impl MultiplayerGame {
         for p in &self.players {
             let x = p.x as i32;
             let y = p.y as i32;
-            let is_santa = p.id == 1;
             
             // Animation Bob
             let bob = ((self.frame_count / 10) % 2) as i32;
             
             // Colors
-            let suit_color = if is_santa { 0xD32F2FFF } else { 0x1976D2FF }; // Red vs Blue
+            let suit_color = SLOTS[p.id as usize - 1].suit; // Red, Blue, Green, Orange
             let trim_color = 0xFFFFFFFF; // White
             let skin_color = 0xFFCC80FF; // Peach
             let boot_color = 0x212121FF; // Black
@@ -2268,11 +2232,11 @@ impl MultiplayerGame {
             rect!(x=lx+18, y=ty-6, w=4, h=4, color=trim_color); // PomPom
             
             // Label (Optional, maybe remove if too cluttered, or keep small)
-            // text!(if is_santa{"P1"}else{"P2"}, x=x-6, y=ty-20, font="small", color=0xFFFFFFFF);
+            // text!(&format!("P{}", p.id), x=x-6, y=ty-20, font="small", color=0xFFFFFFFF);
         }
         
         // Level 5: Dog & Cage
-        if self.current_level == 5 {
+        if self.rules.dog {
             // Cage
             let cx = self.cage_pos.0 as i32;
             let cy = self.cage_pos.1 as i32;
@@ -2351,7 +2315,9 @@ impl MultiplayerGame {
                 text!("!", x=dx, y=dy-15, font="small", color=0xFF0000FF);
             }
         }
-        
+    }
+
+    fn draw_hud(&self) {
         // HUD Starts Here (Obstacles Loop Removed from here)
 
         // Draw Floating Score Pops (Moved here: After Players)
@@ -2363,26 +2329,54 @@ impl MultiplayerGame {
         
         // HUD (Dark Text for Light BG)
         // Red Flash on Penalty
-        let p1_col = if self.players[0].invuln_timer > 45 { 0xFF0000FF } else { 0xB71C1CFF };
-        let p1_text = format!("{}: {}", self.players[0].name, self.players[0].score);
-        text!(&p1_text, x=10, y=10, color=p1_col); // Dark Red
-        
-        let p2_col = if self.players[1].invuln_timer > 45 { 0xFF0000FF } else { 0x0D47A1FF };
-        let p2_text = format!("{}: {}", self.players[1].name, self.players[1].score);
-        let w = (p2_text.len() * 8) as i32;
-        text!(&p2_text, x=502 - w, y=10, color=p2_col); // Dark Blue
+        // One corner each: P1 top left, P2 top right, P3 bottom right, P4 bottom left
+        for (i, p) in self.players.iter().enumerate() {
+            let col = if p.invuln_timer > 45 { 0xFF0000FF } else { SLOTS[i].hud };
+            let p_text = format!("{}: {}", p.name, p.score);
+            let w = (p_text.len() * 8) as i32;
+            let x = if i == 1 || i == 2 { 502 - w } else { 10 };
+            let y = if i < 2 { 10 } else { 270 };
+            text!(&p_text, x=x, y=y, color=col);
+        }
         
         let mins = self.timer / 60;
         let secs = self.timer % 60;
         let time_text = format!("{:02}:{:02}", mins, secs);
         text!(&time_text, x=240, y=10, color=0xF57F17FF); // Dark Orange/Gold
         
-        if self.game_over {
+        if !self.series.is_single() {
+            let round = (self.series.round + 1).min(self.series.kind.rounds());
+            let round_text = format!("ROUND {}/{}", round, self.series.kind.rounds());
+            text!(&round_text, x=236, y=22, font="small", color=0xF57F17FF);
+        }
+        
+        if self.game_over && self.series.is_single() {
              // Overlay
              rect!(w=512, h=288, color=0xFFFFFFAA); // Light Overlay
-             text!(&self.winner_text, x=200, y=130, font="large", color=0x000000FF); // Black Text
-             text!("Press START to Restart", x=180, y=160, color=0x333333FF);
-             text!("Press X to Exit", x=200, y=180, color=0x333333FF);
+             text!(&self.winner_text, x=200, y=80, font="large", color=0x000000FF); // Black Text
+             self.draw_table(&self.standings(), 110, false);
+             text!("Press START to Restart", x=180, y=190, color=0x333333FF);
+             text!("Press X to Exit", x=200, y=210, color=0x333333FF);
+        } else if self.game_over {
+             // Champion Screen
+             rect!(w=512, h=288, color=0xFFFFFFCC);
+             let standings = self.series_standings();
+             let champion = self.winner_line(&standings, "SERIES DRAW!", " the Series!");
+             text!("SERIES CHAMPION", x=196, y=50, color=0xF57F17FF);
+             text!(&champion, x=160, y=70, font="large", color=0x000000FF);
+             self.draw_table(&standings, 110, true);
+             text!("Press START for a New Series", x=160, y=190, color=0x333333FF);
+             text!("Press X to Exit", x=200, y=210, color=0x333333FF);
+        } else if self.next_level_timer > 0 {
+             // Intermission between rounds
+             rect!(w=512, h=288, color=0xFFFFFFAA);
+             let header = format!("ROUND {} RESULT", self.series.round);
+             text!(&header, x=200, y=50, color=0xF57F17FF);
+             text!(&self.winner_text, x=200, y=70, font="large", color=0x000000FF);
+             self.draw_table(&self.series_standings(), 110, true);
+             let next = format!("Next: Level {} in {}", self.series.next_level(), self.next_level_timer / 60 + 1);
+             text!(&next, x=180, y=190, color=0x333333FF);
+             text!("Press A to Continue", x=188, y=210, color=0x333333FF);
         }
         
         // Vignette (Light edges/Frost?) 
@@ -2390,3 +2384,396 @@ impl MultiplayerGame {
         rect!(w=512, h=288, color=0xFFFFFF22); // Subtle Frost overlay
     }
 }
+
+impl MultiplayerGame {
+    // Ranked rows for the results screens; tied rows share a place
+    fn draw_table(&self, standings: &[Standing], top: i32, show_wins: bool) {
+        if show_wins {
+            text!("WINS", x=300, y=top - 14, font="small", color=0x555555FF);
+            text!("POINTS", x=340, y=top - 14, font="small", color=0x555555FF);
+        }
+        for (i, s) in standings.iter().enumerate() {
+            let place = 1 + standings.iter().filter(|o| (o.wins, o.score) > (s.wins, s.score)).count();
+            let suffix = match place { 1 => "st", 2 => "nd", 3 => "rd", _ => "th" };
+            let y = top + i as i32 * 16;
+            let place_text = format!("{}{}", place, suffix);
+            let score_text = s.score.to_string();
+            text!(&place_text, x=150, y=y, color=0x333333FF);
+            text!(&s.name, x=190, y=y, color=SLOTS[s.team as usize - 1].hud);
+            if show_wins {
+                let wins_text = s.wins.to_string();
+                text!(&wins_text, x=308, y=y, color=0x333333FF);
+            }
+            text!(&score_text, x=340, y=y, color=0x333333FF);
+        }
+    }
+}
+
+impl GameMode for MultiplayerGame {
+    fn from_replay(replay: &Replay) -> Self {
+        let mut game = MultiplayerGame::with_players(replay.names(), replay.pairs, replay.minutes, replay.level, replay.seed);
+        game.rival_ai = replay.rival.map(|level| RivalAi::new(level, replay.seed));
+        if let Some(arena) = &replay.arena {
+            game.load_arena(arena.clone());
+        }
+        game.start_series(replay.series);
+        game
+    }
+
+    fn update(&mut self, input: &Input) {
+        MultiplayerGame::update(self, input)
+    }
+
+    fn draw(&self, settings: &Settings) {
+        MultiplayerGame::draw(self, settings)
+    }
+
+    fn is_running(&self) -> bool {
+        !self.game_over
+    }
+
+    fn take_shake(&mut self) -> usize {
+        std::mem::take(&mut self.shake)
+    }
+
+    fn take_sounds(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.sounds)
+    }
+
+    fn instructions(&self) -> &'static [&'static str] {
+        &[
+            "Multiplayer:",
+            "- Keys: your device's, as the join screen lists",
+            "- Start / Esc: Pause Menu",
+            "- B: Leave (after the match)",
+            "Goal: Deliver to the most houses!",
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::input::{InputFeed, BTN_A, BTN_RIGHT, BTN_START};
+    use crate::model::arena::{ArenaHouse, ArenaObstacle};
+    use crate::model::replay::ReplayMode;
+
+    // Empty arena with a single house under player 1's spawn (50, 50)
+    fn arena(level: u32, team: u8, points: u32) -> MultiplayerGame {
+        let mut game = MultiplayerGame::new("Santa".to_string(), "Rival".to_string(), 3, level, 7);
+        game.obstacles.clear();
+        game.powerups.clear();
+        game.houses = vec![House {
+            x: 50.0, y: 50.0, points, cooldown: 0,
+            last_collected_by: None, last_collection_time: 0,
+            is_high_value: false, team, gift_timer: 0,
+        }];
+        game
+    }
+
+    #[test]
+    fn capturing_house_awards_its_points() {
+        let mut game = arena(1, 0, 25);
+
+        game.update(&InputFeed::default().next([0, 0]));
+
+        assert_eq!(game.players[0].score, 25);
+        assert_eq!(game.players[1].score, 0);
+        let house = &game.houses[0];
+        assert_eq!(house.points, 5);
+        assert_eq!(house.cooldown, 300);
+        assert_eq!(house.last_collected_by, Some(1));
+        assert!(game.sounds.iter().any(|s| s == "coin"));
+    }
+
+    #[test]
+    fn house_on_cooldown_is_ignored() {
+        let mut game = arena(1, 0, 25);
+        game.houses[0].cooldown = 100;
+
+        game.update(&InputFeed::default().next([0, 0]));
+
+        assert_eq!(game.players[0].score, 0);
+    }
+
+    #[test]
+    fn wrong_team_house_is_penalised() {
+        let mut game = arena(3, 2, 25);
+        game.players[0].score = 30;
+
+        game.update(&InputFeed::default().next([0, 0]));
+
+        assert_eq!(game.players[0].score, 10);
+        assert_eq!(game.houses[0].cooldown, 60);
+        assert_eq!(game.houses[0].points, 25);
+    }
+
+    #[test]
+    fn end_game_picks_winner_or_draw() {
+        let mut game = arena(1, 0, 5);
+
+        game.players[0].score = 40;
+        game.players[1].score = 40;
+        game.end_game();
+        assert!(game.game_over);
+        assert_eq!(game.winner_text, "MATCH DRAW!");
+
+        game.players[1].score = 45;
+        game.end_game();
+        assert_eq!(game.winner_text, "RIVAL Wins!");
+
+        game.players[0].score = 50;
+        game.end_game();
+        assert_eq!(game.winner_text, "SANTA Wins!");
+    }
+
+    fn four_player(pairs: bool, level: u32) -> MultiplayerGame {
+        let names = ["A", "B", "C", "D"].map(String::from).to_vec();
+        MultiplayerGame::with_players(names, pairs, 3, level, 11)
+    }
+
+    #[test]
+    fn every_player_gets_a_corner_and_color() {
+        let game = four_player(false, 1);
+        assert_eq!(game.players.len(), 4);
+        for (i, p) in game.players.iter().enumerate() {
+            assert_eq!(p.id as usize, i + 1);
+            assert_eq!((p.x, p.y), SLOTS[i].spawn);
+            assert_eq!(p.color, SLOTS[i].color);
+        }
+    }
+
+    #[test]
+    fn team_houses_split_evenly_between_players() {
+        for level in 3..=5 {
+            let game = four_player(false, level);
+            for team in 1..=4 {
+                assert!(game.houses.iter().filter(|h| h.team == team).count() >= 3, "level {} team {}", level, team);
+            }
+            let pairs = four_player(true, level);
+            assert!(pairs.houses.iter().all(|h| h.team <= 2));
+        }
+    }
+
+    #[test]
+    fn pairs_share_houses() {
+        // Player 3 is on player 1's team, so team 1 houses are theirs
+        let mut game = four_player(true, 3);
+        game.obstacles.clear();
+        game.powerups.clear();
+        let (x, y) = SLOTS[2].spawn;
+        let house = House { x, y, points: 25, cooldown: 0, last_collected_by: None, last_collection_time: 0, is_high_value: false, team: 1, gift_timer: 0 };
+        game.houses = vec![house.clone()];
+
+        game.update(&InputFeed::default().next([0, 0, 0, 0]));
+        assert_eq!(game.players[2].score, 25);
+
+        // In a free-for-all the same house belongs to player 1 alone
+        let mut ffa = four_player(false, 3);
+        ffa.obstacles.clear();
+        ffa.powerups.clear();
+        ffa.houses = vec![house];
+        ffa.players[2].score = 30;
+        ffa.update(&InputFeed::default().next([0, 0, 0, 0]));
+        assert_eq!(ffa.players[2].score, 10);
+    }
+
+    #[test]
+    fn only_opponent_trails_slow() {
+        let mut game = four_player(true, 3);
+        // Player 3 stands on their teammate's trail, player 2 on an opponent's
+        game.players[0].shadow_trail = vec![(SLOTS[2].spawn.0, SLOTS[2].spawn.1, 100)];
+        game.players[2].shadow_trail = vec![(SLOTS[1].spawn.0, SLOTS[1].spawn.1, 100)];
+        game.frame_count = 1;
+
+        game.update_trails();
+
+        assert_eq!(game.players[2].slow_timer, 0);
+        assert_eq!(game.players[1].slow_timer, 30);
+    }
+
+    #[test]
+    fn results_rank_players_and_pairs() {
+        let mut game = four_player(false, 1);
+        for (p, score) in game.players.iter_mut().zip([10, 40, 25, 40]) {
+            p.score = score;
+        }
+        let ranked: Vec<(String, u32)> = game.standings().into_iter().map(|s| (s.name, s.score)).collect();
+        assert_eq!(ranked[2], ("C".to_string(), 25));
+        assert_eq!(ranked[3], ("A".to_string(), 10));
+        game.end_game();
+        assert_eq!(game.winner_text, "MATCH DRAW!");
+
+        game.players[3].score = 50;
+        game.end_game();
+        assert_eq!(game.winner_text, "D Wins!");
+
+        game.pairs = true;
+        game.end_game();
+        let standings = game.standings();
+        assert_eq!(standings[0].name, "B & D");
+        assert_eq!(standings[0].score, 90);
+        assert_eq!(standings[1].score, 35);
+        assert_eq!(game.winner_text, "B & D Win!");
+    }
+
+    #[test]
+    fn campaign_plays_every_level_then_crowns_a_champion() {
+        let mut replay = Replay::new(crate::model::replay::ReplayMode::Multiplayer, 7, [0, 0]);
+        replay.p1_name = "Santa".to_string();
+        replay.p2_name = "Rival".to_string();
+        replay.minutes = 1;
+        replay.level = 3;
+        replay.series = SeriesKind::Campaign;
+        let mut game = MultiplayerGame::from_replay(&replay);
+        let mut feed = InputFeed::default();
+        assert_eq!(game.current_level, 1);
+
+        for level in 1..=5 {
+            assert_eq!(game.current_level, level);
+            game.players[0].score = 10;
+            game.players[1].score = if level == 2 { 30 } else { 0 };
+            game.end_game();
+            // Only once per round, however often the timer check fires
+            game.end_game();
+            assert_eq!(game.series.round, level);
+            if level < 5 {
+                assert!(!game.game_over);
+                assert_eq!(game.next_level_timer, INTERMISSION);
+                for _ in 0..INTERMISSION {
+                    game.update(&feed.next([0, 0]));
+                }
+                assert_eq!(game.players[0].score, 0);
+                assert_eq!(game.timer, 60);
+            }
+        }
+
+        assert!(game.game_over);
+        assert_eq!(game.series.wins, [4, 1]);
+        assert_eq!(game.series_standings()[0].name, "Santa");
+
+        // Restarting starts the whole campaign over
+        game.update(&feed.next([BTN_START, 0]));
+        assert!(!game.game_over);
+        assert_eq!(game.series.round, 0);
+        assert_eq!(game.current_level, 1);
+    }
+
+    #[test]
+    fn best_of_three_stops_early_and_skips_the_wait() {
+        let mut game = arena(2, 0, 5);
+        game.start_series(SeriesKind::BestOf3);
+        let mut feed = InputFeed::default();
+
+        game.players[1].score = 20;
+        game.end_game();
+        assert_eq!(game.winner_text, "RIVAL Wins!");
+        for _ in 0..61 {
+            game.update(&feed.next([0, 0]));
+        }
+        game.update(&feed.next([BTN_A, 0]));
+        assert_eq!(game.next_level_timer, 0);
+        assert_eq!(game.current_level, 2);
+
+        game.players[1].score = 20;
+        game.end_game();
+        assert!(game.game_over);
+        let champion = game.winner_line(&game.series_standings(), "SERIES DRAW!", " the Series!");
+        assert_eq!(champion, "RIVAL Wins the Series!");
+    }
+
+    #[test]
+    fn match_ends_when_timer_runs_out() {
+        let mut game = arena(1, 0, 5);
+        game.houses.clear();
+        game.timer = 1;
+        let mut feed = InputFeed::default();
+
+        for _ in 0..60 {
+            game.update(&feed.next([0, 0]));
+        }
+
+        assert!(game.game_over);
+        assert_eq!(game.winner_text, "MATCH DRAW!");
+    }
+
+    fn custom_arena() -> Arena {
+        let mut arena = Arena::new("MOAT".to_string());
+        arena.rules = ArenaRules::level(3);
+        arena.houses.push(ArenaHouse { x: 60.0, y: 200.0, team: 1, power: false });
+        arena.houses.push(ArenaHouse { x: 300.0, y: 60.0, team: 3, power: false });
+        arena.houses.push(ArenaHouse { x: 400.0, y: 150.0, team: 0, power: true });
+        arena.obstacles.push(ArenaObstacle { x: 200.0, y: 200.0, kind: 2 });
+        arena.water.push(Zone::new(100.0, -16.0, 30.0, 320.0));
+        arena.bridges.push(Zone::new(100.0, 130.0, 30.0, 24.0));
+        arena.cage = (480.0, 30.0);
+        arena
+    }
+
+    #[test]
+    fn plays_on_a_hand_made_arena() {
+        let mut game = MultiplayerGame::new("Santa".to_string(), "Rival".to_string(), 3, 1, 7);
+        game.load_arena(custom_arena());
+
+        assert_eq!(game.current_level, 3);
+        assert_eq!(game.houses.len(), 3);
+        assert_eq!(game.houses[0].team, 1);
+        // Nobody plays team 3 in a 1v1
+        assert_eq!(game.houses[1].team, 0);
+        assert!(game.houses[2].is_high_value);
+        assert_eq!(game.obstacles.len(), 1);
+        assert_eq!((game.obstacles[0].w, game.obstacles[0].h), (20.0, 30.0));
+        assert_eq!(game.dog_pos, (480.0, 30.0));
+
+        assert!(game.is_in_water(115.0, 60.0));
+        assert!(!game.is_in_water(115.0, 140.0));
+        assert!(!game.is_in_water(256.0, 60.0));
+    }
+
+    #[test]
+    fn built_in_rivers_are_unchanged() {
+        let game = MultiplayerGame::new("Santa".to_string(), "Rival".to_string(), 3, 5, 7);
+        assert!(game.is_in_water(256.0, 30.0));
+        assert!(game.is_in_water(30.0, 144.0));
+        assert!(!game.is_in_water(256.0, 64.0));
+        assert!(!game.is_in_water(114.0, 144.0));
+        assert!(!game.is_in_water(200.0, 100.0));
+
+        let game = MultiplayerGame::new("Santa".to_string(), "Rival".to_string(), 3, 1, 7);
+        assert!(!game.is_in_water(256.0, 30.0));
+    }
+
+    #[test]
+    fn arena_comes_back_from_a_replay_except_in_a_campaign() {
+        let mut replay = Replay::new(ReplayMode::Multiplayer, 7, [0, 0]);
+        replay.level = 1;
+        replay.minutes = 3;
+        replay.arena = Some(custom_arena());
+        let game = MultiplayerGame::from_replay(&replay);
+        assert_eq!(game.current_level, 3);
+        assert_eq!(game.houses.len(), 3);
+
+        replay.series = SeriesKind::Campaign;
+        let game = MultiplayerGame::from_replay(&replay);
+        assert!(game.arena.is_none());
+        assert_eq!(game.current_level, 1);
+        assert!(game.water.is_empty());
+    }
+
+    #[test]
+    fn arena_rules_mix_mechanics_freely() {
+        // Level 1's calm field, but with trails and a dog
+        let mut arena = custom_arena();
+        arena.rules = ArenaRules { trails: true, dog: true, ..ArenaRules::level(1) };
+        let mut game = MultiplayerGame::new("Santa".to_string(), "Rival".to_string(), 3, 1, 7);
+        game.load_arena(arena);
+        assert_eq!(game.current_level, 1);
+
+        let mut feed = InputFeed::default();
+        for _ in 0..10 {
+            game.update(&feed.next([BTN_RIGHT, 0]));
+        }
+        assert!(!game.players[0].shadow_trail.is_empty());
+        assert!(game.rules.dog && !game.rules.hazards);
+    }
+}
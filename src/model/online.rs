@@ -0,0 +1,307 @@
+use turbo::*;
+use crate::model::input::InputFeed;
+use crate::model::multiplayer::*;
+
+// Client -> match channel
+#[turbo::serialize]
+pub enum OnlineRequest {
+    Join { name: String },
+    // Buttons held this frame. The host sends one every frame and each one
+    // steps the match, so the host's frame rate is the match clock.
+    Input { bits: u16 },
+    // Host only, once both seats are filled
+    Start { minutes: u32, level: u32, seed: u32 },
+    Leave,
+}
+
+// Match channel -> clients
+#[turbo::serialize]
+pub enum OnlineEvent {
+    // Sent to a user when they take a seat (0 = host)
+    Seated(u8),
+    Full,
+    Lobby(LobbyInfo),
+    Snapshot(MatchSnapshot),
+}
+
+#[turbo::serialize]
+#[derive(PartialEq, Default)]
+pub struct LobbyInfo {
+    pub names: [Option<String>; 2],
+    pub in_match: bool,
+}
+
+// Everything a client needs to draw the match. Snow and particles are only
+// decoration and the cage never moves, so they are left out.
+#[turbo::serialize]
+#[derive(PartialEq)]
+pub struct MatchSnapshot {
+    pub players: Vec<MPlayer>,
+    pub houses: Vec<House>,
+    pub obstacles: Vec<Obstacle>,
+    pub powerups: Vec<PowerUp>,
+    pub decors: Vec<Decor>,
+    pub floating_texts: Vec<FloatingText>,
+    pub timer: u32,
+    pub game_over: bool,
+    pub winner_text: String,
+    pub frame_count: u32,
+    pub current_level: u32,
+    pub dog_pos: (f32, f32),
+    pub dog_target: Option<u8>,
+    pub dog_state: u8,
+    // Raised since the last snapshot
+    pub sounds: Vec<String>,
+    pub shake: usize,
+}
+
+impl MultiplayerGame {
+    // Takes the pending sounds and shake along with the state
+    pub fn snapshot(&mut self) -> MatchSnapshot {
+        MatchSnapshot {
+            players: self.players.clone(),
+            houses: self.houses.clone(),
+            obstacles: self.obstacles.clone(),
+            powerups: self.powerups.clone(),
+            decors: self.decors.clone(),
+            floating_texts: self.floating_texts.clone(),
+            timer: self.timer,
+            game_over: self.game_over,
+            winner_text: self.winner_text.clone(),
+            frame_count: self.frame_count,
+            current_level: self.current_level,
+            dog_pos: self.dog_pos,
+            dog_target: self.dog_target,
+            dog_state: self.dog_state,
+            sounds: std::mem::take(&mut self.sounds),
+            shake: std::mem::take(&mut self.shake),
+        }
+    }
+
+    pub fn apply_snapshot(&mut self, snap: MatchSnapshot) {
+        self.players = snap.players;
+        self.houses = snap.houses;
+        self.obstacles = snap.obstacles;
+        self.powerups = snap.powerups;
+        self.decors = snap.decors;
+        self.floating_texts = snap.floating_texts;
+        self.timer = snap.timer;
+        self.game_over = snap.game_over;
+        self.winner_text = snap.winner_text;
+        self.frame_count = snap.frame_count;
+        self.current_level = snap.current_level;
+        self.dog_pos = snap.dog_pos;
+        self.dog_target = snap.dog_target;
+        self.dog_state = snap.dog_state;
+        self.sounds.extend(snap.sounds);
+        self.shake = self.shake.max(snap.shake);
+    }
+}
+
+// The authoritative side of an online match, kept free of channel calls
+// so it can be stepped in tests like the local games.
+#[turbo::serialize]
+#[derive(Default)]
+pub struct MatchServer {
+    // Turbo OS user id in each seat
+    pub seats: [Option<String>; 2],
+    pub names: [String; 2],
+    pub bits: [u16; 2],
+    pub feed: InputFeed,
+    pub game: Option<MultiplayerGame>,
+}
+
+impl MatchServer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn seat_of(&self, user: &str) -> Option<usize> {
+        self.seats.iter().position(|s| s.as_deref() == Some(user))
+    }
+
+    // Seat the user now holds, or None if the lobby is full
+    pub fn join(&mut self, user: &str, name: String) -> Option<u8> {
+        let seat = match self.seat_of(user) {
+            Some(seat) => seat,
+            None => {
+                let seat = self.seats.iter().position(|s| s.is_none())?;
+                self.seats[seat] = Some(user.to_string());
+                seat
+            },
+        };
+        self.names[seat] = name;
+        Some(seat as u8)
+    }
+
+    // Frees the user's seat. A match in progress is abandoned. False if they weren't seated.
+    pub fn leave(&mut self, user: &str) -> bool {
+        let Some(seat) = self.seat_of(user) else { return false };
+        self.seats[seat] = None;
+        self.bits[seat] = 0;
+        self.game = None;
+        true
+    }
+
+    // True when the input came from the host, meaning the match should step
+    pub fn set_input(&mut self, user: &str, bits: u16) -> bool {
+        match self.seat_of(user) {
+            Some(seat) => {
+                self.bits[seat] = bits;
+                seat == 0
+            },
+            None => false,
+        }
+    }
+
+    pub fn start(&mut self, user: &str, minutes: u32, level: u32, seed: u32) -> bool {
+        if self.seat_of(user) != Some(0) || self.seats.iter().any(|s| s.is_none()) {
+            return false;
+        }
+        let [p1, p2] = self.names.clone();
+        self.game = Some(MultiplayerGame::new(p1, p2, minutes.clamp(1, 10), level.clamp(1, 5), seed));
+        self.feed = InputFeed::default();
+        true
+    }
+
+    // One frame of the match with the latest buttons from both seats
+    pub fn tick(&mut self) -> Option<MatchSnapshot> {
+        let game = self.game.as_mut()?;
+        let input = self.feed.next(self.bits);
+        game.update(&input);
+        Some(game.snapshot())
+    }
+
+    pub fn lobby(&self) -> LobbyInfo {
+        let name = |seat: usize| self.seats[seat].as_ref().map(|_| self.names[seat].clone());
+        LobbyInfo { names: [name(0), name(1)], in_match: self.game.is_some() }
+    }
+}
+
+// One channel per lobby code
+#[turbo::os::channel(program = "santa_rival", name = "match")]
+pub struct MatchChannel {
+    server: MatchServer,
+}
+
+impl ChannelHandler for MatchChannel {
+    type Recv = OnlineRequest;
+    type Send = OnlineEvent;
+
+    fn new() -> Self {
+        Self { server: MatchServer::new() }
+    }
+
+    fn on_disconnect(&mut self, user_id: &str) -> Result<(), std::io::Error> {
+        if self.server.leave(user_id) {
+            Self::broadcast(OnlineEvent::Lobby(self.server.lobby()))?;
+        }
+        Ok(())
+    }
+
+    fn on_data(&mut self, user_id: &str, data: Self::Recv) -> Result<(), std::io::Error> {
+        match data {
+            OnlineRequest::Join { name } => match self.server.join(user_id, name) {
+                Some(seat) => {
+                    Self::send(user_id, OnlineEvent::Seated(seat))?;
+                    Self::broadcast(OnlineEvent::Lobby(self.server.lobby()))
+                },
+                None => Self::send(user_id, OnlineEvent::Full),
+            },
+            OnlineRequest::Input { bits } => {
+                if self.server.set_input(user_id, bits)
+                    && let Some(snap) = self.server.tick()
+                {
+                    Self::broadcast(OnlineEvent::Snapshot(snap))?;
+                }
+                Ok(())
+            },
+            OnlineRequest::Start { minutes, level, seed } => {
+                if self.server.start(user_id, minutes, level, seed) {
+                    Self::broadcast(OnlineEvent::Lobby(self.server.lobby()))?;
+                }
+                Ok(())
+            },
+            OnlineRequest::Leave => self.on_disconnect(user_id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::input::{BTN_A, BTN_RIGHT};
+
+    fn full_lobby() -> MatchServer {
+        let mut server = MatchServer::new();
+        assert_eq!(server.join("host", "SANTA".to_string()), Some(0));
+        assert_eq!(server.join("guest", "RIVAL".to_string()), Some(1));
+        server
+    }
+
+    #[test]
+    fn seats_fill_in_order_and_rejoining_keeps_the_seat() {
+        let mut server = full_lobby();
+        assert_eq!(server.join("third", "X".to_string()), None);
+        assert_eq!(server.join("guest", "RIVAL 2".to_string()), Some(1));
+        assert_eq!(server.lobby().names[1].as_deref(), Some("RIVAL 2"));
+    }
+
+    #[test]
+    fn only_the_host_starts_and_steps_the_match() {
+        let mut server = full_lobby();
+        assert!(!server.start("guest", 3, 1, 42));
+        assert!(server.start("host", 3, 1, 42));
+        assert!(server.lobby().in_match);
+
+        assert!(!server.set_input("guest", BTN_RIGHT));
+        assert!(server.tick().is_some());
+        assert!(server.set_input("host", BTN_A));
+    }
+
+    #[test]
+    fn server_match_follows_the_local_simulation() {
+        let mut server = full_lobby();
+        server.start("host", 3, 1, 7);
+        let mut local = MultiplayerGame::new("SANTA".to_string(), "RIVAL".to_string(), 3, 1, 7);
+        let mut feed = InputFeed::default();
+
+        let mut snap = None;
+        for frame in 0..120u16 {
+            let bits = [if frame % 3 == 0 { BTN_RIGHT } else { 0 }, BTN_RIGHT];
+            server.set_input("guest", bits[1]);
+            server.set_input("host", bits[0]);
+            snap = server.tick();
+            local.update(&feed.next(bits));
+        }
+        let snap = snap.unwrap();
+        assert_eq!(snap.players, local.players);
+        assert_eq!(snap.frame_count, local.frame_count);
+    }
+
+    #[test]
+    fn snapshots_fit_a_channel_message() {
+        let mut server = full_lobby();
+        for level in 1..=5 {
+            server.start("host", 3, level, level);
+            for frame in 0..1200u32 {
+                let dir = 1 << (frame / 90 % 4) as u16;
+                server.set_input("guest", dir);
+                server.set_input("host", dir);
+                let snap = server.tick().unwrap();
+                // Turbo's client receive buffer is 4096 bytes
+                assert!(borsh::to_vec(&OnlineEvent::Snapshot(snap)).unwrap().len() < 4096);
+            }
+        }
+    }
+
+    #[test]
+    fn leaving_ends_the_match() {
+        let mut server = full_lobby();
+        server.start("host", 3, 1, 1);
+        assert!(server.leave("guest"));
+        assert!(server.game.is_none());
+        assert!(!server.leave("guest"));
+        assert_eq!(server.join("new", "NEW".to_string()), Some(1));
+    }
+}
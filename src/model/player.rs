@@ -1,5 +1,6 @@
 use turbo::*;
 use crate::model::world::World;
+use crate::model::input::Input;
 
 #[turbo::serialize]
 #[derive(PartialEq)]
@@ -11,7 +12,12 @@ pub struct Player {
     pub width: f32,
     pub height: f32,
     pub on_ground: bool,
-    pub score: u32,
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Player {
@@ -24,22 +30,23 @@ impl Player {
             width: 16.0,
             height: 24.0,
             on_ground: false,
-            score: 0,
         }
     }
 
-    pub fn update(&mut self, world: &mut World) {
+    pub fn update(&mut self, world: &World, input: &Input) {
+        let gp = input.pad(0);
+
         // Horizontal Movement
-        if gamepad::get(0).left.pressed() {
+        if gp.left.pressed() {
             self.velocity_x = -3.0;
-        } else if gamepad::get(0).right.pressed() {
+        } else if gp.right.pressed() {
             self.velocity_x = 3.0;
         } else {
             self.velocity_x = 0.0;
         }
 
         // Jump
-        if (gamepad::get(0).a.just_pressed() || gamepad::get(0).up.just_pressed()) && self.on_ground {
+        if (gp.a.just_pressed() || gp.up.just_pressed()) && self.on_ground {
             self.velocity_y = -7.0;
             self.on_ground = false;
         }
@@ -55,25 +62,6 @@ impl Player {
         self.y += self.velocity_y;
         self.on_ground = false; // Assume falling until collision proves otherwise
         self.resolve_collisions_y(world);
-
-        // Collect Gifts
-        for gift in world.gifts.iter_mut() {
-            if !gift.collected {
-                if self.check_aabb(self.x, self.y, self.width, self.height, gift.x, gift.y, 12.0, 12.0) {
-                    gift.collected = true;
-                    self.score += 100;
-                }
-            }
-        }
-        
-        // Fall off world
-        if self.y > 300.0 {
-            // Respawn
-            self.x = world.camera_x + 50.0;
-            self.y = 0.0;
-            self.velocity_y = 0.0;
-            self.score = self.score.saturating_sub(50);
-        }
     }
 
     fn resolve_collisions_x(&mut self, world: &World) {
@@ -103,6 +91,11 @@ impl Player {
         }
     }
 
+    // Player box against any other box
+    pub fn overlaps(&self, x: f32, y: f32, w: f32, h: f32) -> bool {
+        self.check_aabb(self.x, self.y, self.width, self.height, x, y, w, h)
+    }
+
     fn check_aabb(&self, x1: f32, y1: f32, w1: f32, h1: f32, x2: f32, y2: f32, w2: f32, h2: f32) -> bool {
         x1 < x2 + w2 &&
         x1 + w1 > x2 &&
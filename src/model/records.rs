@@ -0,0 +1,155 @@
+use turbo::*;
+
+pub const MAX_RECORDS: usize = 10;
+
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub enum RecordKind {
+    GiftPacking,
+    RaindeerRush,
+    BreakerEasy,
+    BreakerMedium,
+    BreakerHard,
+    BreakerVeryHard,
+    SilentSanta,
+    RooftopRun,
+}
+
+impl RecordKind {
+    pub const ALL: [RecordKind; 8] = [
+        RecordKind::GiftPacking,
+        RecordKind::RaindeerRush,
+        RecordKind::BreakerEasy,
+        RecordKind::BreakerMedium,
+        RecordKind::BreakerHard,
+        RecordKind::BreakerVeryHard,
+        RecordKind::SilentSanta,
+        RecordKind::RooftopRun,
+    ];
+
+    // Breaker keeps one table per difficulty (menu_selection 0-3)
+    pub fn breaker(difficulty: u8) -> Self {
+        match difficulty {
+            0 => RecordKind::BreakerEasy,
+            1 => RecordKind::BreakerMedium,
+            2 => RecordKind::BreakerHard,
+            _ => RecordKind::BreakerVeryHard,
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            RecordKind::GiftPacking => "GIFT PACKING",
+            RecordKind::RaindeerRush => "RAINDEER RUSH",
+            RecordKind::BreakerEasy => "BREAKER - EASY",
+            RecordKind::BreakerMedium => "BREAKER - MEDIUM",
+            RecordKind::BreakerHard => "BREAKER - HARD",
+            RecordKind::BreakerVeryHard => "BREAKER - VERY HARD",
+            RecordKind::SilentSanta => "SILENT SANTA",
+            RecordKind::RooftopRun => "ROOFTOP RUN",
+        }
+    }
+
+    // Silent Santa is ranked by completion time, everything else by score
+    pub fn lower_is_better(&self) -> bool {
+        *self == RecordKind::SilentSanta
+    }
+
+    pub fn format_value(&self, value: i32) -> String {
+        if *self == RecordKind::SilentSanta {
+            // Stored as hundredths of a second
+            format!("{}.{:02}s", value / 100, value % 100)
+        } else {
+            format!("{}", value)
+        }
+    }
+}
+
+#[turbo::serialize]
+pub struct RecordEntry {
+    pub name: String,
+    pub value: i32,
+}
+
+#[turbo::serialize]
+pub struct RecordTable {
+    pub kind: RecordKind,
+    pub entries: Vec<RecordEntry>,
+}
+
+#[turbo::serialize]
+pub struct RecordBook {
+    pub tables: Vec<RecordTable>,
+}
+
+impl Default for RecordBook {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RecordBook {
+    pub fn new() -> Self {
+        Self {
+            tables: RecordKind::ALL.iter().map(|kind| RecordTable { kind: *kind, entries: vec![] }).collect(),
+        }
+    }
+
+    pub fn table(&self, kind: RecordKind) -> Option<&RecordTable> {
+        self.tables.iter().find(|t| t.kind == kind)
+    }
+
+    // Inserts a result and returns its rank (0-based) if it made the top 10
+    pub fn submit(&mut self, kind: RecordKind, name: &str, value: i32) -> Option<usize> {
+        let idx = match self.tables.iter().position(|t| t.kind == kind) {
+            Some(i) => i,
+            None => {
+                self.tables.push(RecordTable { kind, entries: vec![] });
+                self.tables.len() - 1
+            }
+        };
+        let table = &mut self.tables[idx];
+
+        // Ties go below existing entries so older records keep their place
+        let lower = kind.lower_is_better();
+        let pos = table.entries.iter()
+            .position(|e| if lower { value < e.value } else { value > e.value })
+            .unwrap_or(table.entries.len());
+        if pos >= MAX_RECORDS {
+            return None;
+        }
+
+        table.entries.insert(pos, RecordEntry { name: name.to_string(), value });
+        table.entries.truncate(MAX_RECORDS);
+        Some(pos)
+    }
+
+    pub fn draw_table(&self, kind: RecordKind, x: i32, y: i32, highlight: Option<usize>) {
+        let w = 140;
+        let h = 130;
+        rect!(x=x, y=y, w=w, h=h, color=0x000000EE);
+        rect!(x=x, y=y, w=w, h=h, border_size=1, border_color=0xFFD700FF, color=0x00000000);
+        text!("TOP 10", x=x+8, y=y+6, font="medium", color=0xFFD700FF);
+
+        let entries: &[RecordEntry] = match self.table(kind) {
+            Some(t) => &t.entries,
+            None => &[],
+        };
+
+        for i in 0..MAX_RECORDS {
+            let row_y = y + 22 + i as i32 * 10;
+            let is_new = highlight == Some(i);
+            let col = if is_new { 0x00FF00FF } else { 0xFFFFFFFF };
+            if let Some(e) = entries.get(i) {
+                let rank = format!("{:>2}.", i + 1);
+                let value = kind.format_value(e.value);
+                text!(&rank, x=x+6, y=row_y, font="small", color=col);
+                text!(&e.name, x=x+24, y=row_y, font="small", color=col);
+                text!(&value, x=x+w - 8 - value.len() as i32 * 5, y=row_y, font="small", color=col);
+            } else {
+                let rank = format!("{:>2}. ---", i + 1);
+                text!(&rank, x=x+6, y=row_y, font="small", color=0x555555FF);
+            }
+        }
+    }
+}
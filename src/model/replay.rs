@@ -0,0 +1,426 @@
+use crate::model::rival_ai::RivalLevel;
+use crate::model::series::SeriesKind;
+use crate::model::arena::Arena;
+use crate::model::input::{all_pads, MAX_PLAYERS};
+
+// Oldest replays are dropped once the save holds this many
+pub const MAX_REPLAYS: usize = 5;
+
+const TEXT_HEADER: &str = "SANTA-REPLAY 1";
+
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub enum ReplayMode {
+    GiftPacking,
+    RaindeerRush,
+    Breaker,
+    SilentSanta,
+    Multiplayer,
+    RooftopRun,
+}
+
+impl ReplayMode {
+    pub const ALL: [ReplayMode; 6] = [
+        ReplayMode::GiftPacking,
+        ReplayMode::RaindeerRush,
+        ReplayMode::Breaker,
+        ReplayMode::SilentSanta,
+        ReplayMode::Multiplayer,
+        ReplayMode::RooftopRun,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            ReplayMode::GiftPacking => "GIFT PACKING",
+            ReplayMode::RaindeerRush => "RAINDEER RUSH",
+            ReplayMode::Breaker => "SANTA BREAKER",
+            ReplayMode::SilentSanta => "SILENT SANTA",
+            ReplayMode::Multiplayer => "MULTIPLAYER",
+            ReplayMode::RooftopRun => "ROOFTOP RUN",
+        }
+    }
+
+    // Name used in the text format
+    pub fn tag(&self) -> &'static str {
+        match self {
+            ReplayMode::GiftPacking => "gift_packing",
+            ReplayMode::RaindeerRush => "raindeer_rush",
+            ReplayMode::Breaker => "breaker",
+            ReplayMode::SilentSanta => "silent_santa",
+            ReplayMode::Multiplayer => "multiplayer",
+            ReplayMode::RooftopRun => "rooftop_run",
+        }
+    }
+
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|m| m.tag() == tag)
+    }
+}
+
+// `len` consecutive frames with the same buttons held on every pad
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub struct ReplayRun {
+    pub p1: u16,
+    pub p2: u16,
+    pub p3: u16,
+    pub p4: u16,
+    pub len: u16,
+}
+
+impl ReplayRun {
+    fn bits(&self) -> [u16; MAX_PLAYERS] {
+        [self.p1, self.p2, self.p3, self.p4]
+    }
+}
+
+// A whole session: everything needed to rebuild the game, plus its input
+#[turbo::serialize]
+#[derive(PartialEq)]
+pub struct Replay {
+    pub mode: ReplayMode,
+    pub seed: u32,
+    // Buttons already held when the session started
+    pub held: [u16; MAX_PLAYERS],
+    // Multiplayer setup (unused by single-player modes)
+    pub p1_name: String,
+    pub p2_name: String,
+    pub p3_name: String,
+    pub p4_name: String,
+    // 2-4, and whether four players split into 1+3 vs 2+4
+    pub players: u32,
+    pub pairs: bool,
+    pub minutes: u32,
+    pub level: u32,
+    // CPU playing player 2
+    pub rival: Option<RivalLevel>,
+    pub series: SeriesKind,
+    // Hand-made field played instead of the level's own
+    pub arena: Option<Arena>,
+    // Santa Breaker pack levels unlocked when the session started, so its
+    // level select plays back the same
+    pub breaker_unlocked: u32,
+    pub runs: Vec<ReplayRun>,
+}
+
+impl Replay {
+    pub fn new<const N: usize>(mode: ReplayMode, seed: u32, held: [u16; N]) -> Self {
+        Self {
+            mode,
+            seed,
+            held: all_pads(held),
+            p1_name: "".to_string(),
+            p2_name: "".to_string(),
+            p3_name: "".to_string(),
+            p4_name: "".to_string(),
+            players: 2,
+            pairs: false,
+            minutes: 0,
+            level: 0,
+            rival: None,
+            series: SeriesKind::Single,
+            arena: None,
+            breaker_unlocked: 1,
+            runs: vec![],
+        }
+    }
+
+    pub fn push<const N: usize>(&mut self, bits: [u16; N]) {
+        let bits = all_pads(bits);
+        if let Some(last) = self.runs.last_mut()
+            && last.bits() == bits && last.len < u16::MAX
+        {
+            last.len += 1;
+            return;
+        }
+        let [p1, p2, p3, p4] = bits;
+        self.runs.push(ReplayRun { p1, p2, p3, p4, len: 1 });
+    }
+
+    // Player names in pad order
+    pub fn names(&self) -> Vec<String> {
+        [&self.p1_name, &self.p2_name, &self.p3_name, &self.p4_name]
+            .into_iter()
+            .take(self.players.clamp(2, MAX_PLAYERS as u32) as usize)
+            .cloned()
+            .collect()
+    }
+
+    // Pads written out in text: two unless more players took part
+    fn text_pads(&self) -> usize {
+        if self.players > 2 { MAX_PLAYERS } else { 2 }
+    }
+
+    // Same setup (players, level, rival, series, arena...) with a new seed and
+    // no input yet, for restarting a session
+    pub fn restarted<const N: usize>(&self, seed: u32, held: [u16; N]) -> Self {
+        Self { seed, held: all_pads(held), runs: vec![], ..self.clone() }
+    }
+
+    pub fn frame_count(&self) -> u32 {
+        self.runs.iter().map(|r| r.len as u32).sum()
+    }
+
+    // Plain text export, one `key value` per line followed by the input runs
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        out.push_str(TEXT_HEADER);
+        out.push('\n');
+        out.push_str(&format!("mode {}\n", self.mode.tag()));
+        out.push_str(&format!("seed {}\n", self.seed));
+        let pads = self.text_pads();
+        out.push_str(&format!("held {}\n", hex_list(&self.held[..pads])));
+        out.push_str(&format!("p1 {}\n", self.p1_name));
+        out.push_str(&format!("p2 {}\n", self.p2_name));
+        if self.players > 2 {
+            out.push_str(&format!("players {}\n", self.players));
+            out.push_str(&format!("p3 {}\n", self.p3_name));
+        }
+        if self.players > 3 {
+            out.push_str(&format!("p4 {}\n", self.p4_name));
+        }
+        if self.pairs {
+            out.push_str("teams pairs\n");
+        }
+        out.push_str(&format!("minutes {}\n", self.minutes));
+        out.push_str(&format!("level {}\n", self.level));
+        if let Some(rival) = self.rival {
+            out.push_str(&format!("rival {}\n", rival.tag()));
+        }
+        if self.series != SeriesKind::Single {
+            out.push_str(&format!("series {}\n", self.series.tag()));
+        }
+        if let Some(arena) = &self.arena {
+            out.push_str(&format!("arena {}\n", arena.to_json()));
+        }
+        if self.mode == ReplayMode::Breaker {
+            out.push_str(&format!("unlocked {}\n", self.breaker_unlocked));
+        }
+        out.push_str("frames\n");
+        for run in &self.runs {
+            out.push_str(&format!("{} {}\n", hex_list(&run.bits()[..pads]), run.len));
+        }
+        out
+    }
+
+    pub fn from_text(text: &str) -> Option<Self> {
+        let mut lines = text.lines();
+        if lines.next()?.trim() != TEXT_HEADER {
+            return None;
+        }
+
+        let mut replay = Replay::new(ReplayMode::GiftPacking, 0, [0, 0]);
+        let mut in_frames = false;
+        for line in lines {
+            let line = line.trim_end();
+            if line.is_empty() {
+                continue;
+            }
+            if in_frames {
+                // Pads first, then the run length
+                let (pads, len) = line.rsplit_once(' ')?;
+                let [p1, p2, p3, p4] = parse_hex_list(pads)?;
+                let len = len.parse().ok()?;
+                replay.runs.push(ReplayRun { p1, p2, p3, p4, len });
+                continue;
+            }
+            if line == "frames" {
+                in_frames = true;
+                continue;
+            }
+
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            match key {
+                "mode" => replay.mode = ReplayMode::from_tag(value)?,
+                "seed" => replay.seed = value.parse().ok()?,
+                "held" => replay.held = parse_hex_list(value)?,
+                "p1" => replay.p1_name = value.to_string(),
+                "p2" => replay.p2_name = value.to_string(),
+                "p3" => replay.p3_name = value.to_string(),
+                "p4" => replay.p4_name = value.to_string(),
+                "players" => replay.players = value.parse().ok().filter(|n| (2..=MAX_PLAYERS as u32).contains(n))?,
+                "teams" if value == "pairs" => replay.pairs = true,
+                "minutes" => replay.minutes = value.parse().ok()?,
+                "level" => replay.level = value.parse().ok()?,
+                "rival" => replay.rival = Some(RivalLevel::from_tag(value)?),
+                "series" => replay.series = SeriesKind::from_tag(value)?,
+                "arena" => replay.arena = Some(Arena::from_json(value)?),
+                "unlocked" => replay.breaker_unlocked = value.parse().ok()?,
+                _ => return None,
+            }
+        }
+        Some(replay)
+    }
+}
+
+// Steps through a replay one frame at a time
+#[turbo::serialize]
+pub struct ReplayPlayer {
+    pub replay: Replay,
+    pub frame: u32,
+    run: usize,
+    pos: u16,
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: Replay) -> Self {
+        Self { replay, frame: 0, run: 0, pos: 0 }
+    }
+
+    // Next frame's buttons, or None once the recording is exhausted
+    pub fn next_frame(&mut self) -> Option<[u16; MAX_PLAYERS]> {
+        let run = *self.replay.runs.get(self.run)?;
+        self.pos += 1;
+        if self.pos >= run.len {
+            self.run += 1;
+            self.pos = 0;
+        }
+        self.frame += 1;
+        Some(run.bits())
+    }
+}
+
+fn hex_list(bits: &[u16]) -> String {
+    bits.iter().map(|b| format!("{:x}", b)).collect::<Vec<_>>().join(" ")
+}
+
+// Two or four hex pads; missing pads are idle
+fn parse_hex_list(text: &str) -> Option<[u16; MAX_PLAYERS]> {
+    let bits = text.split_whitespace()
+        .map(|b| u16::from_str_radix(b, 16).ok())
+        .collect::<Option<Vec<_>>>()?;
+    if bits.len() != 2 && bits.len() != MAX_PLAYERS {
+        return None;
+    }
+    let mut out = [0; MAX_PLAYERS];
+    out[..bits.len()].copy_from_slice(&bits);
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::arena::ArenaRules;
+
+    fn sample() -> Replay {
+        let mut replay = Replay::new(ReplayMode::Multiplayer, 1234, [0x10, 0]);
+        replay.p1_name = "SANTA CLAUS".to_string();
+        replay.p2_name = "RIVAL".to_string();
+        replay.minutes = 3;
+        replay.level = 4;
+        for frame in 0..100u16 {
+            replay.push([frame / 30, 0x100]);
+        }
+        replay
+    }
+
+    #[test]
+    fn push_run_length_encodes() {
+        let replay = sample();
+        assert_eq!(replay.runs.len(), 4);
+        assert_eq!(replay.runs[0], ReplayRun { p1: 0, p2: 0x100, p3: 0, p4: 0, len: 30 });
+        assert_eq!(replay.frame_count(), 100);
+    }
+
+    #[test]
+    fn text_round_trip() {
+        let replay = sample();
+        let parsed = Replay::from_text(&replay.to_text()).expect("parses");
+        assert_eq!(parsed, replay);
+    }
+
+    #[test]
+    fn breaker_replays_keep_their_unlocked_levels() {
+        let mut replay = Replay::new(ReplayMode::Breaker, 77, [0, 0]);
+        replay.breaker_unlocked = 9;
+        replay.push([0x10, 0]);
+        let text = replay.to_text();
+        assert!(text.contains("unlocked 9\n") && text.contains("level 0\n"));
+        assert_eq!(Replay::from_text(&text), Some(replay));
+        assert!(!sample().to_text().contains("unlocked"));
+    }
+
+    #[test]
+    fn restarting_keeps_the_setup_and_the_arena() {
+        let mut replay = sample();
+        replay.series = SeriesKind::BestOf3;
+        let mut arena = Arena::new("FORK".to_string());
+        arena.rules = ArenaRules::level(4);
+        replay.arena = Some(arena);
+
+        let again = replay.restarted(99, [0, 0x20]);
+        assert_eq!(again.seed, 99);
+        assert_eq!(again.held[1], 0x20);
+        assert!(again.runs.is_empty());
+        assert_eq!(again.arena, replay.arena);
+        assert_eq!((again.p1_name.as_str(), again.minutes, again.level), ("SANTA CLAUS", 3, 4));
+        assert_eq!(again.series, replay.series);
+    }
+
+    #[test]
+    fn text_round_trip_keeps_the_cpu_rival() {
+        let mut replay = sample();
+        replay.rival = Some(RivalLevel::Hard);
+        let text = replay.to_text();
+        assert!(text.contains("rival hard\n"));
+        assert_eq!(Replay::from_text(&text), Some(replay));
+    }
+
+    #[test]
+    fn text_round_trip_keeps_the_series() {
+        let mut replay = sample();
+        replay.series = SeriesKind::Campaign;
+        let text = replay.to_text();
+        assert!(text.contains("series campaign\n"));
+        assert_eq!(Replay::from_text(&text), Some(replay));
+    }
+
+    #[test]
+    fn text_round_trip_keeps_the_arena() {
+        let mut replay = sample();
+        let mut arena = Arena::new("ISLAND".to_string());
+        arena.rules = ArenaRules::level(3);
+        replay.arena = Some(arena);
+        let text = replay.to_text();
+        assert!(text.contains("arena {\"name\":\"ISLAND\""));
+        assert_eq!(Replay::from_text(&text), Some(replay));
+    }
+
+    #[test]
+    fn player_yields_every_frame_then_stops() {
+        let mut player = ReplayPlayer::new(sample());
+        let mut frames = vec![];
+        while let Some(bits) = player.next_frame() {
+            frames.push(bits);
+        }
+        assert_eq!(frames.len(), 100);
+        assert_eq!(frames[29], [0, 0x100, 0, 0]);
+        assert_eq!(frames[30], [1, 0x100, 0, 0]);
+    }
+
+    #[test]
+    fn text_keeps_two_pad_columns_for_two_players() {
+        let text = sample().to_text();
+        assert!(text.contains("held 10 0\n"));
+        assert!(text.contains("\n0 100 30\n"));
+        assert!(!text.contains("players"));
+    }
+
+    #[test]
+    fn text_round_trip_with_four_players_in_pairs() {
+        let mut replay = Replay::new(ReplayMode::Multiplayer, 99, [0, 0, 0x10, 0]);
+        replay.p1_name = "A".to_string();
+        replay.p2_name = "B".to_string();
+        replay.p3_name = "C".to_string();
+        replay.p4_name = "D".to_string();
+        replay.players = 4;
+        replay.pairs = true;
+        replay.push([1, 2, 4, 8]);
+        replay.push([1, 2, 4, 8]);
+        replay.push([0, 0, 0, 0x20]);
+        let text = replay.to_text();
+        assert!(text.contains("\n1 2 4 8 2\n"));
+        assert_eq!(replay.names(), ["A", "B", "C", "D"]);
+        assert_eq!(Replay::from_text(&text), Some(replay));
+    }
+}
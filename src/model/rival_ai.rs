@@ -0,0 +1,443 @@
+use crate::model::rng::Rng;
+use crate::model::input::*;
+use crate::model::multiplayer::MultiplayerGame;
+use crate::model::arena::Zone;
+
+// Player 2 when nobody is at the second pad
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub enum RivalLevel {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl RivalLevel {
+    pub const ALL: [RivalLevel; 3] = [RivalLevel::Easy, RivalLevel::Normal, RivalLevel::Hard];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RivalLevel::Easy => "EASY",
+            RivalLevel::Normal => "NORMAL",
+            RivalLevel::Hard => "HARD",
+        }
+    }
+
+    // Name used in the replay text format
+    pub fn tag(&self) -> &'static str {
+        match self {
+            RivalLevel::Easy => "easy",
+            RivalLevel::Normal => "normal",
+            RivalLevel::Hard => "hard",
+        }
+    }
+
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|l| l.tag() == tag)
+    }
+
+    // Frames between picking a new goal
+    fn think_every(&self) -> u32 {
+        match self {
+            RivalLevel::Easy => 40,
+            RivalLevel::Normal => 16,
+            RivalLevel::Hard => 6,
+        }
+    }
+
+    // How far past a hazard's reach it starts steering away
+    fn caution(&self) -> f32 {
+        match self {
+            RivalLevel::Easy => 12.0,
+            RivalLevel::Normal => 28.0,
+            RivalLevel::Hard => 40.0,
+        }
+    }
+}
+
+// Rival's index in MultiplayerGame::players (id 2, team 2)
+const ME: usize = 1;
+const TEAM: u8 = 2;
+const SPEED: f32 = 2.0;
+
+// Plays player 2 by pressing its d-pad each frame. It only reads the match
+// state, so a replay with the same seed, level and player 1 input plays out the same.
+#[turbo::serialize]
+#[derive(PartialEq)]
+pub struct RivalAi {
+    pub level: RivalLevel,
+    // Points to walk through, ending at the goal; bridge ends come first when it's across water
+    pub path: Vec<(f32, f32)>,
+    pub think_timer: u32,
+    // Easy only: frames left dawdling
+    pub idle_timer: u32,
+    prev: u16,
+    rng: Rng,
+}
+
+impl RivalAi {
+    pub fn new(level: RivalLevel, seed: u32) -> Self {
+        Self {
+            level,
+            path: vec![],
+            think_timer: 0,
+            idle_timer: 0,
+            prev: 0,
+            // Own generator, so the match's rng draws are the same with or without a CPU
+            rng: Rng::new(seed ^ 0x51A7_0B07),
+        }
+    }
+
+    // Player 2's pad this frame
+    pub fn next_pad(&mut self, game: &MultiplayerGame) -> Pad {
+        let bits = self.think(game);
+        let pad = Pad::from_bits(bits, self.prev);
+        self.prev = bits;
+        pad
+    }
+
+    fn think(&mut self, game: &MultiplayerGame) -> u16 {
+        let me = (game.players[ME].x, game.players[ME].y);
+
+        if self.think_timer > 0 { self.think_timer -= 1; }
+        if self.idle_timer > 0 {
+            self.idle_timer -= 1;
+            return 0;
+        }
+
+        // Never change plans halfway over a bridge
+        if (self.think_timer == 0 || self.path.is_empty()) && !Self::on_river(game, me) {
+            self.think_timer = self.level.think_every();
+            if self.level == RivalLevel::Easy && self.rng.u32().is_multiple_of(5) {
+                self.idle_timer = 20;
+            }
+            self.path = match self.choose_goal(game, me) {
+                Some(goal) => Self::route(game, me, goal),
+                None => vec![],
+            };
+        }
+
+        // Next point on the path
+        while let Some(&(x, y)) = self.path.first() {
+            if dist(me, (x, y)) > 4.0 || self.path.len() == 1 {
+                break;
+            }
+            self.path.remove(0);
+        }
+        let Some(&target) = self.path.first() else { return 0 };
+        if dist(me, target) < 2.0 {
+            self.path.clear();
+            return 0;
+        }
+
+        let (mut dx, mut dy) = unit(me, target);
+        // Hazards push it off the line, except on a bridge where a sidestep means water
+        if !Self::on_river(game, me) {
+            let (px, py) = self.avoidance(game, me, (dx, dy));
+            dx += px;
+            dy += py;
+        }
+        to_bits(dx, dy)
+    }
+
+    // Best house or power-up for the time it takes to get there
+    fn choose_goal(&mut self, game: &MultiplayerGame, me: (f32, f32)) -> Option<(f32, f32)> {
+        let rival = (game.players[0].x, game.players[0].y);
+        let mut best: Option<(f32, (f32, f32))> = None;
+        let mut consider = |value: f32, at: (f32, f32), rng: &mut Rng| {
+            if value <= 0.0 {
+                return;
+            }
+            let frames = path_len(me, &Self::route(game, me, at)) / SPEED;
+            let mut score = value / (frames + 30.0);
+            if self.level == RivalLevel::Easy {
+                score *= 0.5 + rng.f32();
+            }
+            if best.is_none_or(|(s, _)| score > s) {
+                best = Some((score, at));
+            }
+        };
+
+        for house in &game.houses {
+            if house.is_high_value || (game.rules.teams && house.team != TEAM) {
+                continue;
+            }
+            let eta = dist(me, (house.x, house.y)) / SPEED;
+            // Hard heads for houses that will be ready by the time it arrives
+            let ready = match self.level {
+                RivalLevel::Hard => (house.cooldown as f32) < eta,
+                _ => house.cooldown == 0,
+            };
+            if !ready {
+                continue;
+            }
+            let mut value = house.points as f32;
+            // and leaves the ones player 1 is about to take
+            if self.level == RivalLevel::Hard && dist(rival, (house.x, house.y)) + 40.0 < dist(me, (house.x, house.y)) {
+                value *= 0.3;
+            }
+            consider(value, (house.x, house.y), &mut self.rng);
+        }
+
+        for pu in &game.powerups {
+            if pu.collected {
+                continue;
+            }
+            let value = match (pu.kind, self.level) {
+                (0, _) => 50.0,
+                (1, RivalLevel::Easy) => 10.0,
+                (1, _) => 25.0,
+                // Risky gifts are a coin flip; Easy can't tell them from the real thing,
+                // and with the dog about a bad one sets it loose
+                (_, RivalLevel::Easy) => 50.0,
+                (_, _) if game.rules.dog => 0.0,
+                (_, _) => 5.0,
+            };
+            consider(value, (pu.x, pu.y), &mut self.rng);
+        }
+
+        best.map(|(_, at)| at)
+    }
+
+    // Sum of pushes away from bombs, snowmen and a chasing dog. Each push also
+    // leans sideways, so a hazard dead ahead is walked around rather than stopped at.
+    fn avoidance(&self, game: &MultiplayerGame, me: (f32, f32), heading: (f32, f32)) -> (f32, f32) {
+        let caution = self.level.caution();
+        let mut push = (0.0, 0.0);
+        let mut away = |from: (f32, f32), reach: f32, weight: f32| {
+            let d = dist(me, from);
+            if d < reach + caution && d > 0.01 {
+                let strength = weight * (reach + caution - d) / caution;
+                let (ax, ay) = ((me.0 - from.0) / d, (me.1 - from.1) / d);
+                // Sideways: further out the side it's already on, or left if dead ahead
+                let along = ax * heading.0 + ay * heading.1;
+                let (mut sx, mut sy) = (ax - along * heading.0, ay - along * heading.1);
+                let side = (sx * sx + sy * sy).sqrt();
+                if side < 0.05 {
+                    (sx, sy) = (heading.1, -heading.0);
+                } else {
+                    (sx, sy) = (sx / side, sy / side);
+                }
+                push.0 += (ax + sx) * strength;
+                push.1 += (ay + sy) * strength;
+            }
+        };
+
+        let radius = game.players[ME].radius;
+        for o in &game.obstacles {
+            match o.kind {
+                // Hidden bombs wait off-screen
+                0 if o.x > -500.0 => away((o.x + o.w / 2.0, o.y + o.h / 2.0), o.w / 2.0 + radius, 1.5),
+                2 => away((o.x, o.y), 15.0 + radius, 1.5),
+                _ => {},
+            }
+        }
+        if game.rules.dog && game.dog_state == 1 && self.level != RivalLevel::Easy {
+            let weight = if game.dog_target == Some(TEAM) { 4.0 } else { 1.0 };
+            away(game.dog_pos, 20.0, weight);
+        }
+        push
+    }
+
+    // Straight to `to`, or over the bridges when water is in the way
+    fn route(game: &MultiplayerGame, from: (f32, f32), to: (f32, f32)) -> Vec<(f32, f32)> {
+        let region = |p| region(&game.water, p);
+        let (rf, rt) = (region(from), region(to));
+        if rf == rt {
+            return vec![to];
+        }
+
+        let ends = |z: &Zone| {
+            let (p, q) = z.landings(&game.water);
+            [(p, q), (q, p)]
+        };
+        let mut best: Option<Vec<(f32, f32)>> = None;
+        let mut consider = |path: Vec<(f32, f32)>| {
+            if best.as_ref().is_none_or(|b| path_len(from, &path) < path_len(from, b)) {
+                best = Some(path);
+            }
+        };
+        for (a, b) in game.bridges.iter().flat_map(ends) {
+            if region(a) != rf {
+                continue;
+            }
+            if region(b) == rt {
+                consider(vec![a, b, to]);
+                continue;
+            }
+            // Crossing two rivers (level 5's corner-to-corner trips) takes two bridges
+            for (c, d) in game.bridges.iter().flat_map(ends) {
+                if region(c) == region(b) && region(d) == rt {
+                    consider(vec![a, b, c, d, to]);
+                }
+            }
+        }
+        best.unwrap_or(vec![to])
+    }
+
+    fn on_river(game: &MultiplayerGame, (x, y): (f32, f32)) -> bool {
+        game.on_river(x, y)
+    }
+}
+
+// Which bank of every river a point is on, one bit per river
+fn region(water: &[Zone], (x, y): (f32, f32)) -> u32 {
+    water.iter().enumerate().fold(0, |bits, (i, zone)| {
+        let (cx, cy) = zone.center();
+        let far_side = if zone.is_vertical() { x >= cx } else { y >= cy };
+        bits | ((far_side as u32) << i)
+    })
+}
+
+fn dist(a: (f32, f32), b: (f32, f32)) -> f32 {
+    ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+}
+
+fn unit(from: (f32, f32), to: (f32, f32)) -> (f32, f32) {
+    let d = dist(from, to).max(0.01);
+    ((to.0 - from.0) / d, (to.1 - from.1) / d)
+}
+
+fn path_len(from: (f32, f32), path: &[(f32, f32)]) -> f32 {
+    let mut at = from;
+    let mut len = 0.0;
+    for &p in path {
+        len += dist(at, p);
+        at = p;
+    }
+    len
+}
+
+// Eight-way d-pad closest to a direction
+fn to_bits(dx: f32, dy: f32) -> u16 {
+    let len = (dx * dx + dy * dy).sqrt();
+    if len < 0.1 {
+        return 0;
+    }
+    let mut bits = 0;
+    if dx > 0.38 * len { bits |= BTN_RIGHT; }
+    if dx < -0.38 * len { bits |= BTN_LEFT; }
+    if dy > 0.38 * len { bits |= BTN_DOWN; }
+    if dy < -0.38 * len { bits |= BTN_UP; }
+    bits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::multiplayer::{House, Obstacle};
+    use crate::model::input::InputFeed;
+    use crate::model::arena::{Arena, ArenaRules};
+
+    // CPU rival alone with one of its houses; player 1 parked out of the way
+    fn duel(level: u32, rival_at: (f32, f32), house_at: (f32, f32)) -> MultiplayerGame {
+        let mut game = MultiplayerGame::new("Santa".to_string(), "CPU".to_string(), 3, level, 11);
+        game.rival_ai = Some(RivalAi::new(RivalLevel::Normal, 11));
+        game.obstacles.clear();
+        game.powerups.clear();
+        game.houses = vec![House {
+            x: house_at.0, y: house_at.1, points: 25, cooldown: 0,
+            last_collected_by: None, last_collection_time: 0,
+            is_high_value: false, team: 2, gift_timer: 0,
+        }];
+        game.players[0].x = 20.0;
+        game.players[0].y = 20.0;
+        game.players[ME].x = rival_at.0;
+        game.players[ME].y = rival_at.1;
+        game
+    }
+
+    fn play(game: &mut MultiplayerGame, frames: u32) {
+        let mut feed = InputFeed::default();
+        for _ in 0..frames {
+            game.update(&feed.next([0, 0]));
+            if game.players[ME].score > 0 {
+                return;
+            }
+        }
+    }
+
+    #[test]
+    fn walks_to_its_house() {
+        let mut game = duel(1, (462.0, 238.0), (300.0, 80.0));
+        play(&mut game, 300);
+        assert_eq!(game.players[ME].score, 25);
+        assert_eq!(game.players[0].score, 0);
+    }
+
+    #[test]
+    fn crosses_the_river_by_a_bridge() {
+        let mut game = duel(3, (462.0, 238.0), (100.0, 60.0));
+        play(&mut game, 600);
+        assert_eq!(game.players[ME].score, 25);
+    }
+
+    #[test]
+    fn takes_two_bridges_across_the_level_5_corners() {
+        let game = duel(5, (462.0, 238.0), (60.0, 60.0));
+        let path = RivalAi::route(&game, (462.0, 238.0), (60.0, 60.0));
+        assert_eq!(path.len(), 5);
+
+        let mut game = game;
+        play(&mut game, 900);
+        assert_eq!(game.players[ME].score, 25);
+    }
+
+    #[test]
+    fn steers_around_a_bomb_on_its_line() {
+        let mut game = duel(2, (400.0, 140.0), (100.0, 140.0));
+        game.obstacles.push(Obstacle { x: 238.0, y: 128.0, w: 24.0, h: 24.0, respawn_timer: 900, kind: 0 });
+        game.shuffle_timer = 10_000;
+        play(&mut game, 400);
+        assert_eq!(game.players[ME].score, 25);
+        assert!(game.obstacles[0].x > 0.0, "bomb went off");
+    }
+
+    #[test]
+    fn scores_on_every_level_and_difficulty() {
+        for level in 1..=5 {
+            for difficulty in RivalLevel::ALL {
+                let mut game = MultiplayerGame::new("Santa".to_string(), "CPU".to_string(), 3, level, 3);
+                game.rival_ai = Some(RivalAi::new(difficulty, 3));
+                let mut feed = InputFeed::default();
+                for _ in 0..1800 {
+                    game.update(&feed.next([0, 0]));
+                }
+                assert!(game.players[ME].score > 0, "level {} {}", level, difficulty.name());
+            }
+        }
+    }
+
+    #[test]
+    fn ignores_the_other_teams_houses() {
+        let mut game = duel(3, (400.0, 140.0), (350.0, 140.0));
+        game.houses[0].team = 1;
+        play(&mut game, 200);
+        assert_eq!(game.players[ME].score, 0);
+        assert!((game.players[ME].x - 400.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn finds_the_bridge_on_a_hand_made_arena() {
+        let mut arena = Arena::new("FORD".to_string());
+        arena.rules = ArenaRules::level(3);
+        arena.water.push(Zone::new(-16.0, 180.0, 544.0, 30.0));
+        arena.bridges.push(Zone::new(400.0, 180.0, 30.0, 30.0));
+        let mut game = duel(1, (100.0, 100.0), (100.0, 260.0));
+        game.load_arena(arena);
+        game.obstacles.clear();
+        game.powerups.clear();
+        game.houses = vec![House {
+            x: 100.0, y: 260.0, points: 25, cooldown: 0,
+            last_collected_by: None, last_collection_time: 0,
+            is_high_value: false, team: 2, gift_timer: 0,
+        }];
+        game.players[ME].x = 100.0;
+        game.players[ME].y = 100.0;
+
+        let path = RivalAi::route(&game, (100.0, 100.0), (100.0, 260.0));
+        assert_eq!(path, vec![(415.0, 170.0), (415.0, 220.0), (100.0, 260.0)]);
+        play(&mut game, 900);
+        assert_eq!(game.players[ME].score, 25);
+    }
+}
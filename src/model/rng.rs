@@ -0,0 +1,27 @@
+// Small seedable generator (splitmix32) shared by every game mode.
+// Each game owns one, so a run can be replayed exactly from its seed.
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub struct Rng {
+    pub seed: u32,
+    state: u32,
+}
+
+impl Rng {
+    pub fn new(seed: u32) -> Self {
+        Self { seed, state: seed }
+    }
+
+    pub fn u32(&mut self) -> u32 {
+        self.state = self.state.wrapping_add(0x9E3779B9);
+        let mut z = self.state;
+        z = (z ^ (z >> 16)).wrapping_mul(0x85EBCA6B);
+        z = (z ^ (z >> 13)).wrapping_mul(0xC2B2AE35);
+        z ^ (z >> 16)
+    }
+
+    // 0.0 - 1.0
+    pub fn f32(&mut self) -> f32 {
+        (self.u32() >> 8) as f32 / (1u32 << 24) as f32
+    }
+}
@@ -0,0 +1,300 @@
+use turbo::*;
+use crate::model::rng::Rng;
+use crate::model::input::Input;
+use crate::model::player::Player;
+use crate::model::world::{Chimney, Hazard, World};
+use crate::model::game_mode::GameMode;
+use crate::model::replay::Replay;
+use crate::model::records::RecordKind;
+use crate::model::storage::SaveData;
+use crate::model::settings::Settings;
+
+// Frames of blinking after a respawn, during which icicles don't hurt
+const RESPAWN_GRACE: u32 = 90;
+
+#[turbo::serialize]
+pub struct RooftopGame {
+    pub player: Player,
+    pub world: World,
+    pub rng: Rng,
+    pub score: i32,
+    pub lives: i32,
+    pub level: u32,
+    // Gifts in the sack, waiting for a chimney
+    pub carried: u32,
+    pub delivered: u32,
+    pub invulnerable: u32,
+    // "Level clear" banner countdown
+    pub banner_timer: u32,
+    pub game_over: bool,
+    pub frame_count: u32,
+    // Screen shake requested this frame (pixels); GameState applies and clears it
+    pub shake: usize,
+
+    // Records
+    pub result_recorded: bool,
+    pub record_rank: Option<usize>,
+}
+
+impl RooftopGame {
+    pub fn new(seed: u32) -> Self {
+        let mut rng = Rng::new(seed);
+        let world = World::new(&mut rng);
+        let mut player = Player::new();
+        let (x, y) = world.respawn_point(0.0, player.height);
+        player.x = x + 40.0;
+        player.y = y;
+
+        Self {
+            player,
+            world,
+            rng,
+            score: 0,
+            lives: 3,
+            level: 1,
+            carried: 0,
+            delivered: 0,
+            invulnerable: 0,
+            banner_timer: 0,
+            game_over: false,
+            frame_count: 0,
+            shake: 0,
+            result_recorded: false,
+            record_rank: None,
+        }
+    }
+
+    pub fn update(&mut self, input: &Input) {
+        if self.game_over {
+            if input.pad(0).start.just_pressed() || input.pad(0).a.just_pressed() {
+                *self = Self::new(self.rng.u32());
+            }
+            return;
+        }
+
+        self.frame_count += 1;
+        if self.invulnerable > 0 { self.invulnerable -= 1; }
+        if self.banner_timer > 0 { self.banner_timer -= 1; }
+
+        self.player.update(&self.world, input);
+        // The camera never scrolls back, so the left edge of the screen is a wall
+        if self.player.x < self.world.camera_x {
+            self.player.x = self.world.camera_x;
+        }
+        self.world.update(self.player.x, &mut self.rng);
+
+        // Collect Gifts
+        for gift in self.world.gifts.iter_mut() {
+            if !gift.collected && self.player.overlaps(gift.x, gift.y, 12.0, 12.0) {
+                gift.collected = true;
+                self.carried += 1;
+                self.score += 100;
+            }
+        }
+
+        // Standing on a chimney drops the whole sack down it
+        if self.player.on_ground && self.carried > 0 {
+            let feet = self.player.y + self.player.height;
+            let (px, pw) = (self.player.x, self.player.width);
+            if let Some(chimney) = self.world.chimneys.iter_mut().find(|c| {
+                !c.delivered && (feet - c.y).abs() < 0.5 && px < c.x + Chimney::W && px + pw > c.x
+            }) {
+                chimney.delivered = true;
+                self.score += 200 * self.carried as i32;
+                self.delivered += self.carried;
+                self.carried = 0;
+            }
+        }
+
+        // Level End
+        if self.player.x >= World::finish_x(self.level) {
+            self.score += 500 * self.level as i32;
+            self.level += 1;
+            self.banner_timer = 120;
+        }
+
+        // Hazards
+        let spiked = self.invulnerable == 0 && self.world.hazards.iter().any(|h| {
+            self.player.overlaps(h.x + 2.0, h.y + 2.0, h.width - 4.0, Hazard::H - 2.0)
+        });
+        // Fall off world
+        if spiked || self.player.y > 300.0 {
+            self.lose_life();
+        }
+    }
+
+    fn lose_life(&mut self) {
+        self.lives -= 1;
+        self.score = (self.score - 50).max(0);
+        self.shake = 3;
+        if self.lives <= 0 {
+            self.game_over = true;
+            return;
+        }
+
+        // Respawn
+        let (x, y) = self.world.respawn_point(self.world.camera_x + 40.0, self.player.height);
+        self.player.x = x;
+        self.player.y = y;
+        self.player.velocity_x = 0.0;
+        self.player.velocity_y = 0.0;
+        self.invulnerable = RESPAWN_GRACE;
+    }
+
+    pub fn draw(&self) {
+        self.world.draw();
+
+        // Blink while invulnerable
+        if self.invulnerable == 0 || (self.invulnerable / 4).is_multiple_of(2) {
+            self.player.draw(self.world.camera_x);
+        }
+
+        // HUD
+        let score_txt = format!("SCORE: {}", self.score);
+        text!(&score_txt, x=10, y=10, font="medium", color=0x00FFFFFF);
+        let level_txt = format!("LEVEL {}", self.level);
+        text!(&level_txt, x=10, y=24, font="small", color=0xFFFFFFFF);
+        let sack_txt = format!("SACK: {}", self.carried);
+        text!(&sack_txt, x=220, y=10, font="medium", color=0xFF6666FF);
+        let lives_txt = format!("LIVES: {}", self.lives);
+        text!(&lives_txt, x=440, y=10, font="medium", color=0xFF0000FF);
+
+        if self.banner_timer > 0 {
+            let banner = format!("LEVEL {} CLEAR!", self.level - 1);
+            text!(&banner, x=(512 - banner.len() as i32 * 8) / 2, y=70, font="large", color=0xFFD700FF);
+        }
+
+        // Game Over
+        if self.game_over {
+            rect!(x=156, y=94, w=200, h=100, color=0x000000EE);
+            rect!(x=156, y=94, w=200, h=100, border_size=2, border_color=0xFF0000FF, color=0x00000000);
+            text!("OFF THE ROOF!", x=194, y=110, font="large", color=0xFF0000FF);
+            let final_score = format!("Score: {}  Level: {}", self.score, self.level);
+            text!(&final_score, x=170, y=134, font="medium", color=0xFFFFFFFF);
+            let gifts_txt = format!("Gifts Delivered: {}", self.delivered);
+            text!(&gifts_txt, x=170, y=146, font="small", color=0xFFFFFFFF);
+            if let Some(rank) = self.record_rank {
+                let record_txt = format!("NEW RECORD! #{}", rank + 1);
+                text!(&record_txt, x=170, y=158, font="small", color=0xFFD700FF);
+            }
+            text!("Press START to Retry", x=170, y=170, font="small", color=0xAAAAAAFF);
+            let seed_txt = format!("Seed: {}", self.rng.seed);
+            text!(&seed_txt, x=170, y=182, font="small", color=0x666666FF);
+        }
+    }
+}
+
+impl GameMode for RooftopGame {
+    fn from_replay(replay: &Replay) -> Self {
+        RooftopGame::new(replay.seed)
+    }
+
+    fn update(&mut self, input: &Input) {
+        RooftopGame::update(self, input)
+    }
+
+    fn draw(&self, _settings: &Settings) {
+        RooftopGame::draw(self)
+    }
+
+    fn is_running(&self) -> bool {
+        !self.game_over
+    }
+
+    fn take_shake(&mut self) -> usize {
+        std::mem::take(&mut self.shake)
+    }
+
+    fn submit_record(&mut self, save: &mut SaveData, name: &str) {
+        if self.game_over && !self.result_recorded {
+            self.result_recorded = true;
+            self.record_rank = save.submit_record(RecordKind::RooftopRun, name, self.score);
+        }
+    }
+
+    fn draw_records(&self, save: &SaveData) {
+        if self.game_over {
+            save.records.draw_table(RecordKind::RooftopRun, 364, 80, self.record_rank);
+        }
+    }
+
+    fn instructions(&self) -> &'static [&'static str] {
+        &[
+            "Rooftop Run:",
+            "- Left/Right: Run",
+            "- A / Up: Jump",
+            "- Land on a chimney to deliver gifts",
+            "Goal: Reach the flag, avoid icicles!",
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::input::{InputFeed, BTN_RIGHT};
+    use crate::model::world::{CHUNK_W, LEVEL_CHUNKS};
+
+    #[test]
+    fn world_generates_ahead_and_forgets_behind() {
+        let mut rng = Rng::new(7);
+        let mut world = World::new(&mut rng);
+        for step in 1..=40 {
+            world.update(step as f32 * 100.0, &mut rng);
+            let right = world.camera_x + 512.0;
+            assert!(world.platforms.iter().any(|p| p.roof && p.x + p.width >= right));
+            assert!(world.platforms.iter().all(|p| p.x + p.width > world.camera_x - 64.0));
+        }
+    }
+
+    #[test]
+    fn gaps_stay_jumpable() {
+        let mut rng = Rng::new(99);
+        let mut world = World::new(&mut rng);
+        world.update((LEVEL_CHUNKS * 3) as f32 * CHUNK_W, &mut rng);
+        let mut roofs: Vec<_> = world.platforms.iter().filter(|p| p.roof).collect();
+        roofs.sort_by(|a, b| a.x.total_cmp(&b.x));
+        for pair in roofs.windows(2) {
+            let gap = pair[1].x - (pair[0].x + pair[0].width);
+            assert!((0.0..=88.0).contains(&gap), "gap {}", gap);
+            assert!(pair[0].y - pair[1].y <= 40.0);
+        }
+    }
+
+    #[test]
+    fn falling_costs_a_life_and_respawns_on_a_roof() {
+        let mut game = RooftopGame::new(3);
+        let mut feed = InputFeed::default();
+        game.player.y = 320.0;
+        game.update(&feed.next([0, 0]));
+        assert_eq!(game.lives, 2);
+        assert!(game.invulnerable > 0);
+        let roof = game.world.platforms.iter().find(|p| p.roof && game.player.x >= p.x && game.player.x < p.x + p.width);
+        assert!(roof.is_some());
+    }
+
+    #[test]
+    fn chimney_takes_the_sack() {
+        let mut game = RooftopGame::new(5);
+        let mut feed = InputFeed::default();
+        let chimney = *game.world.chimneys.first().expect("a chimney in the first screens");
+        game.carried = 2;
+        game.player.x = chimney.x;
+        game.player.y = chimney.y - game.player.height;
+        game.player.on_ground = true;
+        game.update(&feed.next([0, 0]));
+        assert_eq!(game.carried, 0);
+        assert_eq!(game.delivered, 2);
+        assert!(game.world.chimneys.iter().any(|c| c.delivered));
+    }
+
+    #[test]
+    fn passing_the_flag_clears_the_level() {
+        let mut game = RooftopGame::new(11);
+        let mut feed = InputFeed::default();
+        game.player.x = World::finish_x(1) - 1.0;
+        game.update(&feed.next([BTN_RIGHT, 0]));
+        assert_eq!(game.level, 2);
+        assert!(game.score >= 500);
+    }
+}
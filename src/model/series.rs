@@ -0,0 +1,143 @@
+// How many rounds a multiplayer match runs for
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub enum SeriesKind {
+    Single,
+    BestOf3,
+    BestOf5,
+    // Levels 1 to 5 in order
+    Campaign,
+}
+
+impl SeriesKind {
+    pub const ALL: [SeriesKind; 4] = [SeriesKind::Single, SeriesKind::BestOf3, SeriesKind::BestOf5, SeriesKind::Campaign];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SeriesKind::Single => "SINGLE MATCH",
+            SeriesKind::BestOf3 => "BEST OF 3",
+            SeriesKind::BestOf5 => "BEST OF 5",
+            SeriesKind::Campaign => "CAMPAIGN 1-5",
+        }
+    }
+
+    // Name used in the replay text format
+    pub fn tag(&self) -> &'static str {
+        match self {
+            SeriesKind::Single => "single",
+            SeriesKind::BestOf3 => "best3",
+            SeriesKind::BestOf5 => "best5",
+            SeriesKind::Campaign => "campaign",
+        }
+    }
+
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|k| k.tag() == tag)
+    }
+
+    // Most rounds the series can take
+    pub fn rounds(&self) -> u32 {
+        match self {
+            SeriesKind::Single => 1,
+            SeriesKind::BestOf3 => 3,
+            SeriesKind::BestOf5 | SeriesKind::Campaign => 5,
+        }
+    }
+}
+
+// Running tally across the rounds of a series, one entry per player
+#[turbo::serialize]
+#[derive(PartialEq)]
+pub struct Series {
+    pub kind: SeriesKind,
+    // Level picked in setup; best-of series replay it every round
+    pub level: u32,
+    // Rounds finished so far
+    pub round: u32,
+    pub wins: Vec<u32>,
+    pub points: Vec<u32>,
+}
+
+impl Series {
+    pub fn new(kind: SeriesKind, level: u32, players: usize) -> Self {
+        Self { kind, level, round: 0, wins: vec![0; players], points: vec![0; players] }
+    }
+
+    pub fn is_single(&self) -> bool {
+        self.kind == SeriesKind::Single
+    }
+
+    // Level of the round about to be played
+    pub fn next_level(&self) -> u32 {
+        match self.kind {
+            SeriesKind::Campaign => (self.round + 1).min(5),
+            _ => self.level,
+        }
+    }
+
+    // A finished round: every player's score, and who won it (none on a draw)
+    pub fn record(&mut self, scores: &[u32], winners: &[usize]) {
+        self.round += 1;
+        for (total, score) in self.points.iter_mut().zip(scores) {
+            *total += score;
+        }
+        for &w in winners {
+            self.wins[w] += 1;
+        }
+    }
+
+    // True once no more rounds are needed: a best-of is over as soon as
+    // someone has a majority, a campaign always plays all five levels
+    pub fn is_decided(&self) -> bool {
+        let rounds = self.kind.rounds();
+        let majority = self.kind != SeriesKind::Campaign
+            && self.wins.iter().any(|&w| w > rounds / 2);
+        self.round >= rounds || majority
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn best_of_three_ends_on_a_majority() {
+        let mut series = Series::new(SeriesKind::BestOf3, 4, 2);
+        series.record(&[30, 10], &[0]);
+        assert!(!series.is_decided());
+        series.record(&[20, 25], &[0]);
+        assert!(series.is_decided());
+        assert_eq!(series.wins, [2, 0]);
+        assert_eq!(series.points, [50, 35]);
+        assert_eq!(series.next_level(), 4);
+    }
+
+    #[test]
+    fn drawn_rounds_still_count_towards_the_limit() {
+        let mut series = Series::new(SeriesKind::BestOf3, 1, 3);
+        for _ in 0..2 {
+            series.record(&[10, 10, 10], &[]);
+            assert!(!series.is_decided());
+        }
+        series.record(&[10, 20, 10], &[1]);
+        assert!(series.is_decided());
+    }
+
+    #[test]
+    fn campaign_walks_the_levels_and_plays_them_all() {
+        let mut series = Series::new(SeriesKind::Campaign, 3, 2);
+        for level in 1..=5 {
+            assert_eq!(series.next_level(), level);
+            assert!(!series.is_decided());
+            series.record(&[10, 0], &[0]);
+        }
+        assert!(series.is_decided());
+    }
+
+    #[test]
+    fn tags_round_trip() {
+        for kind in SeriesKind::ALL {
+            assert_eq!(SeriesKind::from_tag(kind.tag()), Some(kind));
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use turbo::*;
+use crate::model::input::*;
+
+pub const MAX_VOLUME: u8 = 10;
+
+// Buttons that can be rebound, in the order shown on the Options screen
+pub const REMAPPABLE: [u16; 6] = [BTN_A, BTN_B, BTN_X, BTN_Y, BTN_START, BTN_SELECT];
+
+// Label for a physical button, with its default P1 key
+pub fn button_name(bit: u16) -> &'static str {
+    match bit {
+        BTN_A => "A (Z)",
+        BTN_B => "B (X)",
+        BTN_X => "X (C)",
+        BTN_Y => "Y (V)",
+        BTN_START => "START (SPACE)",
+        BTN_SELECT => "SELECT (ENTER)",
+        _ => "?",
+    }
+}
+
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub struct Settings {
+    pub music_on: bool,
+    pub music_volume: u8, // 0 - MAX_VOLUME
+    pub sfx_volume: u8,   // 0 - MAX_VOLUME
+    pub screen_shake: bool,
+    pub reduced_particles: bool,
+    // bindings[i] is the physical button that acts as REMAPPABLE[i]
+    pub bindings: [u16; 6],
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Settings {
+    pub fn new() -> Self {
+        Self {
+            music_on: true,
+            music_volume: 7,
+            sfx_volume: 8,
+            screen_shake: true,
+            reduced_particles: false,
+            bindings: REMAPPABLE,
+        }
+    }
+
+    // Physical pad bits -> the logical buttons the games see
+    pub fn remap(&self, raw: u16) -> u16 {
+        let mut bits = raw & (BTN_UP | BTN_DOWN | BTN_LEFT | BTN_RIGHT);
+        for (i, logical) in REMAPPABLE.iter().enumerate() {
+            if raw & self.bindings[i] != 0 {
+                bits |= logical;
+            }
+        }
+        bits
+    }
+
+    // Gives `REMAPPABLE[index]` a new physical button, swapping with
+    // whichever action had it so every button stays reachable
+    pub fn rebind(&mut self, index: usize, physical: u16) {
+        if let Some(other) = self.bindings.iter().position(|b| *b == physical) {
+            self.bindings[other] = self.bindings[index];
+        }
+        self.bindings[index] = physical;
+    }
+
+    // Draw every particle, or every other one when reduced
+    pub fn particle_step(&self) -> usize {
+        if self.reduced_particles { 2 } else { 1 }
+    }
+
+    pub fn play_sfx(&self, name: &str) {
+        if self.sfx_volume == 0 {
+            return;
+        }
+        audio::set_volume(name, self.sfx_volume as f32 / MAX_VOLUME as f32);
+        audio::play(name);
+    }
+
+    // Called every frame: keeps the menu music in line with the settings
+    pub fn apply_music(&self, name: &str) {
+        if !self.music_on || self.music_volume == 0 {
+            if audio::is_playing(name) {
+                audio::stop(name);
+            }
+            return;
+        }
+        audio::set_volume(name, self.music_volume as f32 / MAX_VOLUME as f32);
+        if !audio::is_playing(name) {
+            audio::play(name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_mapping_is_identity() {
+        let settings = Settings::new();
+        let raw = BTN_LEFT | BTN_A | BTN_START;
+        assert_eq!(settings.remap(raw), raw);
+    }
+
+    #[test]
+    fn rebind_swaps_with_previous_owner() {
+        let mut settings = Settings::new();
+        // A now fires from the X button; X takes over A's old button
+        settings.rebind(0, BTN_X);
+        assert_eq!(settings.bindings[0], BTN_X);
+        assert_eq!(settings.bindings[2], BTN_A);
+        assert_eq!(settings.remap(BTN_X | BTN_UP), BTN_A | BTN_UP);
+        assert_eq!(settings.remap(BTN_A), BTN_X);
+    }
+
+    #[test]
+    fn directions_are_never_remapped() {
+        let mut settings = Settings::new();
+        settings.rebind(4, BTN_SELECT);
+        let dirs = BTN_UP | BTN_DOWN | BTN_LEFT | BTN_RIGHT;
+        assert_eq!(settings.remap(dirs), dirs);
+    }
+}
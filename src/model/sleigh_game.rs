@@ -1,4 +1,18 @@
 use turbo::*;
+use crate::model::rng::Rng;
+use crate::model::input::Input;
+use crate::model::game_mode::GameMode;
+use crate::model::replay::Replay;
+use crate::model::records::RecordKind;
+use crate::model::storage::SaveData;
+use crate::model::settings::Settings;
+use crate::model::sleigh_waves::{EnemyKind, SleighBoss, WaveDirector, ShotContact, enemy_fire, shot_contact, BOSS_NAME, BOSS_PHASES};
+use crate::model::sleigh_powerups::{PowerKind, PowerTimers, SleighPowerUp, DROP_LIFE};
+
+// Invulnerable frames after losing a life, and after the shield pops
+const HIT_IFRAMES: u32 = 90;
+const SHIELD_IFRAMES: u32 = 45;
+const GRAZE_SCORE: i32 = 5;
 
 #[turbo::serialize]
 pub struct SleighStar {
@@ -15,10 +29,44 @@ pub struct SleighBullet {
     pub w: f32,
     pub h: f32,
     pub speed: f32,
+    // Sideways drift (spread shots)
+    pub vy: f32,
+    // Candy-cane laser: goes through enemies, hitting each once
+    pub pierce: bool,
+    pub hit: Vec<u32>,
+}
+
+impl SleighBullet {
+    pub fn new(x: f32, y: f32, vy: f32, laser: bool) -> Self {
+        if laser {
+            Self { x, y: y - 1.0, w: 24.0, h: 4.0, speed: 10.0, vy, pierce: true, hit: vec![] }
+        } else {
+            Self { x, y, w: 8.0, h: 3.0, speed: 7.0, vy, pierce: false, hit: vec![] }
+        }
+    }
+}
+
+// Enemy fire, flying in a straight line
+#[turbo::serialize]
+pub struct SleighShot {
+    pub x: f32,
+    pub y: f32,
+    pub vx: f32,
+    pub vy: f32,
+    // Already scored a near miss
+    pub grazed: bool,
+}
+
+impl SleighShot {
+    pub fn at_angle(from: (f32, f32), angle: f32, speed: f32) -> Self {
+        Self { x: from.0, y: from.1, vx: angle.cos() * speed, vy: angle.sin() * speed, grazed: false }
+    }
 }
 
 #[turbo::serialize]
 pub struct SleighEnemy {
+    // Lets a piercing shot remember who it already hit
+    pub id: u32,
     pub x: f32,
     pub y: f32,
     pub w: f32,
@@ -27,6 +75,10 @@ pub struct SleighEnemy {
     pub hp: i32,
     pub max_hp: i32,
     pub color: u32,
+    pub kind: EnemyKind,
+    // Height it entered at (weavers swing around it)
+    pub base_y: f32,
+    pub age: u32,
 }
 
 #[turbo::serialize]
@@ -48,24 +100,40 @@ pub struct SleighGame {
     pub player_y: f32,
     pub player_w: f32,
     pub player_h: f32,
+    // Frames left of blinking invulnerability after a hit
+    pub invincible: u32,
+    // Near misses with enemy snowballs, each worth GRAZE_SCORE
+    pub grazes: u32,
     
-    // Difficulty
-    pub enemy_base_speed: f32,
-    pub spawn_rate: u32,
+    // Difficulty comes from the waves
+    pub director: WaveDirector,
+    pub boss: Option<SleighBoss>,
+    pub next_enemy_id: u32,
     pub fire_delay: u32,
     pub last_shot_frame: u32,
-    pub difficulty_timer: u32,
     
     pub frame_count: u32,
+    pub rng: Rng,
+    // Screen shake requested this frame (pixels); GameState applies and clears it
+    pub shake: usize,
 
     pub bullets: Vec<SleighBullet>,
     pub enemies: Vec<SleighEnemy>,
+    pub enemy_shots: Vec<SleighShot>,
+    pub powerups: Vec<SleighPowerUp>,
+    pub power: PowerTimers,
+    // Frames left of the gift bomb's white flash
+    pub bomb_flash: u32,
     pub particles: Vec<SleighParticle>,
     pub stars: Vec<SleighStar>,
+
+    // Records
+    pub result_recorded: bool,
+    pub record_rank: Option<usize>,
 }
 
 impl SleighGame {
-    pub fn new() -> Self {
+    pub fn new(seed: u32) -> Self {
         let mut game = Self {
             score: 0,
             lives: 3,
@@ -74,57 +142,57 @@ impl SleighGame {
             player_y: 130.0,
             player_w: 60.0, // Scaled down from 80
             player_h: 30.0, // Scaled down from 50
-            enemy_base_speed: 2.0,
-            spawn_rate: 100,
+            invincible: 0,
+            grazes: 0,
+            director: WaveDirector::new(),
+            boss: None,
+            next_enemy_id: 0,
             fire_delay: 20, // Frames (approx 200ms at 60fps is 12 frames, but lets settle on 20)
             last_shot_frame: 0,
-            difficulty_timer: 0,
             frame_count: 0,
+            rng: Rng::new(seed),
+            shake: 0,
             bullets: vec![],
             enemies: vec![],
+            enemy_shots: vec![],
+            powerups: vec![],
+            power: PowerTimers::new(),
+            bomb_flash: 0,
             particles: vec![],
             stars: vec![],
+            result_recorded: false,
+            record_rank: None,
         };
         
         // Init Stars
         for _ in 0..100 {
             game.stars.push(SleighStar {
-                x: (rand() % 512) as f32,
-                y: (rand() % 288) as f32,
-                size: (rand() % 2 + 1) as f32,
-                speed: (rand() % 4 + 1) as f32,
+                x: (game.rng.u32() % 512) as f32,
+                y: (game.rng.u32() % 288) as f32,
+                size: (game.rng.u32() % 2 + 1) as f32,
+                speed: (game.rng.u32() % 4 + 1) as f32,
             });
         }
         
         game
     }
 
-    pub fn update(&mut self) {
+    // Still playing (pausable); false on the game over screen
+    pub fn update(&mut self, input: &Input) {
         if self.game_over {
-            if gamepad::get(0).start.just_pressed() || gamepad::get(0).a.just_pressed() {
-                *self = Self::new();
+            if input.pad(0).start.just_pressed() || input.pad(0).a.just_pressed() {
+                *self = Self::new(self.rng.u32());
             }
             return;
         }
 
         self.frame_count += 1;
-        self.difficulty_timer += 1;
-
-        // Difficulty Increase (Every ~10s = 600 frames)
-        if self.difficulty_timer > 600 {
-            self.difficulty_timer = 0;
-            self.enemy_base_speed += 0.5;
-            if self.spawn_rate > 30 { self.spawn_rate -= 10; }
-            if self.fire_delay > 10 { self.fire_delay -= 2; }
-            
-            // Apply to existing? No, keeps simple.
-            // Notify? We can draw text.
-        }
+        self.invincible = self.invincible.saturating_sub(1);
 
         // Player Move
         // Speed 3.0
         let speed = 3.0;
-        let gp = gamepad::get(0);
+        let gp = input.pad(0);
         if gp.left.pressed() && self.player_x > 0.0 { self.player_x -= speed; }
         if gp.right.pressed() && self.player_x < 512.0 - self.player_w { self.player_x += speed; }
         if gp.up.pressed() && self.player_y > 0.0 { self.player_y -= speed; }
@@ -133,13 +201,13 @@ impl SleighGame {
         // Shoot (Auto-fire space held or single press)
         if gp.a.pressed() || gp.start.pressed() { // A or Start or Space mapped to A usually
             if self.frame_count - self.last_shot_frame > self.fire_delay {
-                self.bullets.push(SleighBullet {
-                    x: self.player_x + self.player_w,
-                    y: self.player_y + 15.0, // Center-ish
-                    w: 8.0,
-                    h: 3.0,
-                    speed: 7.0,
-                });
+                let x = self.player_x + self.player_w;
+                let y = self.player_y + 15.0; // Center-ish
+                let laser = self.power.is_active(PowerKind::Laser);
+                let fan: &[f32] = if self.power.is_active(PowerKind::Spread) { &[-1.2, 0.0, 1.2] } else { &[0.0] };
+                for vy in fan {
+                    self.bullets.push(SleighBullet::new(x, y, *vy, laser));
+                }
                 self.last_shot_frame = self.frame_count;
             }
         }
@@ -147,87 +215,62 @@ impl SleighGame {
         // Update Stars
         for star in &mut self.stars {
             star.x -= star.speed;
-            if star.x < 0.0 { star.x = 512.0; star.y = (rand() % 288) as f32; }
+            if star.x < 0.0 { star.x = 512.0; star.y = (self.rng.u32() % 288) as f32; }
         }
 
         // Update Bullets
         for b in &mut self.bullets {
             b.x += b.speed;
+            b.y += b.vy;
         }
-        self.bullets.retain(|b| b.x < 520.0);
+        self.bullets.retain(|b| b.x < 520.0 && b.y > -10.0 && b.y < 298.0);
+        self.power.tick();
+        self.bomb_flash = self.bomb_flash.saturating_sub(1);
 
         // Update Enemies
         // Spawn
-        if self.frame_count % self.spawn_rate == 0 {
-             let hp = (rand() % 5 + 1) as i32 + (self.score / 500);
-             let _eh = 30.0;
-             let ey = (rand() % (288 - 40)) as f32 + 10.0;
-             let base_s = self.enemy_base_speed;
-             
-             // Random HSL-ish Color logic
-             // Just simple predefined colors for now
-             let colors = [0xFF0000FF, 0x00FF00FF, 0x0000FFFF, 0xFFFF00FF, 0xFF00FFFF, 0x00FFFFFF];
-             let col = colors[(rand() % 6) as usize];
-
-             self.enemies.push(SleighEnemy {
-                 x: 520.0,
-                 y: ey,
-                 w: 40.0,
-                 h: 40.0,
-                 speed: base_s + ((rand() % 20) as f32 / 10.0),
-                 hp,
-                 max_hp: hp,
-                 color: col,
-             });
+        if let Some(kind) = self.director.tick(&mut self.rng) {
+            self.spawn_enemy(kind, None);
+        }
+        if self.director.take_boss() {
+            self.boss = Some(SleighBoss::new(self.director.cycle() + 1));
         }
 
+        let target = (self.player_x + self.player_w / 2.0, self.player_y + self.player_h / 2.0);
         for i in (0..self.enemies.len()).rev() {
             let mut remove = false;
             // Move
-            self.enemies[i].x -= self.enemies[i].speed;
+            self.move_enemy(i, target);
 
             // Collision Player
             // Clone to avoid borrow issues when modifying self.enemies later
             let e = self.enemies[i].clone(); 
             if aabb_intersect(self.player_x, self.player_y, self.player_w, self.player_h,
                               e.x, e.y, e.w, e.h) {
-                
-                self.lives -= 1;
-                self.create_particles(self.player_x + self.player_w/2.0, self.player_y + self.player_h/2.0, 0xFF0000FF, 10);
-                remove = true;
-                if self.lives <= 0 { self.game_over = true; }
+                // While blinking the sleigh passes straight through
+                remove = self.hurt_player();
             }
             
             // Collision Bullets
             if !remove {
                 // Use indices to avoid double borrow
-                let mut hit_idx = None;
-                for j in (0..self.bullets.len()).rev() {
-                    let b = &self.bullets[j];
-                     if aabb_intersect(b.x, b.y, b.w, b.h, e.x, e.y, e.w, e.h) {
-                          hit_idx = Some(j);
-                          self.enemies[i].hp -= 1;
-                          if self.enemies[i].hp <= 0 {
-                               self.score += self.enemies[i].max_hp * 10;
-                               // We need to create particles, but we can't call self.create_particles here easily due to borrow of 'e' (from enemies[i]) and 'self.enemies' index
-                               // Defer or inline logic? Inline is easier for now.
-                               // Inline particle creation logic to avoid method call borrow issues
-                               for _ in 0..8 {
-                                    let vx = ((rand() % 100) as f32 - 50.0) / 10.0;
-                                    let vy = ((rand() % 100) as f32 - 50.0) / 10.0;
-                                    self.particles.push(SleighParticle {
-                                        x: self.enemies[i].x + self.enemies[i].w/2.0, 
-                                        y: self.enemies[i].y + self.enemies[i].h/2.0, 
-                                        vx, vy, life: 1.0, color: self.enemies[i].color
-                                    });
-                               }
-                               remove = true;
-                          }
-                          break; 
-                     }
-                }
+                let hit_idx = self.bullets.iter().rposition(|b| {
+                    !b.hit.contains(&e.id) && aabb_intersect(b.x, b.y, b.w, b.h, e.x, e.y, e.w, e.h)
+                });
                 if let Some(idx) = hit_idx {
-                    self.bullets.remove(idx);
+                    if self.bullets[idx].pierce {
+                        self.bullets[idx].hit.push(e.id);
+                    } else {
+                        self.bullets.remove(idx);
+                    }
+                    self.enemies[i].hp -= 1;
+                    if self.enemies[i].hp <= 0 {
+                        self.destroy_enemy(&e);
+                        if let Some(kind) = PowerKind::roll_drop(&mut self.rng) {
+                            self.powerups.push(SleighPowerUp::new(e.x + e.w / 2.0, e.y + e.h / 2.0, kind));
+                        }
+                        remove = true;
+                    }
                 }
             }
 
@@ -236,6 +279,24 @@ impl SleighGame {
             }
         }
 
+        self.update_boss(target);
+        self.update_powerups();
+
+        // Enemy Shots
+        for s in &mut self.enemy_shots {
+            s.x += s.vx;
+            s.y += s.vy;
+        }
+        self.enemy_shots.retain(|s| s.x > -10.0 && s.x < 522.0 && s.y > -10.0 && s.y < 298.0);
+        self.update_shot_contacts();
+
+        // Wave Cleared
+        if self.director.is_spawning_done() && self.enemies.is_empty() && self.boss.is_none() && !self.game_over {
+            self.score += 100 * self.director.wave as i32;
+            if self.fire_delay > 10 { self.fire_delay -= 2; }
+            self.director.next_wave();
+        }
+
         // Update Particles
         for p in &mut self.particles {
             p.x += p.vx;
@@ -245,26 +306,207 @@ impl SleighGame {
         self.particles.retain(|p| p.life > 0.0);
     }
     
+    // Enters from the right at a random height, or at `y`
+    fn spawn_enemy(&mut self, kind: EnemyKind, y: Option<f32>) {
+        let hp = (self.rng.u32() % 5 + 1) as i32 + self.director.hp_bonus();
+        let id = self.next_enemy_id;
+        self.next_enemy_id += 1;
+        let ey = y.unwrap_or_else(|| (self.rng.u32() % (288 - 40)) as f32 + 10.0);
+        let speed = (self.director.speed() + (self.rng.u32() % 20) as f32 / 10.0) * kind.speed_scale();
+        self.enemies.push(SleighEnemy {
+            id,
+            x: 520.0,
+            y: ey,
+            w: 40.0,
+            h: 40.0,
+            speed,
+            hp,
+            max_hp: hp,
+            color: kind.color(),
+            kind,
+            base_y: ey,
+            age: 0,
+        });
+    }
+
+    // Snowballs that touch the sleigh hurt it; ones that only brush past score a graze
+    fn update_shot_contacts(&mut self) {
+        if self.invincible > 0 {
+            return;
+        }
+        let player = (self.player_x, self.player_y + 5.0, self.player_w, self.player_h - 5.0);
+        let mut hit = None;
+        let mut grazed = vec![];
+        for (i, s) in self.enemy_shots.iter_mut().enumerate() {
+            match shot_contact(player, s) {
+                ShotContact::Hit => {
+                    hit = Some(i);
+                    break;
+                },
+                ShotContact::Graze if !s.grazed => {
+                    s.grazed = true;
+                    grazed.push((s.x, s.y));
+                },
+                _ => {},
+            }
+        }
+        for (x, y) in grazed {
+            self.grazes += 1;
+            self.score += GRAZE_SCORE;
+            self.create_particles(x, y, 0xFFFFFFFF, 3);
+        }
+        if let Some(i) = hit {
+            self.enemy_shots.remove(i);
+            self.hurt_player();
+        }
+    }
+
+    fn move_enemy(&mut self, i: usize, target: (f32, f32)) {
+        let e = &mut self.enemies[i];
+        e.age += 1;
+        e.x -= e.speed;
+        match e.kind {
+            EnemyKind::Drifter => {},
+            EnemyKind::Weaver => {
+                e.y = (e.base_y + (e.age as f32 * 0.08).sin() * 40.0).clamp(0.0, 288.0 - e.h);
+            },
+            EnemyKind::Seeker => {
+                let dy = target.1 - (e.y + e.h / 2.0);
+                e.y += dy.clamp(-1.2, 1.2);
+            },
+            EnemyKind::Gunner | EnemyKind::Burster => {},
+            // Turns slowly as it goes
+            EnemyKind::Spinner => {
+                e.y = (e.base_y + (e.age as f32 * 0.03).sin() * 20.0).clamp(0.0, 288.0 - e.h);
+            },
+        }
+        let from = (e.x, e.y + e.h / 2.0);
+        let shots = enemy_fire(e.kind.pattern(), from, target, e.age);
+        self.enemy_shots.extend(shots);
+    }
+
+    fn update_boss(&mut self, target: (f32, f32)) {
+        let Some(boss) = &mut self.boss else { return };
+        let (shots, minion) = boss.update(target);
+        self.enemy_shots.extend(shots);
+        let (bx, by, bw, bh) = (boss.x, boss.y, boss.w, boss.h);
+        let center = boss.center();
+
+        // Gifts hit the envelope
+        let mut phase_changed = false;
+        self.bullets.retain(|b| {
+            if aabb_intersect(b.x, b.y, b.w, b.h, bx, by, bw, bh) {
+                phase_changed |= boss.hit(1);
+                return false;
+            }
+            true
+        });
+        let defeated = boss.hp <= 0;
+
+        if phase_changed {
+            self.shake = 3;
+            self.create_particles(center.0, center.1, 0x66BB6AFF, 16);
+        }
+        if minion {
+            self.spawn_enemy(EnemyKind::Drifter, Some(center.1 - 20.0));
+        }
+        if aabb_intersect(self.player_x, self.player_y, self.player_w, self.player_h, bx, by, bw, bh) && self.hurt_player() {
+            // Bounced back out of the way
+            self.player_x = (self.player_x - 80.0).max(0.0);
+        }
+        if defeated {
+            self.score += 1000 * (self.director.cycle() as i32 + 1);
+            self.shake = 6;
+            for _ in 0..4 {
+                let (ox, oy) = ((self.rng.u32() % 100) as f32 - 50.0, (self.rng.u32() % 40) as f32 - 20.0);
+                self.create_particles(center.0 + ox, center.1 + oy, 0x66BB6AFF, 12);
+            }
+            self.enemy_shots.clear();
+            // Always leaves a prize
+            let kind = PowerKind::pick(&mut self.rng);
+            self.powerups.push(SleighPowerUp::new(center.0, center.1, kind));
+            self.boss = None;
+        }
+    }
+
+    fn destroy_enemy(&mut self, e: &SleighEnemy) {
+        self.score += e.max_hp * 10;
+        self.create_particles(e.x + e.w/2.0, e.y + e.h/2.0, e.color, 8);
+    }
+
+    fn update_powerups(&mut self) {
+        self.powerups.retain_mut(|p| p.update());
+        let (px, py, pw, ph) = (self.player_x, self.player_y, self.player_w, self.player_h);
+        let size = SleighPowerUp::SIZE;
+        let Some(i) = self.powerups.iter().position(|p| aabb_intersect(px, py, pw, ph, p.x - size / 2.0, p.y - size / 2.0, size, size)) else { return };
+        let pickup = self.powerups.remove(i);
+        let kind = pickup.kind;
+        self.power.grant(kind);
+        match kind {
+            PowerKind::ExtraLife => self.lives += 1,
+            PowerKind::GiftBomb => self.detonate_bomb(),
+            PowerKind::Spread | PowerKind::Laser | PowerKind::Shield => {},
+        }
+        // A ring in its colour, with a few gold sparks
+        self.create_ring(pickup.x, pickup.y, kind.color(), 16);
+        self.create_particles(pickup.x, pickup.y, 0xFFD700FF, 6);
+    }
+
+    // Every enemy and snowball on screen is gone; the boss just takes a chunk
+    fn detonate_bomb(&mut self) {
+        for e in std::mem::take(&mut self.enemies) {
+            self.destroy_enemy(&e);
+        }
+        self.enemy_shots.clear();
+        if let Some(boss) = self.boss.as_mut().filter(|b| !b.is_entering()) {
+            boss.hit(10);
+        }
+        self.bomb_flash = 20;
+        self.shake = 6;
+    }
+
+    // False when the sleigh is still blinking from the last hit and nothing happens
+    fn hurt_player(&mut self) -> bool {
+        if self.invincible > 0 {
+            return false;
+        }
+        let center = (self.player_x + self.player_w/2.0, self.player_y + self.player_h/2.0);
+        if self.power.absorb_hit() {
+            self.invincible = SHIELD_IFRAMES;
+            self.shake = 2;
+            self.create_ring(center.0, center.1, PowerKind::Shield.color(), 20);
+            return true;
+        }
+        self.lives -= 1;
+        self.invincible = HIT_IFRAMES;
+        self.shake = 4;
+        self.create_particles(center.0, center.1, 0xFF0000FF, 10);
+        if self.lives <= 0 { self.game_over = true; }
+        true
+    }
+
     fn create_particles(&mut self, x: f32, y: f32, color: u32, count: usize) {
         for _ in 0..count {
-             let vx = ((rand() % 100) as f32 - 50.0) / 10.0;
-             let vy = ((rand() % 100) as f32 - 50.0) / 10.0;
+             let vx = ((self.rng.u32() % 100) as f32 - 50.0) / 10.0;
+             let vy = ((self.rng.u32() % 100) as f32 - 50.0) / 10.0;
              self.particles.push(SleighParticle {
                  x, y, vx, vy, life: 1.0, color
              });
         }
     }
 
-    pub fn draw(&self) {
-        // BG
-        rect!(w=512, h=288, color=0x000000FF);
-
-        // Stars
-        for s in &self.stars {
-            rect!(x=s.x as i32, y=s.y as i32, w=s.size as u32, h=s.size as u32, color=0xFFFFFFFF);
+    // Particles flying out evenly in every direction
+    fn create_ring(&mut self, x: f32, y: f32, color: u32, count: usize) {
+        for i in 0..count {
+            let angle = std::f32::consts::TAU * i as f32 / count as f32;
+            self.particles.push(SleighParticle {
+                x, y, vx: angle.cos() * 3.0, vy: angle.sin() * 3.0, life: 1.0, color
+            });
         }
+    }
 
-        // Player (Sleigh + Reindeer)
+    // Santa, the sleigh and Rudolph, at the player position
+    fn draw_player(&self) {
         let px = self.player_x as i32;
         let py = self.player_y as i32;
 
@@ -346,11 +588,51 @@ impl SleighGame {
         let sack_y = sy - 5;
         rect!(x=sack_x, y=sack_y, w=8, h=10, color=0x795548FF); // Brown Sack
         rect!(x=sack_x+1, y=sack_y-2, w=6, h=2, color=0xA1887FFF); // Top tied
+    }
+
+    pub fn draw(&self, settings: &Settings) {
+        // BG
+        rect!(w=512, h=288, color=0x000000FF);
+
+        // Stars
+        for s in &self.stars {
+            rect!(x=s.x as i32, y=s.y as i32, w=s.size as u32, h=s.size as u32, color=0xFFFFFFFF);
+        }
 
+        // Player (blinks while invulnerable)
+        let (px, py) = (self.player_x as i32, self.player_y as i32);
+        if self.invincible == 0 || self.frame_count % 8 < 4 {
+            self.draw_player();
+        }
+
+        // Shield bubble (blinks when it's about to run out)
+        let shield = self.power.left(PowerKind::Shield);
+        if shield > 0 && (shield > 120 || self.frame_count % 10 < 5) {
+            circ!(x=px-4, y=py-29, d=80, border_size=1, border_color=PowerKind::Shield.color(), color=0x00E5FF22);
+        }
 
         // Bullets
         for b in &self.bullets {
-            rect!(x=b.x as i32, y=b.y as i32, w=b.w as u32, h=b.h as u32, color=0xFFFF00FF);
+            if b.pierce {
+                // Candy cane: red with white stripes
+                rect!(x=b.x as i32, y=b.y as i32, w=b.w as u32, h=b.h as u32, color=0xFF1744FF);
+                for i in 0..(b.w as i32 / 6) {
+                    rect!(x=b.x as i32 + i * 6 + 1, y=b.y as i32, w=2, h=b.h as u32, color=0xFFFFFFFF);
+                }
+            } else {
+                rect!(x=b.x as i32, y=b.y as i32, w=b.w as u32, h=b.h as u32, color=0xFFFF00FF);
+            }
+        }
+
+        // Power-ups (blink before they vanish)
+        for p in &self.powerups {
+            if p.age + 120 > DROP_LIFE && self.frame_count % 10 < 5 {
+                continue;
+            }
+            let half = SleighPowerUp::SIZE as i32 / 2;
+            let (x, y) = (p.x as i32 - half, p.y as i32 - half);
+            rect!(x=x, y=y, w=SleighPowerUp::SIZE as u32, h=SleighPowerUp::SIZE as u32, border_size=1, border_color=0xFFFFFFFF, color=p.kind.color());
+            text!(p.kind.icon(), x=x+5, y=y+4, font="small", color=0x000000FF);
         }
 
         // Enemies
@@ -364,6 +646,39 @@ impl SleighGame {
             // Ribbon
             rect!(x=ex+ew as i32/2 - 2, y=ey, w=4, h=eh, color=0xFFFFFF66);
             rect!(x=ex, y=ey+eh as i32/2 - 2, w=ew, h=4, color=0xFFFFFF66);
+            match e.kind {
+                EnemyKind::Drifter => {},
+                // Bow on top
+                EnemyKind::Weaver => {
+                    rect!(x=ex+ew as i32/2 - 8, y=ey-5, w=6, h=5, color=0xFFD700FF);
+                    rect!(x=ex+ew as i32/2 + 2, y=ey-5, w=6, h=5, color=0xFFD700FF);
+                },
+                // Glowing eyes on a lump of coal
+                EnemyKind::Seeker => {
+                    rect!(x=ex+6, y=ey+6, w=6, h=4, color=0xFF5722FF);
+                    rect!(x=ex+ew as i32-12, y=ey+6, w=6, h=4, color=0xFF5722FF);
+                },
+                // Cannon facing the sleigh
+                EnemyKind::Gunner => {
+                    rect!(x=ex-8, y=ey+eh as i32/2 - 3, w=10, h=6, color=0x212121FF);
+                },
+                // Bauble cap and hook
+                EnemyKind::Burster => {
+                    rect!(x=ex+ew as i32/2 - 6, y=ey-4, w=12, h=4, color=0xBDBDBDFF);
+                    rect!(x=ex+ew as i32/2 - 1, y=ey-8, w=2, h=4, color=0xBDBDBDFF);
+                },
+                // Snowflake arms, turning with its spiral
+                EnemyKind::Spinner => {
+                    let (cx, cy) = (ex + ew as i32 / 2, ey + eh as i32 / 2);
+                    let turn = e.age as f32 * 0.04;
+                    for arm in 0..3 {
+                        let a = turn + arm as f32 * std::f32::consts::PI / 3.0;
+                        let (dx, dy) = ((a.cos() * 24.0) as i32, (a.sin() * 24.0) as i32);
+                        rect!(x=cx+dx-2, y=cy+dy-2, w=4, h=4, color=0xFFFFFFFF);
+                        rect!(x=cx-dx-2, y=cy-dy-2, w=4, h=4, color=0xFFFFFFFF);
+                    }
+                },
+            }
             
             // Health Number
             let val = e.hp;
@@ -374,8 +689,18 @@ impl SleighGame {
             text!(&txt, x=ex + offset, y=ey + 10, font="large", color=0x000000FF);
         }
 
+        if let Some(boss) = &self.boss {
+            draw_boss(boss, self.frame_count);
+        }
+
+        // Enemy Shots (snowballs with a red core)
+        for s in &self.enemy_shots {
+            circ!(x=s.x as i32 - 3, y=s.y as i32 - 3, d=6, color=0xFFFFFFFF);
+            rect!(x=s.x as i32 - 1, y=s.y as i32 - 1, w=2, h=2, color=0xFF1744FF);
+        }
+
         // Particles
-        for p in &self.particles {
+        for p in self.particles.iter().step_by(settings.particle_step()) {
              rect!(x=p.x as i32, y=p.y as i32, w=2, h=2, color=p.color);
         }
 
@@ -386,6 +711,44 @@ impl SleighGame {
         let lives_txt = format!("LIVES: {}", self.lives);
         text!(&lives_txt, x=440, y=10, font="medium", color=0xFF0000FF);
 
+        let graze_txt = format!("GRAZE: {}", self.grazes);
+        text!(&graze_txt, x=440, y=24, font="small", color=0xAAAAAAFF);
+
+        let wave_txt = format!("WAVE {}", self.director.wave);
+        text!(&wave_txt, x=256 - wave_txt.len() as i32 * 4 / 2, y=4, font="small", color=0xAAAAAAFF);
+
+        if let Some(boss) = &self.boss {
+            draw_boss_bar(boss);
+        }
+
+        // Power timers under the score, one draining bar each
+        for (i, kind) in self.power.running().into_iter().enumerate() {
+            let y = 26 + i as i32 * 10;
+            let fill = 60 * self.power.left(kind) / kind.duration();
+            text!(kind.name(), x=10, y=y, font="small", color=kind.color());
+            rect!(x=70, y=y+1, w=60, h=4, color=0x333333FF);
+            rect!(x=70, y=y+1, w=fill, h=4, color=kind.color());
+        }
+        if let Some(kind) = self.power.notice {
+            let txt = format!("{}!", kind.name());
+            text!(&txt, x=(self.player_x as i32 + 30) - txt.len() as i32 * 5 / 2, y=self.player_y as i32 - 24, font="medium", color=kind.color());
+        }
+
+        // Gift bomb flash
+        if self.bomb_flash > 0 {
+            let alpha = self.bomb_flash * 10;
+            rect!(w=512, h=288, color=0xFFFFFF00 | alpha);
+        }
+
+        // Wave Banner
+        if self.director.in_break() && !self.game_over {
+            let title = format!("WAVE {}", self.director.wave);
+            let name = self.director.name();
+            let col = if self.director.is_boss_wave() { 0xFF1744FF } else { 0xFFD700FF };
+            text!(&title, x=256 - title.len() as i32 * 8 / 2, y=110, font="large", color=col);
+            text!(name, x=256 - name.len() as i32 * 6 / 2, y=132, font="medium", color=0xFFFFFFFF);
+        }
+
         // Game Over
         if self.game_over {
             rect!(x=156, y=94, w=200, h=100, color=0x000000EE);
@@ -393,19 +756,117 @@ impl SleighGame {
             text!("MISSION FAILED", x=190, y=110, font="large", color=0xFF0000FF);
             let final_score = format!("Final Score: {}", self.score);
             text!(&final_score, x=180, y=140, font="medium", color=0xFFFFFFFF);
+            if let Some(rank) = self.record_rank {
+                let record_txt = format!("NEW RECORD! #{}", rank + 1);
+                text!(&record_txt, x=180, y=156, font="small", color=0xFFD700FF);
+            }
             text!("Press START to Retry", x=180, y=170, font="small", color=0xAAAAAAFF);
+            let seed_txt = format!("Seed: {}", self.rng.seed);
+            text!(&seed_txt, x=180, y=182, font="small", color=0x666666FF);
         }
     }
 }
 
+// The Grinch's blimp: a green envelope with a gondola, and the Grinch aboard
+fn draw_boss(boss: &SleighBoss, frame: u32) {
+    let (x, y) = (boss.x as i32, boss.y as i32);
+    let (w, h) = (boss.w as i32, boss.h as i32);
+    let skin = if boss.flash > 0 && frame % 4 < 2 { 0xFFFFFFFF } else { 0x558B2FFF };
+
+    // Envelope, rounded with stacked rects
+    rect!(x=x+10, y=y, w=(w-20) as u32, h=(h-20) as u32, color=skin);
+    rect!(x=x, y=y+8, w=w as u32, h=(h-36) as u32, color=skin);
+    rect!(x=x+10, y=y+6, w=(w-20) as u32, h=4, color=0x7CB342FF);
+    // Tail fins
+    rect!(x=x+w-6, y=y-6, w=10, h=12, color=0x33691EFF);
+    rect!(x=x+w-6, y=y+h-30, w=10, h=12, color=0x33691EFF);
+
+    // Gondola
+    let gy = y + h - 20;
+    rect!(x=x+30, y=gy-2, w=2, h=4, color=0x3E2723FF);
+    rect!(x=x+w-32, y=gy-2, w=2, h=4, color=0x3E2723FF);
+    rect!(x=x+24, y=gy+2, w=(w-48) as u32, h=16, color=0x6D4C41FF);
+
+    // Grinch
+    let gx = x + w / 2 - 8;
+    rect!(x=gx, y=gy-8, w=16, h=12, color=0x9CCC65FF);
+    rect!(x=gx+2, y=gy-4, w=4, h=2, color=0xFFEB3BFF);
+    rect!(x=gx+10, y=gy-4, w=4, h=2, color=0xFFEB3BFF);
+    rect!(x=gx+3, y=gy+1, w=10, h=1, color=0x1B5E20FF);
+    // Santa hat
+    rect!(x=gx, y=gy-12, w=16, h=4, color=0xD32F2FFF);
+    rect!(x=gx+14, y=gy-14, w=4, h=4, color=0xFFFFFFFF);
+}
+
+// Boss HP across the top, one segment per phase
+fn draw_boss_bar(boss: &SleighBoss) {
+    let (bar_x, bar_y, bar_w) = (136, 28, 240);
+    let seg_w = bar_w / BOSS_PHASES;
+    text!(BOSS_NAME, x=256 - BOSS_NAME.len() as i32 * 4 / 2, y=bar_y - 9, font="small", color=0xFF1744FF);
+    rect!(x=bar_x-1, y=bar_y-1, w=(bar_w+2) as u32, h=8, color=0xFFFFFFFF);
+    rect!(x=bar_x, y=bar_y, w=bar_w as u32, h=6, color=0x330000FF);
+    let colors = [0x66BB6AFF, 0xFFA726FF, 0xEF5350FF];
+    let (left, share) = boss.phase_hp();
+    for phase in 0..BOSS_PHASES {
+        // Segments drain right to left; the ones before the current phase are empty
+        let fill = match phase.cmp(&(BOSS_PHASES - 1 - boss.phase)) {
+            std::cmp::Ordering::Less => seg_w,
+            std::cmp::Ordering::Equal => seg_w * left / share.max(1),
+            std::cmp::Ordering::Greater => 0,
+        };
+        rect!(x=bar_x + phase * seg_w, y=bar_y, w=fill as u32, h=6, color=colors[boss.phase as usize]);
+    }
+    for i in 1..BOSS_PHASES {
+        rect!(x=bar_x + i * seg_w, y=bar_y - 1, w=1, h=8, color=0xFFFFFFFF);
+    }
+}
+
 fn aabb_intersect(x1: f32, y1: f32, w1: f32, h1: f32, x2: f32, y2: f32, w2: f32, h2: f32) -> bool {
     x1 < x2 + w2 && x1 + w1 > x2 && y1 < y2 + h2 && y1 + h1 > y2
 }
 
-fn rand() -> u32 {
-    unsafe {
-        static mut SEED: u32 = 98765;
-        SEED = SEED.wrapping_mul(1103515245).wrapping_add(12345);
-        SEED
+impl GameMode for SleighGame {
+    fn from_replay(replay: &Replay) -> Self {
+        SleighGame::new(replay.seed)
+    }
+
+    fn update(&mut self, input: &Input) {
+        SleighGame::update(self, input)
+    }
+
+    fn draw(&self, settings: &Settings) {
+        SleighGame::draw(self, settings)
+    }
+
+    fn is_running(&self) -> bool {
+        !self.game_over
+    }
+
+    fn take_shake(&mut self) -> usize {
+        std::mem::take(&mut self.shake)
+    }
+
+    fn submit_record(&mut self, save: &mut SaveData, name: &str) {
+        if self.game_over && !self.result_recorded {
+            self.result_recorded = true;
+            self.record_rank = save.submit_record(RecordKind::RaindeerRush, name, self.score);
+        }
+    }
+
+    fn draw_records(&self, save: &SaveData) {
+        if self.game_over {
+            save.records.draw_table(RecordKind::RaindeerRush, 364, 80, self.record_rank);
+        }
+    }
+
+    fn instructions(&self) -> &'static [&'static str] {
+        &[
+            "Raindeer Rush:",
+            "- Arrows: Move Sleigh, A: Shoot Gifts",
+            "- Start / Esc: Pause Menu",
+            "Survive the waves. Every 5th: a boss!",
+            "Brush past snowballs to score grazes.",
+            "Drops: Spread, Laser, Shield, Life, Bomb",
+        ]
     }
 }
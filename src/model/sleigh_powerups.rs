@@ -0,0 +1,258 @@
+use crate::model::rng::Rng;
+
+// Chance (out of 100) that a destroyed enemy drops something
+pub const DROP_CHANCE: u32 = 12;
+// Frames a dropped power-up drifts around before it's gone
+pub const DROP_LIFE: u32 = 600;
+// Frames the name of the last pickup stays in the HUD
+pub const NOTICE_TIME: u32 = 90;
+
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub enum PowerKind {
+    // Three gifts in a fan
+    Spread,
+    // Candy-cane shots that go through enemies
+    Laser,
+    // Soaks up the next hit
+    Shield,
+    ExtraLife,
+    // Clears the screen
+    GiftBomb,
+}
+
+impl PowerKind {
+    pub const ALL: [PowerKind; 5] = [
+        PowerKind::Spread, PowerKind::Laser, PowerKind::Shield, PowerKind::ExtraLife, PowerKind::GiftBomb,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PowerKind::Spread => "SPREAD",
+            PowerKind::Laser => "CANDY LASER",
+            PowerKind::Shield => "SHIELD",
+            PowerKind::ExtraLife => "EXTRA LIFE",
+            PowerKind::GiftBomb => "GIFT BOMB",
+        }
+    }
+
+    // Letter on the pickup
+    pub fn icon(&self) -> &'static str {
+        match self {
+            PowerKind::Spread => "S",
+            PowerKind::Laser => "L",
+            PowerKind::Shield => "O",
+            PowerKind::ExtraLife => "+",
+            PowerKind::GiftBomb => "B",
+        }
+    }
+
+    pub fn color(&self) -> u32 {
+        match self {
+            PowerKind::Spread => 0xFFD700FF,
+            PowerKind::Laser => 0xFF1744FF,
+            PowerKind::Shield => 0x00E5FFFF,
+            PowerKind::ExtraLife => 0x76FF03FF,
+            PowerKind::GiftBomb => 0xFF9100FF,
+        }
+    }
+
+    // Frames it lasts; 0 for the ones that happen on pickup
+    pub fn duration(&self) -> u32 {
+        match self {
+            PowerKind::Spread => 600,
+            PowerKind::Laser => 480,
+            PowerKind::Shield => 900,
+            PowerKind::ExtraLife | PowerKind::GiftBomb => 0,
+        }
+    }
+
+    // Relative drop weights; lives are the rarest
+    fn weight(&self) -> u32 {
+        match self {
+            PowerKind::Spread => 30,
+            PowerKind::Laser => 25,
+            PowerKind::Shield => 20,
+            PowerKind::GiftBomb => 15,
+            PowerKind::ExtraLife => 10,
+        }
+    }
+
+    // Any kind, by weight
+    pub fn pick(rng: &mut Rng) -> PowerKind {
+        let total: u32 = Self::ALL.iter().map(|k| k.weight()).sum();
+        let mut roll = rng.u32() % total;
+        for kind in Self::ALL {
+            if roll < kind.weight() {
+                return kind;
+            }
+            roll -= kind.weight();
+        }
+        PowerKind::Spread
+    }
+
+    // What a destroyed enemy leaves behind, if anything
+    pub fn roll_drop(rng: &mut Rng) -> Option<PowerKind> {
+        if rng.u32() % 100 < DROP_CHANCE { Some(Self::pick(rng)) } else { None }
+    }
+}
+
+// A pickup drifting left across the sky
+#[turbo::serialize]
+pub struct SleighPowerUp {
+    pub x: f32,
+    pub y: f32,
+    pub base_y: f32,
+    pub kind: PowerKind,
+    pub age: u32,
+}
+
+impl SleighPowerUp {
+    pub const SIZE: f32 = 14.0;
+
+    pub fn new(x: f32, y: f32, kind: PowerKind) -> Self {
+        let y = y.clamp(20.0, 288.0 - 20.0);
+        Self { x, y, base_y: y, kind, age: 0 }
+    }
+
+    // False once it has drifted off or timed out
+    pub fn update(&mut self) -> bool {
+        self.age += 1;
+        self.x -= 0.8;
+        self.y = self.base_y + (self.age as f32 * 0.1).sin() * 6.0;
+        self.x > -Self::SIZE && self.age < DROP_LIFE
+    }
+}
+
+// What the sleigh has picked up and how long each timed power has left
+#[turbo::serialize]
+#[derive(PartialEq)]
+pub struct PowerTimers {
+    pub spread: u32,
+    pub laser: u32,
+    pub shield: u32,
+    // Last pickup, named in the HUD for a moment
+    pub notice: Option<PowerKind>,
+    pub notice_timer: u32,
+}
+
+impl Default for PowerTimers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PowerTimers {
+    pub fn new() -> Self {
+        Self { spread: 0, laser: 0, shield: 0, notice: None, notice_timer: 0 }
+    }
+
+    fn timer(&mut self, kind: PowerKind) -> Option<&mut u32> {
+        match kind {
+            PowerKind::Spread => Some(&mut self.spread),
+            PowerKind::Laser => Some(&mut self.laser),
+            PowerKind::Shield => Some(&mut self.shield),
+            PowerKind::ExtraLife | PowerKind::GiftBomb => None,
+        }
+    }
+
+    // Starts (or restarts) a timed power; instant ones are only announced
+    pub fn grant(&mut self, kind: PowerKind) {
+        if let Some(timer) = self.timer(kind) {
+            *timer = kind.duration();
+        }
+        self.notice = Some(kind);
+        self.notice_timer = NOTICE_TIME;
+    }
+
+    pub fn is_active(&self, kind: PowerKind) -> bool {
+        self.left(kind) > 0
+    }
+
+    pub fn left(&self, kind: PowerKind) -> u32 {
+        match kind {
+            PowerKind::Spread => self.spread,
+            PowerKind::Laser => self.laser,
+            PowerKind::Shield => self.shield,
+            PowerKind::ExtraLife | PowerKind::GiftBomb => 0,
+        }
+    }
+
+    // Timed powers still running, for the HUD
+    pub fn running(&self) -> Vec<PowerKind> {
+        PowerKind::ALL.into_iter().filter(|k| self.is_active(*k)).collect()
+    }
+
+    // True (and the shield is gone) if the shield took the hit
+    pub fn absorb_hit(&mut self) -> bool {
+        let shielded = self.shield > 0;
+        self.shield = 0;
+        shielded
+    }
+
+    pub fn tick(&mut self) {
+        self.spread = self.spread.saturating_sub(1);
+        self.laser = self.laser.saturating_sub(1);
+        self.shield = self.shield.saturating_sub(1);
+        self.notice_timer = self.notice_timer.saturating_sub(1);
+        if self.notice_timer == 0 {
+            self.notice = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_are_occasional_and_every_kind_turns_up() {
+        let mut rng = Rng::new(11);
+        let drops: Vec<PowerKind> = (0..5000).filter_map(|_| PowerKind::roll_drop(&mut rng)).collect();
+        assert!(drops.len() > 400 && drops.len() < 800, "{}", drops.len());
+        for kind in PowerKind::ALL {
+            assert!(drops.contains(&kind), "{}", kind.name());
+        }
+        let lives = drops.iter().filter(|k| **k == PowerKind::ExtraLife).count();
+        let spreads = drops.iter().filter(|k| **k == PowerKind::Spread).count();
+        assert!(lives < spreads);
+    }
+
+    #[test]
+    fn timed_powers_run_out_and_instant_ones_only_announce() {
+        let mut power = PowerTimers::new();
+        power.grant(PowerKind::Spread);
+        power.grant(PowerKind::GiftBomb);
+        assert_eq!(power.running(), vec![PowerKind::Spread]);
+        assert_eq!(power.notice, Some(PowerKind::GiftBomb));
+
+        for _ in 0..NOTICE_TIME {
+            power.tick();
+        }
+        assert_eq!(power.notice, None);
+        for _ in NOTICE_TIME..PowerKind::Spread.duration() {
+            power.tick();
+        }
+        assert!(!power.is_active(PowerKind::Spread));
+    }
+
+    #[test]
+    fn shield_soaks_exactly_one_hit() {
+        let mut power = PowerTimers::new();
+        assert!(!power.absorb_hit());
+        power.grant(PowerKind::Shield);
+        assert!(power.absorb_hit());
+        assert!(!power.absorb_hit());
+    }
+
+    #[test]
+    fn pickups_drift_off_or_expire() {
+        let mut drop = SleighPowerUp::new(500.0, 300.0, PowerKind::Laser);
+        assert!(drop.y <= 268.0);
+        let mut frames = 0;
+        while drop.update() {
+            frames += 1;
+        }
+        assert!(frames < DROP_LIFE);
+    }
+}
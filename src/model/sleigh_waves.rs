@@ -0,0 +1,449 @@
+use crate::model::rng::Rng;
+use crate::model::sleigh_game::SleighShot;
+
+// Frames between one wave ending and the next one starting; its banner shows meanwhile
+pub const WAVE_BREAK: u32 = 150;
+// Every fifth wave is a boss
+pub const BOSS_EVERY: u32 = 5;
+pub const BOSS_PHASES: i32 = 3;
+// How far outside the sleigh's hitbox a snowball counts as a graze
+pub const GRAZE_MARGIN: f32 = 12.0;
+
+// How an enemy flies
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub enum EnemyKind {
+    // Straight left
+    Drifter,
+    // Left along a sine wave
+    Weaver,
+    // Drifts towards the sleigh's height
+    Seeker,
+    // Slow, and shoots at the sleigh
+    Gunner,
+    // Bauble that bursts into a ring of snowballs
+    Burster,
+    // Snowflake that sprays a spiral
+    Spinner,
+}
+
+// How an enemy shoots
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum FirePattern {
+    None,
+    // One shot at the sleigh
+    Aimed,
+    // A ring in every direction at once
+    Radial,
+    // One shot at a time, turning as it goes
+    Spiral,
+}
+
+impl EnemyKind {
+    pub fn color(&self) -> u32 {
+        match self {
+            EnemyKind::Drifter => 0xE53935FF,
+            EnemyKind::Weaver => 0x43A047FF,
+            EnemyKind::Seeker => 0x424242FF,
+            EnemyKind::Gunner => 0x8E24AAFF,
+            EnemyKind::Burster => 0x1E88E5FF,
+            EnemyKind::Spinner => 0x80DEEAFF,
+        }
+    }
+
+    pub fn pattern(&self) -> FirePattern {
+        match self {
+            EnemyKind::Drifter | EnemyKind::Weaver | EnemyKind::Seeker => FirePattern::None,
+            EnemyKind::Gunner => FirePattern::Aimed,
+            EnemyKind::Burster => FirePattern::Radial,
+            EnemyKind::Spinner => FirePattern::Spiral,
+        }
+    }
+
+    pub fn speed_scale(&self) -> f32 {
+        match self {
+            EnemyKind::Drifter => 1.0,
+            EnemyKind::Weaver => 0.9,
+            EnemyKind::Seeker => 0.8,
+            EnemyKind::Gunner => 0.6,
+            EnemyKind::Burster => 0.7,
+            EnemyKind::Spinner => 0.5,
+        }
+    }
+}
+
+// One of the regular waves: who comes, how many, and how often
+pub struct WavePlan {
+    pub name: &'static str,
+    pub kinds: &'static [EnemyKind],
+    pub count: u32,
+    pub interval: u32,
+}
+
+// Waves 1-4 of every five, in order; the fifth is the boss
+pub const WAVES: [WavePlan; 4] = [
+    WavePlan { name: "SNOWBALL SQUALL", kinds: &[EnemyKind::Drifter], count: 10, interval: 50 },
+    WavePlan { name: "JINGLE WEAVE", kinds: &[EnemyKind::Weaver, EnemyKind::Drifter], count: 12, interval: 45 },
+    WavePlan { name: "COAL SEEKERS", kinds: &[EnemyKind::Seeker, EnemyKind::Weaver, EnemyKind::Burster], count: 12, interval: 45 },
+    WavePlan { name: "NAUGHTY GUNNERS", kinds: &[EnemyKind::Gunner, EnemyKind::Spinner, EnemyKind::Seeker, EnemyKind::Drifter], count: 14, interval: 40 },
+];
+
+pub const BOSS_NAME: &str = "THE GRINCH'S BLIMP";
+
+// Decides what comes next in the endless run. Enemies in play are the game's
+// business; the director only says when to send each one.
+#[turbo::serialize]
+#[derive(PartialEq)]
+pub struct WaveDirector {
+    // Current wave, from 1
+    pub wave: u32,
+    // Enemies (or the boss) sent so far this wave
+    pub spawned: u32,
+    pub spawn_timer: u32,
+    // Counts down before the wave starts
+    pub break_timer: u32,
+}
+
+impl Default for WaveDirector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WaveDirector {
+    pub fn new() -> Self {
+        Self { wave: 1, spawned: 0, spawn_timer: 0, break_timer: WAVE_BREAK }
+    }
+
+    pub fn is_boss_wave(&self) -> bool {
+        self.wave.is_multiple_of(BOSS_EVERY)
+    }
+
+    // Bosses beaten so far; every one makes the waves after it harder
+    pub fn cycle(&self) -> u32 {
+        (self.wave - 1) / BOSS_EVERY
+    }
+
+    pub fn plan(&self) -> &'static WavePlan {
+        &WAVES[((self.wave - 1) % BOSS_EVERY) as usize % WAVES.len()]
+    }
+
+    pub fn name(&self) -> &'static str {
+        if self.is_boss_wave() { BOSS_NAME } else { self.plan().name }
+    }
+
+    pub fn count(&self) -> u32 {
+        if self.is_boss_wave() { 1 } else { self.plan().count + 2 * self.cycle() }
+    }
+
+    pub fn speed(&self) -> f32 {
+        2.0 + 0.5 * self.cycle() as f32 + 0.1 * ((self.wave - 1) % BOSS_EVERY) as f32
+    }
+
+    pub fn hp_bonus(&self) -> i32 {
+        self.cycle() as i32
+    }
+
+    pub fn in_break(&self) -> bool {
+        self.break_timer > 0
+    }
+
+    pub fn is_spawning_done(&self) -> bool {
+        self.spawned >= self.count()
+    }
+
+    // Once a frame. Some(kind) when a regular enemy should appear now;
+    // boss waves send theirs through `take_boss` instead.
+    pub fn tick(&mut self, rng: &mut Rng) -> Option<EnemyKind> {
+        if self.break_timer > 0 {
+            self.break_timer -= 1;
+            return None;
+        }
+        if self.is_boss_wave() || self.is_spawning_done() {
+            return None;
+        }
+        self.spawn_timer += 1;
+        if self.spawn_timer < self.plan().interval {
+            return None;
+        }
+        self.spawn_timer = 0;
+        self.spawned += 1;
+        let kinds = self.plan().kinds;
+        Some(kinds[(rng.u32() % kinds.len() as u32) as usize])
+    }
+
+    // True once per boss wave, when the boss should fly in
+    pub fn take_boss(&mut self) -> bool {
+        if self.in_break() || !self.is_boss_wave() || self.is_spawning_done() {
+            return false;
+        }
+        self.spawned = 1;
+        true
+    }
+
+    pub fn next_wave(&mut self) {
+        self.wave += 1;
+        self.spawned = 0;
+        self.spawn_timer = 0;
+        self.break_timer = WAVE_BREAK;
+    }
+}
+
+// The Grinch's blimp. Its HP bar is split into equal phases, each with its own attacks.
+#[turbo::serialize]
+#[derive(PartialEq)]
+pub struct SleighBoss {
+    pub x: f32,
+    pub y: f32,
+    pub w: f32,
+    pub h: f32,
+    pub hp: i32,
+    pub max_hp: i32,
+    // 0..BOSS_PHASES, going up as it takes damage
+    pub phase: i32,
+    pub timer: u32,
+    // Angle of the phase 3 spiral
+    pub spiral: f32,
+    // Frames left of the hit / phase change flash
+    pub flash: u32,
+}
+
+impl SleighBoss {
+    // `number` counts bosses from 1; each one is tougher
+    pub fn new(number: u32) -> Self {
+        let hp = 60 + 30 * (number as i32 - 1);
+        Self { x: 530.0, y: 100.0, w: 120.0, h: 64.0, hp, max_hp: hp, phase: 0, timer: 0, spiral: 0.0, flash: 0 }
+    }
+
+    pub fn center(&self) -> (f32, f32) {
+        (self.x + self.w / 2.0, self.y + self.h / 2.0)
+    }
+
+    pub fn is_entering(&self) -> bool {
+        self.x > 370.0
+    }
+
+    // HP left in the current phase's share of the bar
+    pub fn phase_hp(&self) -> (i32, i32) {
+        let share = self.max_hp / BOSS_PHASES;
+        let floor = self.max_hp - share * (self.phase + 1);
+        ((self.hp - floor).max(0), share)
+    }
+
+    // Takes damage; true when that started a new phase
+    pub fn hit(&mut self, damage: i32) -> bool {
+        self.hp -= damage;
+        self.flash = 4;
+        let share = self.max_hp / BOSS_PHASES;
+        let phase = ((self.max_hp - self.hp.max(0)) / share).min(BOSS_PHASES - 1);
+        if phase != self.phase {
+            self.phase = phase;
+            self.flash = 30;
+            return true;
+        }
+        false
+    }
+
+    // Moves and attacks for one frame: the shots it fired, and whether it drops a minion
+    pub fn update(&mut self, target: (f32, f32)) -> (Vec<SleighShot>, bool) {
+        self.timer += 1;
+        if self.flash > 0 {
+            self.flash -= 1;
+        }
+        if self.is_entering() {
+            self.x -= 1.5;
+            return (vec![], false);
+        }
+
+        // Bobs up and down, faster each phase
+        let bob = 0.02 + 0.01 * self.phase as f32;
+        self.y = 112.0 - self.h / 2.0 + (self.timer as f32 * bob).sin() * 80.0;
+
+        let from = (self.x + 10.0, self.y + self.h / 2.0);
+        let t = self.timer;
+        let mut shots = vec![];
+        let mut minion = false;
+        match self.phase {
+            // Aimed fans of three
+            0 => if t.is_multiple_of(70) {
+                shots = aimed(from, target, 3.0, 3, 0.25);
+            },
+            // Rings of snowballs, and gift minions
+            1 => {
+                if t.is_multiple_of(80) {
+                    shots = radial(from, 10, 2.5, (t / 80) as f32 * 0.3);
+                }
+                minion = t.is_multiple_of(240);
+            },
+            // A spinning spiral, with the odd aimed shot
+            _ => {
+                if t.is_multiple_of(5) {
+                    self.spiral += 0.35;
+                    shots = radial(from, 2, 2.5, self.spiral);
+                }
+                if t.is_multiple_of(120) {
+                    shots.extend(aimed(from, target, 3.5, 1, 0.0));
+                }
+            },
+        }
+        (shots, minion)
+    }
+}
+
+// What an enemy with `pattern` fires on frame `age` of its life. Shots only
+// start once it's well on screen (x < 480) and stop once it's past `to`.
+pub fn enemy_fire(pattern: FirePattern, from: (f32, f32), to: (f32, f32), age: u32) -> Vec<SleighShot> {
+    if from.0 > 480.0 || from.0 < to.0 + 40.0 {
+        return vec![];
+    }
+    match pattern {
+        FirePattern::None => vec![],
+        FirePattern::Aimed if age.is_multiple_of(90) => aimed(from, to, 2.5, 1, 0.0),
+        FirePattern::Radial if age.is_multiple_of(120) => radial(from, 8, 2.0, age as f32 * 0.1),
+        FirePattern::Spiral if age.is_multiple_of(10) => radial(from, 1, 2.0, age as f32 * 0.04),
+        _ => vec![],
+    }
+}
+
+// Where a snowball is relative to the sleigh
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ShotContact {
+    Hit,
+    // Close, but not touching
+    Graze,
+    Clear,
+}
+
+// `player` is the sleigh's hitbox (x, y, w, h); snowballs are 6px across
+pub fn shot_contact(player: (f32, f32, f32, f32), shot: &SleighShot) -> ShotContact {
+    let overlaps = |margin: f32| {
+        shot.x + 3.0 > player.0 - margin && shot.x - 3.0 < player.0 + player.2 + margin
+            && shot.y + 3.0 > player.1 - margin && shot.y - 3.0 < player.1 + player.3 + margin
+    };
+    if overlaps(0.0) {
+        ShotContact::Hit
+    } else if overlaps(GRAZE_MARGIN) {
+        ShotContact::Graze
+    } else {
+        ShotContact::Clear
+    }
+}
+
+// `count` shots at `to`, fanned `spread` radians apart
+pub fn aimed(from: (f32, f32), to: (f32, f32), speed: f32, count: u32, spread: f32) -> Vec<SleighShot> {
+    let angle = (to.1 - from.1).atan2(to.0 - from.0);
+    let first = angle - spread * (count as f32 - 1.0) / 2.0;
+    (0..count).map(|i| SleighShot::at_angle(from, first + spread * i as f32, speed)).collect()
+}
+
+// `count` shots evenly around a circle, turned by `offset` radians
+pub fn radial(from: (f32, f32), count: u32, speed: f32, offset: f32) -> Vec<SleighShot> {
+    let step = std::f32::consts::TAU / count as f32;
+    (0..count).map(|i| SleighShot::at_angle(from, offset + step * i as f32, speed)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_wave(director: &mut WaveDirector, rng: &mut Rng) -> Vec<EnemyKind> {
+        let mut sent = vec![];
+        for _ in 0..5000 {
+            if let Some(kind) = director.tick(rng) {
+                sent.push(kind);
+            }
+        }
+        sent
+    }
+
+    #[test]
+    fn waves_send_their_plan_then_a_boss_every_fifth() {
+        let mut rng = Rng::new(3);
+        let mut director = WaveDirector::new();
+        for wave in 1..=4 {
+            assert_eq!(director.wave, wave);
+            let sent = run_wave(&mut director, &mut rng);
+            assert_eq!(sent.len() as u32, director.plan().count);
+            assert!(sent.iter().all(|k| director.plan().kinds.contains(k)));
+            assert!(!director.take_boss());
+            director.next_wave();
+        }
+
+        assert!(director.is_boss_wave());
+        assert_eq!(director.name(), BOSS_NAME);
+        assert!(run_wave(&mut director, &mut rng).is_empty());
+        assert!(director.take_boss());
+        assert!(!director.take_boss());
+        assert!(director.is_spawning_done());
+    }
+
+    #[test]
+    fn later_cycles_are_harder() {
+        let mut director = WaveDirector::new();
+        let first = (director.count(), director.speed(), director.hp_bonus());
+        for _ in 0..BOSS_EVERY {
+            director.next_wave();
+        }
+        assert_eq!(director.plan().name, WAVES[0].name);
+        assert!(director.count() > first.0 && director.speed() > first.1 && director.hp_bonus() > first.2);
+    }
+
+    #[test]
+    fn boss_changes_phase_at_each_third_of_its_hp() {
+        let mut boss = SleighBoss::new(1);
+        assert_eq!(boss.phase_hp(), (20, 20));
+        assert!(!boss.hit(19));
+        assert!(boss.hit(1));
+        assert_eq!(boss.phase, 1);
+        assert_eq!(boss.phase_hp(), (20, 20));
+        assert!(boss.hit(25));
+        assert_eq!(boss.phase, 2);
+        assert!(!boss.hit(100));
+        assert_eq!(boss.phase, 2);
+    }
+
+    #[test]
+    fn every_phase_attacks_once_the_boss_arrives() {
+        for phase in 0..BOSS_PHASES {
+            let mut boss = SleighBoss::new(1);
+            boss.phase = phase;
+            let mut shots = 0;
+            for _ in 0..600 {
+                shots += boss.update((40.0, 140.0)).0.len();
+            }
+            assert!(!boss.is_entering());
+            assert!(shots > 0, "phase {}", phase);
+        }
+    }
+
+    #[test]
+    fn aimed_shots_head_for_the_target() {
+        let shots = aimed((100.0, 100.0), (0.0, 100.0), 2.0, 1, 0.0);
+        assert!((shots[0].vx + 2.0).abs() < 1e-4 && shots[0].vy.abs() < 1e-4);
+        assert_eq!(radial((0.0, 0.0), 8, 1.0, 0.0).len(), 8);
+    }
+
+    #[test]
+    fn enemy_patterns_fire_on_their_own_rhythm() {
+        let (from, to) = (300.0, 100.0);
+        let fired = |pattern| (1..=240).map(|age| enemy_fire(pattern, (from, to), (40.0, 140.0), age).len()).sum::<usize>();
+        assert_eq!(fired(FirePattern::None), 0);
+        assert_eq!(fired(FirePattern::Aimed), 2);
+        assert_eq!(fired(FirePattern::Radial), 16);
+        assert_eq!(fired(FirePattern::Spiral), 24);
+        // Not from off screen, nor from behind the sleigh
+        assert!(enemy_fire(FirePattern::Radial, (500.0, to), (40.0, 140.0), 120).is_empty());
+        assert!(enemy_fire(FirePattern::Radial, (60.0, to), (40.0, 140.0), 120).is_empty());
+    }
+
+    #[test]
+    fn near_misses_graze_and_touches_hit() {
+        let player = (100.0, 100.0, 60.0, 25.0);
+        let at = |x, y| SleighShot::at_angle((x, y), 0.0, 0.0);
+        assert_eq!(shot_contact(player, &at(130.0, 110.0)), ShotContact::Hit);
+        assert_eq!(shot_contact(player, &at(130.0, 98.0)), ShotContact::Hit);
+        assert_eq!(shot_contact(player, &at(130.0, 90.0)), ShotContact::Graze);
+        assert_eq!(shot_contact(player, &at(170.0, 110.0)), ShotContact::Graze);
+        assert_eq!(shot_contact(player, &at(130.0, 80.0)), ShotContact::Clear);
+    }
+}
@@ -1,4 +1,5 @@
 use turbo::*;
+use crate::model::rng::Rng;
 
 #[turbo::serialize]
 #[derive(Copy, PartialEq)]
@@ -10,20 +11,20 @@ pub struct SnowFlake {
 }
 
 impl SnowFlake {
-    pub fn new() -> Self {
+    pub fn new(rng: &mut Rng) -> Self {
         Self {
-            x: (random::u32() % screen().w()) as f32,
-            y: -((random::u32() % screen().h()) as f32),
-            speed: (random::u32() % 3 + 2) as f32 * 0.5, // 1.0 to 2.5
-            size: if random::u32() % 10 < 8 { 1 } else { 2 }, // Mostly 1px, some 2px
+            x: (rng.u32() % screen().w()) as f32,
+            y: -((rng.u32() % screen().h()) as f32),
+            speed: (rng.u32() % 3 + 2) as f32 * 0.5, // 1.0 to 2.5
+            size: if rng.u32() % 10 < 8 { 1 } else { 2 }, // Mostly 1px, some 2px
         }
     }
 
-    pub fn update(&mut self) {
+    pub fn update(&mut self, rng: &mut Rng) {
         self.y += self.speed;
         if self.y > screen().h() as f32 {
-            self.y = -((random::u32() % 10) as f32);
-            self.x = (random::u32() % screen().w()) as f32;
+            self.y = -((rng.u32() % 10) as f32);
+            self.x = (rng.u32() % screen().w()) as f32;
         }
     }
 
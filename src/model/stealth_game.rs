@@ -1,4 +1,29 @@
 use turbo::*;
+use crate::model::rng::Rng;
+use crate::model::input::Input;
+use crate::model::game_mode::GameMode;
+use crate::model::replay::Replay;
+use crate::model::records::RecordKind;
+use crate::model::storage::SaveData;
+use crate::model::settings::Settings;
+use crate::model::stealth_house::{PropKind, StealthHouse, PLAYER_R};
+use std::f32::consts::{PI, TAU};
+
+// How far, and how wide either side of where it faces (radians), a pet that's up can see
+const VIEW_RANGE: f32 = 200.0;
+const VIEW_HALF_ANGLE: f32 = 0.5;
+// Alert per frame while Santa is in a cone
+const SEEN_ALERT: f32 = 2.5;
+// Sleeping dogs get up at this alert, and lie back down below SLEEP_ALERT
+const WAKE_ALERT: f32 = 60.0;
+const SLEEP_ALERT: f32 = 30.0;
+// Radians per frame an awake dog turns towards footsteps, or looks around otherwise
+const TURN_RATE: f32 = 0.05;
+const LOOK_RATE: f32 = 0.01;
+// Any closer to a pet that's up, with nothing in between, and you're caught
+const CATCH_RANGE: f32 = 50.0;
+// Rays drawn per cone
+const CONE_RAYS: usize = 32;
 
 #[turbo::serialize]
 #[derive(Copy, PartialEq)]
@@ -20,6 +45,34 @@ pub struct StealthDog {
     pub alert: f32,
     pub is_patrol: bool,
     pub name: String,
+    // Radians, 0 is right
+    pub facing: f32,
+    pub awake: bool,
+}
+
+impl StealthDog {
+    // The wolf and awake dogs watch; sleeping dogs only hear
+    pub fn looking(&self) -> bool {
+        self.is_patrol || self.awake
+    }
+
+    // x, y is in this pet's cone, with no wall or furniture in the way
+    pub fn sees(&self, house: &StealthHouse, x: f32, y: f32) -> bool {
+        let (dx, dy) = (x - self.x, y - self.y);
+        self.looking()
+            && dx.hypot(dy) < VIEW_RANGE
+            && angle_between(self.facing, dy.atan2(dx)).abs() <= VIEW_HALF_ANGLE
+            && house.line_of_sight((self.x, self.y), (x, y))
+    }
+
+    // Far ends of the cone's rays, cut short by whatever they hit
+    pub fn view_cone(&self, house: &StealthHouse) -> Vec<(f32, f32)> {
+        (0..=CONE_RAYS).map(|i| {
+            let a = self.facing - VIEW_HALF_ANGLE + 2.0 * VIEW_HALF_ANGLE * i as f32 / CONE_RAYS as f32;
+            let len = house.ray_length((self.x, self.y), a, VIEW_RANGE);
+            (self.x + a.cos() * len, self.y + a.sin() * len)
+        }).collect()
+    }
 }
 
 #[turbo::serialize]
@@ -39,6 +92,8 @@ pub struct StealthWave {
 #[turbo::serialize]
 pub struct StealthGame {
     pub state: StealthState,
+    // Built from the run's seed
+    pub house: StealthHouse,
     pub player_x: f32,
     pub player_y: f32,
     pub cam_x: f32,
@@ -46,7 +101,7 @@ pub struct StealthGame {
     pub stars_collected: u32,
     pub gift1_done: bool, // Dog 1 Room
     pub gift2_done: bool, // Dog 2 Room
-    pub gift3_done: bool, // Tree (Wolf)
+    pub gift3_done: bool, // Tree (Wolf's room)
     pub cookie_done: bool,
     pub exit_open: bool,
     pub time_elapsed: f32, // Timer
@@ -54,34 +109,36 @@ pub struct StealthGame {
     pub stars: Vec<StealthStar>,
     pub waves: Vec<StealthWave>,
     pub msg: String,
+    pub rng: Rng,
+    // Screen shake requested this frame (pixels); GameState applies and clears it
+    pub shake: usize,
+
+    // Records (Win only, ranked by time)
+    pub result_recorded: bool,
+    pub record_rank: Option<usize>,
 }
 
 impl StealthGame {
-    pub fn new() -> Self {
-        // Map is 920x900.
-        // Dogs: 
-        // D1: 175, 175 (Sleep)
-        // D2: 745, 450 (Sleep)
-        // D3: 600, 700 (Patrol 500-800)
-        
+    pub fn new(seed: u32) -> Self {
+        let house = StealthHouse::generate(seed);
+
+        // Two dogs asleep in their rooms, and the wolf pacing the tree's room
+        let [(d1x, d1y), (d2x, d2y)] = house.dog_beds;
+        let (wx, wy, wmin, wmax) = house.wolf;
         let dogs = vec![
-            StealthDog { id: 0, x: 175.0, y: 175.0, patrol_min: 0.0, patrol_max: 0.0, patrol_dir: 0.0, alert: 0.0, is_patrol: false, name: "Dog 1".to_string() },
-            StealthDog { id: 1, x: 745.0, y: 450.0, patrol_min: 0.0, patrol_max: 0.0, patrol_dir: 0.0, alert: 0.0, is_patrol: false, name: "Dog 2".to_string() },
-            StealthDog { id: 2, x: 600.0, y: 700.0, patrol_min: 500.0, patrol_max: 800.0, patrol_dir: 1.5, alert: 0.0, is_patrol: true, name: "Wolf".to_string() },
+            StealthDog { id: 0, x: d1x, y: d1y, patrol_min: 0.0, patrol_max: 0.0, patrol_dir: 0.0, alert: 0.0, is_patrol: false, name: "Dog 1".to_string(), facing: 0.0, awake: false },
+            StealthDog { id: 1, x: d2x, y: d2y, patrol_min: 0.0, patrol_max: 0.0, patrol_dir: 0.0, alert: 0.0, is_patrol: false, name: "Dog 2".to_string(), facing: 0.0, awake: false },
+            StealthDog { id: 2, x: wx, y: wy, patrol_min: wmin, patrol_max: wmax, patrol_dir: 1.5, alert: 0.0, is_patrol: true, name: "Wolf".to_string(), facing: 0.0, awake: true },
         ];
 
-        let stars = vec![
-            StealthStar { x: 100.0, y: 100.0 },
-            StealthStar { x: 745.0, y: 100.0 },
-            StealthStar { x: 100.0, y: 450.0 },
-            StealthStar { x: 745.0, y: 700.0 },
-            StealthStar { x: 250.0, y: 750.0 },
-        ];
+        let stars = house.stars.iter().map(|&(x, y)| StealthStar { x, y }).collect();
+        let (player_x, player_y) = house.spawn;
 
         Self {
             state: StealthState::Menu,
-            player_x: 460.0,
-            player_y: 250.0,
+            house,
+            player_x,
+            player_y,
             cam_x: 0.0,
             cam_y: 0.0,
             stars_collected: 0,
@@ -95,22 +152,27 @@ impl StealthGame {
             stars,
             waves: vec![],
             msg: "".to_string(),
+            rng: Rng::new(seed),
+            shake: 0,
+            result_recorded: false,
+            record_rank: None,
         }
     }
 
-    pub fn update(&mut self) {
-        let gp = gamepad::get(0);
+    // Mission in progress (pausable)
+    pub fn update(&mut self, input: &Input) {
+        let gp = input.pad(0);
 
         if self.state == StealthState::Menu {
+            // The menu already shows the house this run plays in
             if gp.a.just_pressed() || gp.start.just_pressed() {
-                self.reset();
                 self.state = StealthState::Playing;
             }
             return;
         }
         if self.state == StealthState::GameOver || self.state == StealthState::Win {
              if gp.a.just_pressed() || gp.start.just_pressed() {
-                self.state = StealthState::Menu;
+                self.reset();
              }
              return;
         }
@@ -133,21 +195,7 @@ impl StealthGame {
         let ny = self.player_y + dy;
 
         // Collision
-        let walls = [
-             (0.0, 0.0, 920.0, 20.0), (0.0, 880.0, 920.0, 20.0), (0.0, 0.0, 20.0, 900.0), (900.0, 0.0, 20.0, 900.0),
-             (0.0, 300.0, 200.0, 20.0), (720.0, 300.0, 200.0, 20.0),
-             (0.0, 550.0, 200.0, 20.0), (720.0, 550.0, 200.0, 20.0),
-             (300.0, 0.0, 20.0, 200.0), (300.0, 300.0, 20.0, 300.0),
-             (620.0, 0.0, 20.0, 200.0), (620.0, 300.0, 20.0, 250.0),
-             (400.0, 600.0, 20.0, 300.0)
-        ];
-        
-        let mut hit = false;
-        for w in walls {
-            if rect_circle_hit(w.0, w.1, w.2, w.3, nx, ny, 15.0) { hit = true; break; }
-        }
-
-        if !hit {
+        if !self.house.blocked(nx, ny, PLAYER_R) {
             self.player_x = nx;
             self.player_y = ny;
         }
@@ -156,7 +204,7 @@ impl StealthGame {
         let moving = dx != 0.0 || dy != 0.0;
         if moving {
             // Constant low noise. Use patience (stop) to recover.
-             if (rand() % 100) > 85 { // Less frequent waves
+             if (self.rng.u32() % 100) > 85 { // Less frequent waves
                  self.waves.push(StealthWave { x: self.player_x, y: self.player_y, r: 5.0, alpha: 0.6 });
              }
              
@@ -178,46 +226,66 @@ impl StealthGame {
         }
 
         // Dogs Logic
+        let (px, py) = (self.player_x, self.player_y);
         for d in &mut self.dogs {
-            // Patrol
             if d.is_patrol {
+                // Patrol, looking the way it walks
                 d.x += d.patrol_dir;
                 if d.x > d.patrol_max || d.x < d.patrol_min { d.patrol_dir *= -1.0; }
-                if ((self.player_x - d.x).powi(2) + (self.player_y - d.y).powi(2)).sqrt() < 50.0 {
-                    d.alert = 100.0;
+                d.facing = if d.patrol_dir > 0.0 { 0.0 } else { PI };
+            } else {
+                // Loud enough and a dog gets up; calm again and it lies back down
+                if d.alert >= WAKE_ALERT {
+                    d.awake = true;
+                } else if d.alert < SLEEP_ALERT {
+                    d.awake = false;
+                }
+                if d.awake {
+                    // Turn towards footsteps, or look around
+                    let turn = if moving && dist(px, py, d.x, d.y) < 400.0 {
+                        angle_between(d.facing, (py - d.y).atan2(px - d.x)).clamp(-TURN_RATE, TURN_RATE)
+                    } else {
+                        LOOK_RATE
+                    };
+                    d.facing = (d.facing + turn).rem_euclid(TAU);
                 }
             }
+
+            // Sight: walls and furniture block it
+            let spotted = d.sees(&self.house, px, py);
+            if spotted { d.alert += SEEN_ALERT; }
+            let caught = d.looking() && dist(px, py, d.x, d.y) < CATCH_RANGE && self.house.line_of_sight((d.x, d.y), (px, py));
+            if caught { d.alert = 100.0; }
+
             if d.alert < 0.0 { d.alert = 0.0; }
             if d.alert >= 100.0 {
                 self.state = StealthState::GameOver;
-                self.msg = format!("{} WOKE UP!", d.name);
+                self.shake = 4;
+                self.msg = if spotted && !caught { format!("{} SPOTTED YOU!", d.name) } else { format!("{} WOKE UP!", d.name) };
             }
         }
 
-        // Interact (Space/A)
-        if gp.a.just_pressed() || gp.start.just_pressed() {
-            // Need to be reasonably close. 60px is generous.
-            // Gift 1 (Dog 1): 175, 175 (Near Dog 1 but slightly offset? No, room center is ~150,150. Dog is 175,175. Let's aim for 150, 150)
-            // Let's check proximity to Dog itself for "placing near dog".
-            
-            // Objective 1: Near Dog 1 (175, 175). Let's say within 100px of Dog 1.
-            if !self.gift1_done && dist(self.player_x, self.player_y, 175.0, 175.0) < 100.0 {
+        // Interact (A; Start during play opens the pause menu)
+        if gp.a.just_pressed() {
+            // Need to be reasonably close
+            let h = &self.house;
+            let near = |(x, y): (f32, f32), reach: f32| dist(self.player_x, self.player_y, x, y) < reach;
+
+            // Objectives 1 and 2: within 100px of where each dog sleeps
+            if !self.gift1_done && near(h.dog_beds[0], 100.0) {
                 self.gift1_done = true;
             }
-            
-            // Objective 2: Near Dog 2 (745, 450). Within 100px.
-            if !self.gift2_done && dist(self.player_x, self.player_y, 745.0, 450.0) < 100.0 {
+            if !self.gift2_done && near(h.dog_beds[1], 100.0) {
                 self.gift2_done = true;
             }
 
-            // Objective 3: Near Tree/Wolf (800, 800) or Wolf Patrol (600, 700).
-            // User said "Christmas tree ke pass". Tree is at 800,800.
-            if !self.gift3_done && dist(self.player_x, self.player_y, 800.0, 800.0) < 80.0 {
+            // Objective 3: by the Christmas tree
+            if !self.gift3_done && near(h.tree, 80.0) {
                 self.gift3_done = true;
             }
 
-            // Cookie: 100, 750 (Kitchen Table area)
-            if !self.cookie_done && dist(self.player_x, self.player_y, 100.0, 750.0) < 80.0 {
+            // Cookie (Kitchen Table area)
+            if !self.cookie_done && near(h.cookie, 80.0) {
                 self.cookie_done = true;
             }
         }
@@ -238,8 +306,9 @@ impl StealthGame {
              self.exit_open = true;
         }
 
-        // Exit: 410, 20
-        if self.exit_open && self.player_y < 50.0 && (self.player_x - 460.0).abs() < 50.0 {
+        // Exit, in the wall above the hall
+        let (ex, ey) = self.house.exit;
+        if self.exit_open && self.player_y < ey + 30.0 && (self.player_x - ex).abs() < 50.0 {
              self.state = StealthState::Win;
              self.msg = format!("TIME: {:.1}s", self.time_elapsed);
         }
@@ -257,13 +326,13 @@ impl StealthGame {
         }
 
         // Camera
-        self.cam_x = (self.player_x - 256.0).clamp(0.0, 920.0 - 512.0);
-        self.cam_y = (self.player_y - 144.0).clamp(0.0, 900.0 - 288.0);
+        self.cam_x = (self.player_x - 256.0).clamp(0.0, (self.house.width - 512.0).max(0.0));
+        self.cam_y = (self.player_y - 144.0).clamp(0.0, (self.house.height - 288.0).max(0.0));
     }
 
+    // Back to the menu with the next run's house
     fn reset(&mut self) {
-        *self = Self::new();
-        self.state = StealthState::Playing;
+        *self = Self::new(self.rng.u32());
     }
 
     pub fn draw(&self) {
@@ -277,71 +346,53 @@ impl StealthGame {
             text!("- Eat Cookie (Kitchen)", x=140, y=160, font="small", color=0xAAAAAAFF);
             text!("- Collect 5 Stars", x=140, y=175, font="small", color=0xAAAAAAFF);
             text!("Use ARROWS to Move. STOP to quiet down.", x=120, y=200, font="small", color=0xFF00FFFF);
-            text!("Press SPACE/A to Interact.", x=160, y=215, font="small", color=0xFF00FFFF);
+            text!("Press A (Z) to Interact.", x=164, y=213, font="small", color=0xFF00FFFF);
+            text!("Keep out of view cones. Furniture hides you.", x=146, y=226, font="small", color=0xFF00FFFF);
             text!("Press Start to Begin", x=180, y=240, font="small", color=0x888888FF);
+            let seed_txt = format!("House #{}", self.house.seed);
+            text!(&seed_txt, x=256 - seed_txt.len() as i32 * 5 / 2, y=255, font="small", color=0x666666FF);
             return;
         }
 
         let cx = self.cam_x as i32;
         let cy = self.cam_y as i32;
 
+        let h = &self.house;
+
         // Draw Map (Rooms)
-        let rooms = [
-            ("Master Bed", 50, 50, 250, 250, 0x3E2723FF),
-            ("Main Hall", 300, 50, 320, 450, 0x212121FF),
-            ("Kids Bed", 620, 50, 250, 250, 0x3E2723FF),
-            ("Library", 50, 350, 250, 200, 0x263238FF),
-            ("Dining", 620, 350, 250, 200, 0x4E342EFF),
-            ("Kitchen", 50, 600, 350, 250, 0x37474FFF),
-            ("Living", 450, 550, 420, 300, 0x5D4037FF)
-        ];
-        
-        for r in rooms {
-            rect!(x=r.1 - cx, y=r.2 - cy, w=r.3, h=r.4, color=r.5);
-            text!(r.0, x=r.1 - cx + 10, y=r.2 - cy + 25, font="small", color=0xFFFFFF33);
+        for r in &h.rooms {
+            rect!(x=r.x as i32 - cx, y=r.y as i32 - cy, w=r.w as u32, h=r.h as u32, color=r.color);
+            text!(&r.name, x=r.x as i32 - cx + 10, y=r.y as i32 - cy + 5, font="small", color=0xFFFFFF33);
         }
 
         // Walls
-         let walls = [
-             (0, 0, 920, 20), (0, 880, 920, 20), (0, 0, 20, 900), (900, 0, 20, 900),
-             (0, 300, 200, 20), (720, 300, 200, 20),
-             (0, 550, 200, 20), (720, 550, 200, 20),
-             (300, 0, 20, 200), (300, 300, 20, 300),
-             (620, 0, 20, 200), (620, 300, 20, 250),
-             (400, 600, 20, 300)
-        ];
-        for w in walls {
-             rect!(x=w.0 - cx, y=w.1 - cy, w=w.2, h=w.3, color=0x000000FF);
+        for w in &h.walls {
+            rect!(x=w.0 as i32 - cx, y=w.1 as i32 - cy, w=w.2 as u32, h=w.3 as u32, color=0x000000FF);
         }
 
         // Objectives
-        
-        // Gift 1 (Dog 1)
-        if self.gift1_done {
-            text!("GIFT", x=160 - cx, y=100 - cy, font="medium", color=0x2ECC71FF);
-        } else {
-            // Draw a marker for where to place it
-            rect!(x=150 - cx, y=150 - cy, w=50, h=50, border_size=2, border_color=0xAAAAAAFF, color=0x00000000);
-            text!("PLACE", x=155 - cx, y=165 - cy, font="small", color=0xAAAAAAFF);
-        }
 
-        // Gift 2 (Dog 2)
-        if self.gift2_done {
-            text!("GIFT", x=745 - cx, y=400 - cy, font="medium", color=0x2ECC71FF);
-        } else {
-             rect!(x=720 - cx, y=420 - cy, w=50, h=50, border_size=2, border_color=0xAAAAAAFF, color=0x00000000);
-             text!("PLACE", x=725 - cx, y=435 - cy, font="small", color=0xAAAAAAFF);
+        // Gifts 1 and 2, by each sleeping dog
+        for (done, (gx, gy)) in [(self.gift1_done, h.dog_beds[0]), (self.gift2_done, h.dog_beds[1])] {
+            let (gx, gy) = (gx as i32 - cx, gy as i32 - cy);
+            if done {
+                text!("GIFT", x=gx - 15, y=gy - 60, font="medium", color=0x2ECC71FF);
+            } else {
+                // Draw a marker for where to place it
+                rect!(x=gx - 25, y=gy - 25, w=50, h=50, border_size=2, border_color=0xAAAAAAFF, color=0x00000000);
+                text!("PLACE", x=gx - 20, y=gy - 10, font="small", color=0xAAAAAAFF);
+            }
         }
 
         // Gift 3 (Tree)
-        let tx = 800 - cx;
-        let ty = 800 - cy;
+        let tx = h.tree.0 as i32 - cx;
+        let ty = h.tree.1 as i32 - cy;
         if self.gift3_done { text!("GIFT", x=tx, y=ty, font="medium", color=0x2ECC71FF); }
         else { text!("TREE", x=tx, y=ty, font="medium", color=0x388E3CFF); }
 
         // Cookie
-        let kx = 100 - cx;
-        let ky = 750 - cy;
+        let kx = h.cookie.0 as i32 - cx;
+        let ky = h.cookie.1 as i32 - cy;
         if self.cookie_done { text!("YUM", x=kx, y=ky, font="medium", color=0xF1C40FFF); }
         else { text!("COOKIE", x=kx, y=ky, font="medium", color=0xD35400FF); }
 
@@ -350,12 +401,25 @@ impl StealthGame {
             text!("*", x=s.x as i32 - cx, y=s.y as i32 - cy, font="large", color=0xF1C40FFF);
         }
 
+        // View cones, cut short by walls and furniture; red while they can see Santa
+        for d in self.dogs.iter().filter(|d| d.looking()) {
+            let (ox, oy) = (d.x as i32 - cx, d.y as i32 - cy);
+            let (fill, edge) = if d.sees(h, self.player_x, self.player_y) { (0xE74C3C22, 0xE74C3CAA) } else { (0xFFF59D18, 0xFFF59D88) };
+            let cone = d.view_cone(h);
+            for &(x, y) in &cone {
+                path!(start=(ox, oy), end=(x as i32 - cx, y as i32 - cy), size=3, color=fill);
+            }
+            for pair in cone.windows(2) {
+                path!(start=(pair[0].0 as i32 - cx, pair[0].1 as i32 - cy), end=(pair[1].0 as i32 - cx, pair[1].1 as i32 - cy), size=1, color=edge);
+            }
+        }
+
         // Dogs
         for d in &self.dogs {
             let dx = d.x as i32 - cx;
             let dy = d.y as i32 - cy;
-            // Facing? (If patrol, use dir. Else default left/right)
-            let face_right = if d.is_patrol { d.patrol_dir > 0.0 } else { true };
+            // Facing: the way it looks
+            let face_right = d.facing.cos() >= 0.0;
             
             // Animation for dogs
             let anim = (self.time_elapsed * 5.0) as u32 % 2; // 0 or 1
@@ -408,19 +472,28 @@ impl StealthGame {
                 rect!(x=dx-10, y=dy+12, w=8, h=3, color=col);
                 rect!(x=dx+4, y=dy+12, w=8, h=3, color=col);
                 
-                // Head resting (Front)
-                rect!(x=dx+10, y=dy-4, w=12, h=10, color=col); 
+                // Head resting (Front), raised and turned once awake
+                let hx = if face_right { dx + 10 } else { dx - 22 };
+                let hy = if d.awake { dy - 8 } else { dy - 4 };
+                rect!(x=hx, y=hy, w=12, h=10, color=col); 
                 // Ears (Floppy)
-                rect!(x=dx+10, y=dy-2, w=4, h=6, color=spot_col);
-                rect!(x=dx+18, y=dy-2, w=4, h=6, color=spot_col);
+                rect!(x=hx, y=hy+2, w=4, h=6, color=spot_col);
+                rect!(x=hx+8, y=hy+2, w=4, h=6, color=spot_col);
                 // Nose
-                rect!(x=dx+14, y=dy+4, w=4, h=3, color=0x212121FF);
-                // Closed Eyes
-                rect!(x=dx+12, y=dy, w=3, h=1, color=0x3E2723FF);
-                rect!(x=dx+17, y=dy, w=3, h=1, color=0x3E2723FF);
+                rect!(x=hx+4, y=hy+8, w=4, h=3, color=0x212121FF);
+                if d.awake {
+                    // Open Eyes
+                    rect!(x=hx+2, y=hy+3, w=2, h=2, color=0xFFFFFFFF);
+                    rect!(x=hx+8, y=hy+3, w=2, h=2, color=0xFFFFFFFF);
+                    text!("!", x=dx-2, y=dy-22, font="small", color=0xF1C40FFF);
+                } else {
+                    // Closed Eyes
+                    rect!(x=hx+2, y=hy+4, w=3, h=1, color=0x3E2723FF);
+                    rect!(x=hx+7, y=hy+4, w=3, h=1, color=0x3E2723FF);
+                }
                 
                 // Zzz floating
-                if (self.time_elapsed * 2.0) as i32 % 2 == 0 {
+                if !d.awake && (self.time_elapsed * 2.0) as i32 % 2 == 0 {
                     text!("z", x=dx, y=dy-20, font="small", color=0xFFFFFF88);
                 }
             }
@@ -482,9 +555,9 @@ impl StealthGame {
 
         // -- FURNITURE & DECOR --
         
-        // 1. Christmas Tree (At Gift 3 Location: 800, 800)
-        let tx = 800 - cx;
-        let ty = 800 - cy;
+        // 1. Christmas Tree (At Gift 3 Location)
+        let tx = h.tree.0 as i32 - cx;
+        let ty = h.tree.1 as i32 - cy;
         // Trunk
         rect!(x=tx-4, y=ty+20, w=8, h=10, color=0x5D4037FF);
         // Leaves (Layers)
@@ -501,9 +574,9 @@ impl StealthGame {
         // Star on Top
         rect!(x=tx-3, y=ty-20, w=6, h=6, color=0xFFD700FF);
         
-        // 2. Kitchen Table (Near Cookie: 100, 750)
-        let kx = 100 - cx;
-        let ky = 750 - cy;
+        // 2. Kitchen Table (Under the Cookie)
+        let kx = h.cookie.0 as i32 - cx;
+        let ky = h.cookie.1 as i32 - cy;
         // Table Top
         rect!(x=kx-30, y=ky, w=60, h=30, color=0x8D6E63FF); // Brown
         rect!(x=kx-25, y=ky+5, w=50, h=20, color=0xA1887FFF); // Light Brown inlay
@@ -519,14 +592,37 @@ impl StealthGame {
             rect!(x=kx+4, y=ky+16, w=1, h=1, color=0x3E2723FF); // Chip
         }
 
-        // 3. Simple Bed (Master Bed: 175, 120 approx)
-        let bx = 150 - cx;
-        let by_pos = 100 - cy;
+        // 3. Simple Bed (Master Bed, where Dog 1 sleeps)
+        let bx = h.dog_beds[0].0 as i32 - 25 - cx;
+        let by_pos = h.dog_beds[0].1 as i32 - 75 - cy;
         rect!(x=bx, y=by_pos, w=80, h=100, color=0x3F51B5FF); // Blanket
         rect!(x=bx, y=by_pos, w=80, h=30, color=0xFFFFFFFF); // Pillow area
         rect!(x=bx+10, y=by_pos+5, w=25, h=20, color=0xEEEEEEFF); // Pillow 1
         rect!(x=bx+45, y=by_pos+5, w=25, h=20, color=0xEEEEEEFF); // Pillow 2
 
+        // 4. Everything else (good for hiding behind)
+        for p in &h.props {
+            let (x, y, w, ph) = (p.x as i32 - cx, p.y as i32 - cy, p.w as u32, p.h as u32);
+            match p.kind {
+                PropKind::Sofa => {
+                    rect!(x=x, y=y, w=w, h=ph, color=0x6D4C41FF);
+                    rect!(x=x+4, y=y+4, w=w-8, h=ph-12, color=0x8D6E63FF); // Cushions
+                }
+                PropKind::Bookcase => {
+                    rect!(x=x, y=y, w=w, h=ph, color=0x4E342EFF);
+                    for shelf in 0..4 {
+                        rect!(x=x+3, y=y+4+shelf*17, w=w-6, h=12, color=0x1565C0FF); // Books
+                    }
+                }
+                PropKind::Chest => {
+                    rect!(x=x, y=y, w=w, h=ph, color=0x795548FF);
+                    rect!(x=x, y=y+10, w=w, h=3, color=0x3E2723FF); // Lid
+                    rect!(x=x+w as i32/2-3, y=y+12, w=6, h=6, color=0xF1C40FFF); // Lock
+                }
+                // Drawn above
+                PropKind::Bed | PropKind::Table | PropKind::Tree => {}
+            }
+        }
 
         // Waves
         for w in &self.waves {
@@ -553,6 +649,10 @@ impl StealthGame {
         let status = format!("G1:{} G2:{} G3:{} C:{}", g1, g2, g3, ck);
         text!(&status, x=10, y=40, font="small", color=0xAAAAAAFF);
 
+        // Which house this is
+        let seed_txt = format!("House #{}", h.seed);
+        text!(&seed_txt, x=10, y=55, font="small", color=0x666666FF);
+
         // HUD Dog Bars (Fixed Right Side)
         text!("Dog 1", x=400, y=30, font="small", color=0xAAAAAAFF);
         rect!(x=440, y=32, w=60, h=6, color=0x444444FF);
@@ -580,26 +680,27 @@ impl StealthGame {
         rect!(x=mm_x, y=mm_y, w=mm_w, h=mm_h, color=0x111111EE, border_radius=4);
         rect!(x=mm_x, y=mm_y, w=mm_w, h=mm_h, border_size=1, border_color=0x444444FF, color=0x00000000);
         
-        // Scale factor: 920x900 -> 120x120 roughly 0.13
-        let scale = 0.13;
-        for r in rooms {
-            rect!(x=mm_x + (r.1 as f32 * scale) as i32, 
-                  y=mm_y + (r.2 as f32 * scale) as i32, 
-                  w=(r.3 as f32 * scale) as u32, 
-                  h=(r.4 as f32 * scale) as u32, 
+        // Scale factor: the longer side of the house fits the map
+        let scale = mm_w as f32 / h.width.max(h.height);
+        for r in &h.rooms {
+            rect!(x=mm_x + (r.x * scale) as i32, 
+                  y=mm_y + (r.y * scale) as i32, 
+                  w=(r.w * scale) as u32, 
+                  h=(r.h * scale) as u32, 
                   color=0x444444FF);
         }
         // Player Dot
         rect!(x=mm_x + (self.player_x * scale) as i32, y=mm_y + (self.player_y * scale) as i32, w=2, h=2, color=0xFF0000FF);
         
         // Targets
-        if !self.gift1_done { rect!(x=mm_x + (175.0 * scale) as i32, y=mm_y + (175.0 * scale) as i32, w=2, h=2, color=0x00FF00FF); }
-        if !self.gift2_done { rect!(x=mm_x + (745.0 * scale) as i32, y=mm_y + (450.0 * scale) as i32, w=2, h=2, color=0x00FF00FF); }
-        if !self.gift3_done { rect!(x=mm_x + (800.0 * scale) as i32, y=mm_y + (800.0 * scale) as i32, w=2, h=2, color=0x00FF00FF); }
+        let targets = [(self.gift1_done, h.dog_beds[0]), (self.gift2_done, h.dog_beds[1]), (self.gift3_done, h.tree)];
+        for (done, (x, y)) in targets {
+            if !done { rect!(x=mm_x + (x * scale) as i32, y=mm_y + (y * scale) as i32, w=2, h=2, color=0x00FF00FF); }
+        }
         
-        // Exit on Map (Top Center)
+        // Exit on Map (above the hall)
         let exit_col = if self.exit_open { 0x00FF00FF } else { 0x555555FF };
-        rect!(x=mm_x + (460.0 * scale) as i32 - 2, y=mm_y + (20.0 * scale) as i32, w=4, h=2, color=exit_col);
+        rect!(x=mm_x + (h.exit.0 * scale) as i32 - 2, y=mm_y + (h.exit.1 * scale) as i32, w=4, h=2, color=exit_col);
 
 
         if self.exit_open {
@@ -612,31 +713,294 @@ impl StealthGame {
             text!("CAUGHT!", x=200, y=120, font="large", color=0xE74C3CFF);
             text!(&self.msg, x=150, y=150, font="small", color=0xFFFFFFFF);
             text!("Press Start", x=200, y=180, font="small", color=0xAAAAAAFF);
+            let seed_txt = format!("House #{}", h.seed);
+            text!(&seed_txt, x=200, y=190, font="small", color=0x666666FF);
         } else if self.state == StealthState::Win {
             rect!(x=100, y=100, w=312, h=100, color=0x000000EE);
             text!("MISSION COMPLETE", x=160, y=120, font="large", color=0x2ECC71FF);
              text!(&self.msg, x=190, y=150, font="small", color=0xFFFFFFFF);
+            if let Some(rank) = self.record_rank {
+                let record_txt = format!("NEW RECORD! #{}", rank + 1);
+                text!(&record_txt, x=190, y=164, font="small", color=0xFFD700FF);
+            }
             text!("Press Start", x=200, y=180, font="small", color=0xAAAAAAFF);
+            let seed_txt = format!("House #{}", h.seed);
+            text!(&seed_txt, x=200, y=190, font="small", color=0x666666FF);
         }
     }
 }
 
-fn rect_circle_hit(rx: f32, ry: f32, rw: f32, rh: f32, cx: f32, cy: f32, cr: f32) -> bool {
-    let tx = cx.clamp(rx, rx + rw);
-    let ty = cy.clamp(ry, ry + rh);
-    let dx = cx - tx;
-    let dy = cy - ty;
-    (dx*dx + dy*dy) < (cr*cr)
-}
-
 fn dist(x1: f32, y1: f32, x2: f32, y2: f32) -> f32 {
     ((x1 - x2).powi(2) + (y1 - y2).powi(2)).sqrt()
 }
 
-fn rand() -> u32 {
-    unsafe {
-        static mut SEED: u32 = 77777;
-        SEED = SEED.wrapping_mul(1103515245).wrapping_add(12345);
-        SEED
+// Turn from angle `a` to angle `b`, the short way round (-PI..PI)
+fn angle_between(a: f32, b: f32) -> f32 {
+    (b - a + PI).rem_euclid(TAU) - PI
+}
+
+impl GameMode for StealthGame {
+    fn from_replay(replay: &Replay) -> Self {
+        StealthGame::new(replay.seed)
+    }
+
+    fn update(&mut self, input: &Input) {
+        StealthGame::update(self, input)
+    }
+
+    fn draw(&self, _settings: &Settings) {
+        StealthGame::draw(self)
+    }
+
+    fn is_running(&self) -> bool {
+        self.state == StealthState::Playing
+    }
+
+    fn take_shake(&mut self) -> usize {
+        std::mem::take(&mut self.shake)
+    }
+
+    fn submit_record(&mut self, save: &mut SaveData, name: &str) {
+        if self.state == StealthState::Win && !self.result_recorded {
+            self.result_recorded = true;
+            let hundredths = (self.time_elapsed * 100.0).round() as i32;
+            self.record_rank = save.submit_record(RecordKind::SilentSanta, name, hundredths);
+        }
+    }
+
+    fn draw_records(&self, save: &SaveData) {
+        if self.state == StealthState::Win {
+            save.records.draw_table(RecordKind::SilentSanta, 364, 80, self.record_rank);
+        }
+    }
+
+    fn instructions(&self) -> &'static [&'static str] {
+        &[
+            "Santa Mission (Stealth):",
+            "- Arrows: Move Carefuly",
+            "- Stop: Quiet down. Avoid view cones",
+            "- A: Interact",
+            "- Start / Esc: Pause Menu",
+            "Goal: Place Gifts, Eat Cookie, Escape!",
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::input::{InputFeed, BTN_A, BTN_UP};
+    use crate::model::stealth_house::HouseProp;
+
+    fn playing() -> StealthGame {
+        let mut game = StealthGame::new(1);
+        game.state = StealthState::Playing;
+        game
+    }
+
+    #[test]
+    fn standing_still_calms_dogs() {
+        let mut game = playing();
+        game.dogs[0].alert = 50.0;
+
+        game.update(&InputFeed::default().next([0, 0]));
+
+        assert!(game.dogs[0].alert < 50.0);
+        assert!(game.state == StealthState::Playing);
+    }
+
+    #[test]
+    fn moving_raises_alert() {
+        let mut game = playing();
+        let before: Vec<f32> = game.dogs.iter().map(|d| d.alert).collect();
+
+        game.update(&InputFeed::default().next([BTN_UP, 0]));
+
+        for (d, b) in game.dogs.iter().zip(before) {
+            assert!(d.alert > b);
+        }
+    }
+
+    #[test]
+    fn full_alert_is_game_over() {
+        let mut game = playing();
+        // Close enough to hear
+        game.player_x = game.dogs[1].x;
+        game.player_y = game.dogs[1].y + 50.0;
+        game.dogs[1].alert = 99.95;
+
+        game.update(&InputFeed::default().next([BTN_UP, 0]));
+
+        assert!(game.state == StealthState::GameOver);
+        assert_eq!(game.msg, "Dog 2 WOKE UP!");
+    }
+
+    #[test]
+    fn touching_patrol_is_game_over() {
+        let mut game = playing();
+        game.player_x = game.dogs[2].x;
+        game.player_y = game.dogs[2].y + 30.0;
+
+        game.update(&InputFeed::default().next([0, 0]));
+
+        assert!(game.state == StealthState::GameOver);
+        assert_eq!(game.msg, "Wolf WOKE UP!");
+    }
+
+    #[test]
+    fn the_wolf_sees_ahead_not_behind() {
+        // Standing still in front of it: seen
+        let mut game = playing();
+        game.house.props.clear();
+        let (wx, wy) = (game.dogs[2].x, game.dogs[2].y);
+        game.player_x = wx + 120.0;
+        game.player_y = wy;
+        let mut feed = InputFeed::default();
+        for _ in 0..10 {
+            game.update(&feed.next([0, 0]));
+        }
+        assert!(game.dogs[2].alert > 15.0);
+
+        // Behind it: nothing
+        let mut game = playing();
+        game.player_x = wx - 120.0;
+        game.player_y = wy;
+        for _ in 0..10 {
+            game.update(&feed.next([0, 0]));
+        }
+        assert_eq!(game.dogs[2].alert, 0.0);
+    }
+
+    #[test]
+    fn being_seen_long_enough_is_game_over() {
+        let mut game = playing();
+        game.house.props.clear();
+        game.player_x = game.dogs[2].x + 120.0;
+        game.player_y = game.dogs[2].y;
+        game.dogs[2].alert = 99.0;
+
+        game.update(&InputFeed::default().next([0, 0]));
+
+        assert!(game.state == StealthState::GameOver);
+        assert_eq!(game.msg, "Wolf SPOTTED YOU!");
+    }
+
+    #[test]
+    fn walls_and_furniture_hide_santa() {
+        // Right in front, but behind a sofa
+        let mut game = playing();
+        let (wx, wy) = (game.dogs[2].x, game.dogs[2].y);
+        game.player_x = wx + 120.0;
+        game.player_y = wy;
+        game.house.props.push(HouseProp { kind: PropKind::Sofa, x: wx + 70.0, y: wy - 30.0, w: 20.0, h: 60.0 });
+        let mut feed = InputFeed::default();
+        for _ in 0..10 {
+            game.update(&feed.next([0, 0]));
+        }
+        assert_eq!(game.dogs[2].alert, 0.0);
+
+        // Close enough to touch, but through a wall
+        let mut game = playing();
+        game.player_x = wx;
+        game.player_y = wy + 30.0;
+        game.house.walls.push((wx - 60.0, wy + 10.0, 120.0, 10.0));
+        game.update(&feed.next([0, 0]));
+        assert!(game.state == StealthState::Playing);
+        assert_eq!(game.dogs[2].alert, 0.0);
+    }
+
+    #[test]
+    fn dogs_wake_turn_and_settle() {
+        let mut game = playing();
+        game.house.props.clear();
+        let (dx, dy) = (game.dogs[0].x, game.dogs[0].y);
+        // Asleep, it doesn't see Santa right in front of it
+        game.player_x = dx + 80.0;
+        game.player_y = dy;
+        let mut feed = InputFeed::default();
+        game.update(&feed.next([0, 0]));
+        assert!(!game.dogs[0].awake && game.dogs[0].alert == 0.0);
+
+        // Noisy enough and it's up, turning towards Santa's steps
+        game.dogs[0].alert = WAKE_ALERT;
+        game.dogs[0].facing = PI / 2.0;
+        game.update(&feed.next([BTN_UP, 0]));
+        assert!(game.dogs[0].awake && game.dogs[0].looking());
+        assert!(game.dogs[0].facing < PI / 2.0);
+
+        // Once facing Santa it sees them
+        game.dogs[0].facing = 0.0;
+        game.player_y = dy;
+        assert!(game.dogs[0].sees(&game.house, game.player_x, game.player_y));
+        assert!(!game.dogs[1].sees(&game.house, game.player_x, game.player_y));
+
+        // Quiet for long enough and it lies back down
+        game.player_x = dx - 150.0;
+        game.dogs[0].alert = SLEEP_ALERT - 1.0;
+        game.update(&feed.next([0, 0]));
+        assert!(!game.dogs[0].awake);
+    }
+
+    #[test]
+    fn cones_stop_at_walls() {
+        let mut game = playing();
+        game.dogs[2].facing = -PI / 2.0;
+        let (wx, wy) = (game.dogs[2].x, game.dogs[2].y);
+        // Looking up at a wall: every ray ends at or before it
+        let cone = game.dogs[2].view_cone(&game.house);
+        assert_eq!(cone.len(), CONE_RAYS + 1);
+        game.house.walls.push((wx - 200.0, wy - 60.0, 400.0, 10.0));
+        let cone = game.dogs[2].view_cone(&game.house);
+        assert!(cone.iter().all(|p| p.1 >= wy - 50.01));
+        assert!(cone.iter().any(|p| (p.1 - (wy - 50.0)).abs() < 0.01));
+    }
+
+    #[test]
+    fn exit_needs_every_objective() {
+        let mut game = playing();
+        game.gift1_done = true;
+        game.gift2_done = true;
+        game.gift3_done = true;
+        game.stars_collected = 5;
+        game.player_x = game.house.exit.0;
+        game.player_y = game.house.exit.1 + 25.0;
+
+        // Cookie missing: exit stays shut
+        game.update(&InputFeed::default().next([0, 0]));
+        assert!(!game.exit_open);
+        assert!(game.state == StealthState::Playing);
+
+        game.cookie_done = true;
+        game.update(&InputFeed::default().next([0, 0]));
+        assert!(game.exit_open);
+        assert!(game.state == StealthState::Win);
+    }
+
+    #[test]
+    fn objectives_are_where_the_house_put_them() {
+        let mut game = playing();
+        assert_eq!((game.player_x, game.player_y), game.house.spawn);
+        let mut feed = InputFeed::default();
+        for (x, y) in [game.house.tree, game.house.cookie] {
+            game.player_x = x;
+            game.player_y = y;
+            game.update(&feed.next([BTN_A, 0]));
+            game.update(&feed.next([0, 0]));
+        }
+        assert!(game.gift3_done && game.cookie_done);
+        assert!(!game.gift1_done && !game.gift2_done);
+
+        // The run plays the house the menu shows; the next run gets a new one
+        let mut game = StealthGame::new(1);
+        let first = game.house.seed;
+        game.update(&feed.next([BTN_A, 0]));
+        assert!(game.state == StealthState::Playing);
+        assert_eq!(game.house.seed, first);
+        game.state = StealthState::GameOver;
+        game.update(&feed.next([0, 0]));
+        game.update(&feed.next([BTN_A, 0]));
+        assert!(game.state == StealthState::Menu);
+        assert_ne!(game.house.seed, first);
+        assert_eq!(game.rng.seed, game.house.seed);
     }
 }
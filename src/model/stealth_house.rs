@@ -0,0 +1,534 @@
+use crate::model::rng::Rng;
+
+// Santa's collision radius
+pub const PLAYER_R: f32 = 15.0;
+const WALL: f32 = 20.0;
+// Width of the gap a door leaves in a wall
+const DOOR: f32 = 90.0;
+// Rooms per row; there are always 3 rows
+const MIN_COLS: usize = 3;
+const MAX_COLS: usize = 4;
+const ROWS: usize = 3;
+// Doors added on top of the ones every room needs, so there's more than one way round
+const EXTRA_DOORS: usize = 2;
+const STARS: usize = 5;
+// Objectives keep this far inside their room's walls
+const MARGIN: f32 = 40.0;
+// Grid step of the reachability check
+const STEP: f32 = 10.0;
+// Layouts tried before falling back to the one built from FALLBACK_SEED
+const MAX_BUILDS: usize = 10;
+const FALLBACK_SEED: u32 = 0;
+// Furniture keeps this far from anything Santa has to walk to
+const PROP_CLEARANCE: f32 = 50.0;
+
+// Names for the rooms with nothing in particular in them
+const SPARE_ROOMS: [(&str, u32); 7] = [
+    ("Library", 0x263238FF),
+    ("Dining", 0x4E342EFF),
+    ("Study", 0x33302AFF),
+    ("Den", 0x3E3A36FF),
+    ("Playroom", 0x4A3B52FF),
+    ("Pantry", 0x37474FFF),
+    ("Guest Bed", 0x3E2723FF),
+];
+
+#[turbo::serialize]
+pub struct HouseRoom {
+    pub name: String,
+    // Floor inside the walls
+    pub x: f32,
+    pub y: f32,
+    pub w: f32,
+    pub h: f32,
+    pub color: u32,
+}
+
+impl HouseRoom {
+    pub fn center(&self) -> (f32, f32) {
+        (self.x + self.w / 2.0, self.y + self.h / 2.0)
+    }
+}
+
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub enum PropKind {
+    Bed,
+    Table,
+    Tree,
+    Sofa,
+    Bookcase,
+    Chest,
+}
+
+// Furniture that can go in any room, and its size
+const SPARE_PROPS: [(PropKind, f32, f32); 3] = [
+    (PropKind::Sofa, 70.0, 26.0),
+    (PropKind::Bookcase, 24.0, 70.0),
+    (PropKind::Chest, 36.0, 30.0),
+];
+
+// Furniture: pets can't see through it, but Santa can get round (or under) it
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub struct HouseProp {
+    pub kind: PropKind,
+    pub x: f32,
+    pub y: f32,
+    pub w: f32,
+    pub h: f32,
+}
+
+impl HouseProp {
+    fn rect(&self) -> (f32, f32, f32, f32) {
+        (self.x, self.y, self.w, self.h)
+    }
+}
+
+// One house for Silent Santa, built from a seed: a grid of rooms joined by
+// doors, with everything placed by rule:
+// - the hall is on the top row, with the exit in the outside wall above it
+// - the tree and the wolf's patrol are in the room furthest from the hall
+// - the kitchen (cookie) is on the bottom row
+// - the two dogs sleep in two of the other rooms
+// - stars are scattered one to a room, away from the hall and the wolf
+// - the bed, table and tree are furniture, and other rooms get a piece each
+#[turbo::serialize]
+pub struct StealthHouse {
+    pub seed: u32,
+    pub width: f32,
+    pub height: f32,
+    pub rooms: Vec<HouseRoom>,
+    // x, y, w, h
+    pub walls: Vec<(f32, f32, f32, f32)>,
+    pub spawn: (f32, f32),
+    // In the top wall; open once every objective is done
+    pub exit: (f32, f32),
+    // Where Dog 1 and Dog 2 sleep (a gift goes by each)
+    pub dog_beds: [(f32, f32); 2],
+    // The wolf walks y = .1 between x = .2 and .3, starting at .0
+    pub wolf: (f32, f32, f32, f32),
+    pub tree: (f32, f32),
+    pub cookie: (f32, f32),
+    pub stars: Vec<(f32, f32)>,
+    pub props: Vec<HouseProp>,
+}
+
+impl StealthHouse {
+    // Same seed, same house. Every room is joined to the hall by doors far
+    // wider than Santa, and objectives keep clear of the walls, so the first
+    // layout should always pass the walk-through check (the tests hold it to
+    // that). Should one fail, it's rebuilt from the same seed's sequence a
+    // few times, then the tested fallback layout is used instead of hanging.
+    pub fn generate(seed: u32) -> Self {
+        let mut rng = Rng::new(seed);
+        for _ in 0..MAX_BUILDS {
+            let house = Self::build(seed, &mut rng);
+            if house.objectives_reachable() {
+                return house;
+            }
+        }
+        Self::build(seed, &mut Rng::new(FALLBACK_SEED))
+    }
+
+    fn build(seed: u32, rng: &mut Rng) -> Self {
+        let cols = MIN_COLS + rng.u32() as usize % (MAX_COLS - MIN_COLS + 1);
+        let cell = |c: usize, r: usize| r * cols + c;
+
+        // Grid lines, each with a wall on it; sizes step in tens
+        let mut xs = vec![0.0];
+        for _ in 0..cols {
+            xs.push(xs[xs.len() - 1] + 240.0 + (rng.u32() % 9) as f32 * 10.0);
+        }
+        let mut ys = vec![0.0];
+        for _ in 0..ROWS {
+            ys.push(ys[ys.len() - 1] + 220.0 + (rng.u32() % 9) as f32 * 10.0);
+        }
+
+        // Doors: a random spanning tree (every room reachable), plus a few loops
+        let mut doors = vec![];
+        let mut seen = vec![false; cols * ROWS];
+        let hall = cell(rng.u32() as usize % cols, 0);
+        let mut stack = vec![hall];
+        seen[hall] = true;
+        while let Some(&at) = stack.last() {
+            let next: Vec<usize> = neighbors(at, cols).into_iter().filter(|&n| !seen[n]).collect();
+            if next.is_empty() {
+                stack.pop();
+                continue;
+            }
+            let n = next[rng.u32() as usize % next.len()];
+            seen[n] = true;
+            doors.push((at.min(n), at.max(n)));
+            stack.push(n);
+        }
+        let mut spare: Vec<(usize, usize)> = (0..cols * ROWS)
+            .flat_map(|a| neighbors(a, cols).into_iter().filter(move |&b| a < b).map(move |b| (a, b)))
+            .filter(|pair| !doors.contains(pair))
+            .collect();
+        for _ in 0..EXTRA_DOORS.min(spare.len()) {
+            doors.push(spare.swap_remove(rng.u32() as usize % spare.len()));
+        }
+
+        // Walls along every grid line, broken where there's a door
+        let mut walls = vec![];
+        for (i, &x) in xs.iter().enumerate() {
+            for r in 0..ROWS {
+                let door = i > 0 && i < cols && doors.contains(&(cell(i - 1, r), cell(i, r)));
+                wall_with_door(&mut walls, rng, door, ys[r], ys[r + 1] + WALL, |a, len| (x, a, WALL, len));
+            }
+        }
+        for (j, &y) in ys.iter().enumerate() {
+            for c in 0..cols {
+                let door = j > 0 && j < ROWS && doors.contains(&(cell(c, j - 1), cell(c, j)));
+                wall_with_door(&mut walls, rng, door, xs[c], xs[c + 1] + WALL, |a, len| (a, y, len, WALL));
+            }
+        }
+
+        // Doors walked from the hall, for picking the far room
+        let mut steps = vec![usize::MAX; cols * ROWS];
+        steps[hall] = 0;
+        let mut queue = std::collections::VecDeque::from([hall]);
+        while let Some(at) = queue.pop_front() {
+            for &(a, b) in &doors {
+                let other = if a == at { b } else if b == at { a } else { continue };
+                if steps[other] == usize::MAX {
+                    steps[other] = steps[at] + 1;
+                    queue.push_back(other);
+                }
+            }
+        }
+        let furthest = *steps.iter().max().unwrap_or(&0);
+        let far: Vec<usize> = (0..cols * ROWS).filter(|&i| steps[i] == furthest).collect();
+        let living = far[rng.u32() as usize % far.len()];
+        let kitchens: Vec<usize> = (0..cols).map(|c| cell(c, ROWS - 1)).filter(|&i| i != living).collect();
+        let kitchen = kitchens[rng.u32() as usize % kitchens.len()];
+        let mut others: Vec<usize> = (0..cols * ROWS).filter(|i| ![hall, living, kitchen].contains(i)).collect();
+        shuffle(&mut others, rng);
+        let (master, kids) = (others[0], others[1]);
+
+        let mut spares = SPARE_ROOMS.iter();
+        let rooms: Vec<HouseRoom> = (0..cols * ROWS).map(|i| {
+            let (c, r) = (i % cols, i / cols);
+            let (name, color) = match i {
+                _ if i == hall => ("Main Hall", 0x212121FF),
+                _ if i == living => ("Living", 0x5D4037FF),
+                _ if i == kitchen => ("Kitchen", 0x37474FFF),
+                _ if i == master => ("Master Bed", 0x3E2723FF),
+                _ if i == kids => ("Kids Bed", 0x3E2723FF),
+                _ => *spares.next().unwrap_or(&SPARE_ROOMS[0]),
+            };
+            HouseRoom {
+                name: name.to_string(),
+                x: xs[c] + WALL,
+                y: ys[r] + WALL,
+                w: xs[c + 1] - xs[c] - WALL,
+                h: ys[r + 1] - ys[r] - WALL,
+                color,
+            }
+        }).collect();
+
+        // Things in rooms
+        let hall_room = &rooms[hall];
+        let exit = (hall_room.center().0, WALL);
+        let spawn = hall_room.center();
+        let lr = &rooms[living];
+        let wolf_y = lr.y + lr.h * 0.35;
+        let wolf = (lr.x + lr.w / 2.0, wolf_y, lr.x + MARGIN, lr.x + lr.w - MARGIN);
+        let tree = (lr.x + lr.w * 0.7, lr.y + lr.h * 0.7);
+        let cookie = rooms[kitchen].center();
+        let dog_beds = [rooms[master].center(), rooms[kids].center()];
+
+        // Stars one to a room, clear of the sleeping dogs and the cookie
+        let mut star_rooms: Vec<usize> = (0..cols * ROWS).filter(|i| ![hall, living].contains(i)).collect();
+        shuffle(&mut star_rooms, rng);
+        let avoid = [dog_beds[0], dog_beds[1], cookie];
+        let stars = star_rooms.iter().take(STARS).map(|&i| {
+            let room = &rooms[i];
+            let mut spot = room.center();
+            for _ in 0..20 {
+                spot = (
+                    room.x + MARGIN + rng.f32() * (room.w - 2.0 * MARGIN),
+                    room.y + MARGIN + rng.f32() * (room.h - 2.0 * MARGIN),
+                );
+                if avoid.iter().all(|a| (a.0 - spot.0).hypot(a.1 - spot.1) > 60.0) {
+                    break;
+                }
+            }
+            (spot.0.round(), spot.1.round())
+        }).collect::<Vec<(f32, f32)>>();
+
+        // Furniture: the bed Dog 1 sleeps by, the kitchen table, the tree...
+        let bed = HouseProp { kind: PropKind::Bed, x: dog_beds[0].0 - 25.0, y: dog_beds[0].1 - 75.0, w: 80.0, h: 100.0 };
+        let table = HouseProp { kind: PropKind::Table, x: cookie.0 - 30.0, y: cookie.1, w: 60.0, h: 30.0 };
+        let tree_prop = HouseProp { kind: PropKind::Tree, x: tree.0 - 20.0, y: tree.1 - 20.0, w: 40.0, h: 50.0 };
+        let mut props = vec![bed, table, tree_prop];
+        // ...and a piece in each other room, clear of every objective and the way out
+        let mut keep_clear = vec![spawn, (exit.0, exit.1 + 25.0), dog_beds[1], (exit.0, hall_room.y + MARGIN)];
+        keep_clear.extend(&stars);
+        for (i, room) in rooms.iter().enumerate() {
+            if [living, kitchen, master].contains(&i) {
+                continue;
+            }
+            let (kind, w, h) = SPARE_PROPS[rng.u32() as usize % SPARE_PROPS.len()];
+            for _ in 0..20 {
+                let x = (room.x + MARGIN + rng.f32() * (room.w - 2.0 * MARGIN - w)).round();
+                let y = (room.y + MARGIN + rng.f32() * (room.h - 2.0 * MARGIN - h)).round();
+                if keep_clear.iter().all(|p| !rect_circle_hit(x, y, w, h, p.0, p.1, PROP_CLEARANCE)) {
+                    props.push(HouseProp { kind, x, y, w, h });
+                    break;
+                }
+            }
+        }
+
+        Self {
+            seed,
+            width: xs[cols] + WALL,
+            height: ys[ROWS] + WALL,
+            rooms,
+            walls,
+            spawn,
+            exit,
+            dog_beds,
+            wolf,
+            tree,
+            cookie,
+            stars,
+            props,
+        }
+    }
+
+    // A circle of radius `r` at x, y would overlap a wall
+    pub fn blocked(&self, x: f32, y: f32, r: f32) -> bool {
+        self.walls.iter().any(|w| rect_circle_hit(w.0, w.1, w.2, w.3, x, y, r))
+    }
+
+    // How far a ray from `from` at `angle` (radians) gets, up to `max`, before
+    // a wall or piece of furniture stops it. Furniture `from` is inside doesn't
+    // count, so a pet under the bed still sees out.
+    pub fn ray_length(&self, from: (f32, f32), angle: f32, max: f32) -> f32 {
+        let dir = (angle.cos(), angle.sin());
+        self.walls.iter().copied()
+            .chain(self.props.iter().map(HouseProp::rect))
+            .filter(|r| !(from.0 > r.0 && from.0 < r.0 + r.2 && from.1 > r.1 && from.1 < r.1 + r.3))
+            .filter_map(|r| ray_rect(from, dir, r))
+            .fold(max, f32::min)
+    }
+
+    // Nothing in the way between the two spots
+    pub fn line_of_sight(&self, from: (f32, f32), to: (f32, f32)) -> bool {
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let len = dx.hypot(dy);
+        len == 0.0 || self.ray_length(from, dy.atan2(dx), len) >= len
+    }
+
+    // Every spot Santa has to get to: the gift spots, the cookie, each star
+    // and the exit, all walkable from the spawn
+    pub fn objectives_reachable(&self) -> bool {
+        let mut targets = vec![self.dog_beds[0], self.dog_beds[1], self.tree, self.cookie];
+        targets.extend(&self.stars);
+        // Close enough to the exit to leave
+        targets.push((self.exit.0, self.exit.1 + 25.0));
+
+        // Flood fill over a grid of spots where Santa fits
+        let (cols, rows) = ((self.width / STEP) as usize + 1, (self.height / STEP) as usize + 1);
+        let node = |p: (f32, f32)| ((p.0 / STEP).round() as usize, (p.1 / STEP).round() as usize);
+        let free = |(i, j): (usize, usize)| i < cols && j < rows && !self.blocked(i as f32 * STEP, j as f32 * STEP, PLAYER_R);
+        let start = node(self.spawn);
+        if !free(start) {
+            return false;
+        }
+        let mut reached = vec![false; cols * rows];
+        reached[start.1 * cols + start.0] = true;
+        let mut stack = vec![start];
+        while let Some((i, j)) = stack.pop() {
+            let around = [(i.wrapping_sub(1), j), (i + 1, j), (i, j.wrapping_sub(1)), (i, j + 1)];
+            for n in around {
+                if free(n) && !reached[n.1 * cols + n.0] {
+                    reached[n.1 * cols + n.0] = true;
+                    stack.push(n);
+                }
+            }
+        }
+        targets.into_iter().map(node).all(|(i, j)| i < cols && j < rows && reached[j * cols + i])
+    }
+}
+
+// Grid cells sharing a wall with `at`
+fn neighbors(at: usize, cols: usize) -> Vec<usize> {
+    let (c, r) = (at % cols, at / cols);
+    let mut out = vec![];
+    if c > 0 { out.push(at - 1); }
+    if c + 1 < cols { out.push(at + 1); }
+    if r > 0 { out.push(at - cols); }
+    if r + 1 < ROWS { out.push(at + cols); }
+    out
+}
+
+// A wall from `from` to `to` along its length, with a door somewhere in the
+// middle if there is one; `rect` turns a start and length into x, y, w, h
+fn wall_with_door(
+    walls: &mut Vec<(f32, f32, f32, f32)>,
+    rng: &mut Rng,
+    door: bool,
+    from: f32,
+    to: f32,
+    rect: impl Fn(f32, f32) -> (f32, f32, f32, f32),
+) {
+    if !door {
+        walls.push(rect(from, to - from));
+        return;
+    }
+    // Clear of the corners at either end
+    let lo = from + 2.0 * WALL;
+    let hi = to - 2.0 * WALL - DOOR;
+    let gap = (lo + rng.f32() * (hi - lo)).round();
+    walls.push(rect(from, gap - from));
+    walls.push(rect(gap + DOOR, to - gap - DOOR));
+}
+
+fn shuffle<T>(items: &mut [T], rng: &mut Rng) {
+    for i in (1..items.len()).rev() {
+        items.swap(i, rng.u32() as usize % (i + 1));
+    }
+}
+
+fn rect_circle_hit(rx: f32, ry: f32, rw: f32, rh: f32, cx: f32, cy: f32, cr: f32) -> bool {
+    let tx = cx.clamp(rx, rx + rw);
+    let ty = cy.clamp(ry, ry + rh);
+    let dx = cx - tx;
+    let dy = cy - ty;
+    (dx*dx + dy*dy) < (cr*cr)
+}
+
+// Distance along a ray (unit `dir`) to where it enters the box, if it does
+fn ray_rect(from: (f32, f32), dir: (f32, f32), rect: (f32, f32, f32, f32)) -> Option<f32> {
+    let mut t_enter = 0.0f32;
+    let mut t_exit = f32::INFINITY;
+    for (p, d, lo, hi) in [(from.0, dir.0, rect.0, rect.0 + rect.2), (from.1, dir.1, rect.1, rect.1 + rect.3)] {
+        if d.abs() < 1e-6 {
+            if p < lo || p > hi {
+                return None;
+            }
+            continue;
+        }
+        let (t0, t1) = ((lo - p) / d, (hi - p) / d);
+        t_enter = t_enter.max(t0.min(t1));
+        t_exit = t_exit.min(t0.max(t1));
+    }
+    (t_enter <= t_exit).then_some(t_enter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The room a spot is in
+    fn room_of(house: &StealthHouse, p: (f32, f32)) -> usize {
+        house.rooms.iter()
+            .position(|r| p.0 >= r.x && p.0 <= r.x + r.w && p.1 >= r.y && p.1 <= r.y + r.h)
+            .unwrap()
+    }
+
+    #[test]
+    fn same_seed_same_house() {
+        let a = StealthHouse::generate(42);
+        let b = StealthHouse::generate(42);
+        assert!(a.walls == b.walls && a.stars == b.stars && a.tree == b.tree);
+        let c = StealthHouse::generate(43);
+        assert!(a.walls != c.walls);
+    }
+
+    #[test]
+    fn every_objective_is_reachable() {
+        for seed in 0..25 {
+            let house = StealthHouse::generate(seed);
+            assert!(house.objectives_reachable(), "seed {}", seed);
+            // Without needing a rebuild
+            assert!(house.walls == StealthHouse::build(seed, &mut Rng::new(seed)).walls, "seed {}", seed);
+            assert!(!house.blocked(house.spawn.0, house.spawn.1, PLAYER_R));
+            assert_eq!(house.stars.len(), STARS);
+        }
+    }
+
+    #[test]
+    fn rooms_follow_the_rules() {
+        for seed in 0..25 {
+            let house = StealthHouse::generate(seed);
+            let named = |name: &str| house.rooms.iter().position(|r| r.name == name).unwrap();
+            let (hall, living, kitchen) = (named("Main Hall"), named("Living"), named("Kitchen"));
+            let beds = [room_of(&house, house.dog_beds[0]), room_of(&house, house.dog_beds[1])];
+
+            assert_eq!(room_of(&house, house.spawn), hall);
+            assert!(house.rooms[hall].y == WALL && (house.exit.0 - house.rooms[hall].center().0).abs() < 1.0);
+            assert_eq!(room_of(&house, house.tree), living);
+            assert_eq!(room_of(&house, (house.wolf.0, house.wolf.1)), living);
+            assert_eq!(room_of(&house, house.cookie), kitchen);
+            assert!(house.rooms[kitchen].y + house.rooms[kitchen].h == house.height - WALL);
+            let mut used = vec![hall, living, kitchen, beds[0], beds[1]];
+            used.sort();
+            used.dedup();
+            assert_eq!(used.len(), 5, "seed {}", seed);
+
+            // Stars one to a room, never in the hall or with the wolf
+            let mut star_rooms: Vec<usize> = house.stars.iter().map(|s| room_of(&house, *s)).collect();
+            assert!(star_rooms.iter().all(|r| *r != hall && *r != living));
+            star_rooms.sort();
+            star_rooms.dedup();
+            assert_eq!(star_rooms.len(), STARS);
+        }
+    }
+
+    #[test]
+    fn the_fallback_layout_is_reachable() {
+        let house = StealthHouse::build(99, &mut Rng::new(FALLBACK_SEED));
+        assert!(house.objectives_reachable());
+        assert_eq!(house.seed, 99);
+    }
+
+    #[test]
+    fn a_walled_in_objective_is_caught() {
+        let mut house = StealthHouse::generate(7);
+        let (x, y) = house.cookie;
+        house.walls.push((x - 50.0, y - 50.0, 100.0, 10.0));
+        house.walls.push((x - 50.0, y + 40.0, 100.0, 10.0));
+        house.walls.push((x - 50.0, y - 50.0, 10.0, 100.0));
+        house.walls.push((x + 40.0, y - 50.0, 10.0, 100.0));
+        assert!(!house.objectives_reachable());
+    }
+
+    #[test]
+    fn walls_and_furniture_block_sight() {
+        let mut house = StealthHouse::generate(3);
+        let hall = house.rooms.iter().find(|r| r.name == "Main Hall").unwrap().clone();
+        house.props.clear();
+        let (x, y) = (hall.x + 60.0, hall.y + 60.0);
+
+        // Across the room is clear; up through the outside wall isn't
+        assert!(house.line_of_sight((x, y), (x + 100.0, y)));
+        assert!(!house.line_of_sight((x, y), (x, y - 100.0)));
+        assert!((house.ray_length((x, y), -std::f32::consts::FRAC_PI_2, 500.0) - (y - hall.y)).abs() < 0.01);
+        assert_eq!(house.ray_length((x, y), 0.0, 50.0), 50.0);
+
+        // A sofa in between hides the far side, but not from someone sat on it
+        house.props.push(HouseProp { kind: PropKind::Sofa, x: x + 40.0, y: y - 20.0, w: 20.0, h: 40.0 });
+        assert!(!house.line_of_sight((x, y), (x + 100.0, y)));
+        assert!(house.line_of_sight((x + 50.0, y), (x + 100.0, y)));
+        // Or from anywhere it isn't in the way of
+        assert!(house.line_of_sight((x, y), (x, y + 80.0)));
+    }
+
+    #[test]
+    fn furniture_stays_clear_of_objectives() {
+        for seed in 0..25 {
+            let house = StealthHouse::generate(seed);
+            let kinds: Vec<PropKind> = house.props.iter().map(|p| p.kind).collect();
+            assert_eq!(&kinds[..3], &[PropKind::Bed, PropKind::Table, PropKind::Tree]);
+            for p in &house.props[3..] {
+                for s in house.stars.iter().chain([&house.spawn, &house.dog_beds[1]]) {
+                    assert!(!rect_circle_hit(p.x, p.y, p.w, p.h, s.0, s.1, PROP_CLEARANCE), "seed {}", seed);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use turbo::*;
+use turbo::borsh::{BorshDeserialize, BorshSerialize};
+use crate::model::records::{RecordBook, RecordKind};
+use crate::model::replay::{Replay, MAX_REPLAYS};
+use crate::model::settings::Settings;
+use crate::model::arena::{Arena, MAX_ARENAS};
+
+// Each subsystem is saved as its own section with its own version. Bump a
+// section's version when its type changes shape: only that section goes back
+// to its defaults, and the rest of the save (the records above all) is kept.
+const RECORDS_VERSION: u32 = 1;
+const REPLAYS_VERSION: u32 = 2;
+const SETTINGS_VERSION: u32 = 1;
+const ARENAS_VERSION: u32 = 2;
+const BREAKER_VERSION: u32 = 1;
+
+// One subsystem as stored: its borsh bytes, tagged with what they are
+#[turbo::serialize]
+struct SaveSection {
+    name: String,
+    version: u32,
+    data: Vec<u8>,
+}
+
+impl SaveSection {
+    fn write<T: BorshSerialize>(name: &str, version: u32, value: &T) -> Self {
+        Self { name: name.to_string(), version, data: borsh::to_vec(value).unwrap_or_default() }
+    }
+}
+
+// The section called `name`, if it's there, at `version` and readable
+fn read_section<T: BorshDeserialize>(sections: &[SaveSection], name: &str, version: u32) -> Option<T> {
+    let section = sections.iter().find(|s| s.name == name && s.version == version)?;
+    borsh::from_slice(&section.data).ok()
+}
+
+// Turbo only has a single local save slot, so every persistent
+// subsystem (records, replays, settings, ...) lives together in this struct.
+#[turbo::serialize]
+pub struct SaveData {
+    pub records: RecordBook,
+    // Newest last
+    pub replays: Vec<Replay>,
+    pub settings: Settings,
+    // Multiplayer arenas from the editor
+    pub arenas: Vec<Arena>,
+    // Santa Breaker pack levels that can be picked to start from
+    pub breaker_unlocked: u32,
+}
+
+impl Default for SaveData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SaveData {
+    pub fn new() -> Self {
+        Self {
+            records: RecordBook::new(),
+            replays: vec![],
+            settings: Settings::new(),
+            arenas: vec![],
+            breaker_unlocked: 1,
+        }
+    }
+
+    pub fn load() -> Self {
+        Self::from_bytes(&local::load().unwrap_or_else(|_| vec![]))
+    }
+
+    pub fn save(&self) {
+        if local::save(&self.to_bytes()).is_err() {
+            log!("error saving");
+        }
+    }
+
+    // Sections that are missing, from another version or unreadable start
+    // over from their defaults; the others load as saved
+    fn from_bytes(data: &[u8]) -> Self {
+        let sections: Vec<SaveSection> = borsh::from_slice(data).unwrap_or_default();
+        Self {
+            records: read_section(&sections, "records", RECORDS_VERSION).unwrap_or_else(RecordBook::new),
+            replays: read_section(&sections, "replays", REPLAYS_VERSION).unwrap_or_default(),
+            settings: read_section(&sections, "settings", SETTINGS_VERSION).unwrap_or_else(Settings::new),
+            arenas: read_section::<Vec<String>>(&sections, "arenas", ARENAS_VERSION)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|text| Arena::from_json(text))
+                .take(MAX_ARENAS)
+                .collect(),
+            breaker_unlocked: read_section(&sections, "breaker", BREAKER_VERSION).unwrap_or(1),
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let sections = vec![
+            SaveSection::write("records", RECORDS_VERSION, &self.records),
+            SaveSection::write("replays", REPLAYS_VERSION, &self.replays),
+            SaveSection::write("settings", SETTINGS_VERSION, &self.settings),
+            // As JSON text, the same as arenas exported or kept in replays
+            SaveSection::write("arenas", ARENAS_VERSION, &self.arenas.iter().map(Arena::to_json).collect::<Vec<_>>()),
+            SaveSection::write("breaker", BREAKER_VERSION, &self.breaker_unlocked),
+        ];
+        borsh::to_vec(&sections).unwrap_or_default()
+    }
+
+    // Submits a finished run and persists immediately if it placed
+    pub fn submit_record(&mut self, kind: RecordKind, name: &str, value: i32) -> Option<usize> {
+        let rank = self.records.submit(kind, name, value);
+        if rank.is_some() {
+            self.save();
+        }
+        rank
+    }
+
+    // Keeps the latest MAX_REPLAYS sessions; empty sessions are ignored
+    pub fn add_replay(&mut self, replay: Replay) {
+        if replay.runs.is_empty() {
+            return;
+        }
+        self.replays.push(replay);
+        if self.replays.len() > MAX_REPLAYS {
+            let extra = self.replays.len() - MAX_REPLAYS;
+            self.replays.drain(0..extra);
+        }
+        self.save();
+    }
+
+    // Overwrites the arena in `slot`, or adds a new one while there's room.
+    // The slot it went in, or None (and nothing saved) when every slot is full.
+    pub fn store_arena(&mut self, slot: Option<usize>, arena: Arena) -> Option<usize> {
+        let slot = match slot {
+            Some(i) if i < self.arenas.len() => {
+                self.arenas[i] = arena;
+                i
+            },
+            _ if self.arenas.len() < MAX_ARENAS => {
+                self.arenas.push(arena);
+                self.arenas.len() - 1
+            },
+            _ => return None,
+        };
+        self.save();
+        Some(slot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_every_section() {
+        let mut save = SaveData::new();
+        save.records.submit(RecordKind::RooftopRun, "ANN", 1200);
+        save.breaker_unlocked = 7;
+        save.arenas.push(Arena::new("Yard".to_string()));
+
+        let loaded = SaveData::from_bytes(&save.to_bytes());
+        assert_eq!(loaded.records.table(RecordKind::RooftopRun).unwrap().entries[0].value, 1200);
+        assert_eq!(loaded.breaker_unlocked, 7);
+        assert!(loaded.arenas == save.arenas);
+    }
+
+    #[test]
+    fn a_changed_section_keeps_the_rest() {
+        let mut save = SaveData::new();
+        save.records.submit(RecordKind::RooftopRun, "ANN", 1200);
+        save.breaker_unlocked = 7;
+        let mut sections: Vec<SaveSection> = borsh::from_slice(&save.to_bytes()).unwrap();
+        // Settings saved by some other version, arenas garbled
+        sections.iter_mut().find(|s| s.name == "settings").unwrap().version = SETTINGS_VERSION + 1;
+        sections.iter_mut().find(|s| s.name == "arenas").unwrap().data = vec![9, 9];
+
+        let loaded = SaveData::from_bytes(&borsh::to_vec(&sections).unwrap());
+        assert_eq!(loaded.records.table(RecordKind::RooftopRun).unwrap().entries.len(), 1);
+        assert_eq!(loaded.breaker_unlocked, 7);
+        assert!(loaded.settings == Settings::new() && loaded.arenas.is_empty());
+
+        // Arenas that no longer pass the JSON checks are dropped
+        let mut save = SaveData::new();
+        save.arenas.push(Arena::new("Fine".to_string()));
+        let mut broken = Arena::new("Broken".to_string());
+        broken.rules.level = 9;
+        save.arenas.push(broken);
+        let loaded = SaveData::from_bytes(&save.to_bytes());
+        assert_eq!(loaded.arenas.len(), 1);
+        assert_eq!(loaded.arenas[0].name, "Fine");
+
+        // Nothing readable at all: a fresh save
+        assert_eq!(SaveData::from_bytes(&[1, 2, 3]).breaker_unlocked, 1);
+    }
+
+    #[test]
+    fn arenas_go_in_the_slot_asked_for_or_a_free_one() {
+        let mut save = SaveData::new();
+        for i in 0..MAX_ARENAS {
+            assert_eq!(save.store_arena(None, Arena::new(format!("A{}", i))), Some(i));
+        }
+        assert_eq!(save.store_arena(None, Arena::new("Extra".to_string())), None);
+        assert_eq!(save.store_arena(Some(1), Arena::new("Redo".to_string())), Some(1));
+        assert_eq!(save.arenas.len(), MAX_ARENAS);
+        assert_eq!(save.arenas[1].name, "Redo");
+    }
+}
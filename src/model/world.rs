@@ -1,4 +1,15 @@
 use turbo::*;
+use crate::model::rng::Rng;
+
+// The world is generated one screen-wide chunk at a time, ahead of the camera
+pub const CHUNK_W: f32 = 512.0;
+// Chunks per level; the last one ends with the finish flag
+pub const LEVEL_CHUNKS: u32 = 6;
+// Widest gap between roofs (a running jump clears about 140px)
+const MAX_GAP: f32 = 88.0;
+const MIN_ROOF_W: f32 = 96.0;
+// Roofs reach below the screen so they read as buildings
+const ROOF_BOTTOM: f32 = 320.0;
 
 #[turbo::serialize]
 #[derive(Copy, PartialEq)]
@@ -7,6 +18,8 @@ pub struct Platform {
     pub y: f32,
     pub width: f32,
     pub height: f32,
+    // Building roof (safe to respawn on) rather than a floating ledge or chimney
+    pub roof: bool,
 }
 
 #[turbo::serialize]
@@ -17,91 +30,258 @@ pub struct Gift {
     pub collected: bool,
 }
 
+// Solid, and a drop-off point for carried gifts
 #[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub struct Chimney {
+    pub x: f32,
+    pub y: f32,
+    pub delivered: bool,
+}
 
+impl Chimney {
+    pub const W: f32 = 20.0;
+    pub const H: f32 = 24.0;
+}
+
+// Icicle spikes on a roof
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub struct Hazard {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+}
+
+impl Hazard {
+    pub const H: f32 = 8.0;
+}
+
+// Finish pole at the end of each level
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub struct Flag {
+    pub x: f32,
+    pub y: f32,
+    pub level: u32,
+}
+
+#[turbo::serialize]
 pub struct World {
     pub platforms: Vec<Platform>,
     pub gifts: Vec<Gift>,
+    pub chimneys: Vec<Chimney>,
+    pub hazards: Vec<Hazard>,
+    pub flags: Vec<Flag>,
     pub camera_x: f32,
+    // Chunks generated so far; everything left of the camera is dropped
+    pub chunks: u32,
+    // Height of the last roof generated, so the next one stays in jumping range
+    roof_y: f32,
 }
 
 impl World {
-    pub fn new() -> Self {
-        let mut platforms = vec![];
-        let mut gifts = vec![];
-
-        // Ground
-        for i in 0..20 {
-            platforms.push(Platform {
-                x: i as f32 * 64.0,
-                y: 200.0,
-                width: 64.0,
-                height: 32.0,
-            });
-        }
-
-        // Random platforms and gifts
-        let mut rng = random::u32();
-        for i in 1..20 {
-            if rng % 3 == 0 {
-                let px = i as f32 * 100.0 + (rng % 50) as f32;
-                let py = 150.0 - (rng % 80) as f32;
-                platforms.push(Platform {
-                    x: px,
-                    y: py,
-                    width: 48.0,
-                    height: 16.0,
-                });
-                
-                // Add gift on platform
-                gifts.push(Gift {
-                    x: px + 16.0,
-                    y: py - 16.0,
-                    collected: false,
-                });
-            }
-            rng = (rng * 1103515245 + 12345) % 2147483648;
-        }
-
-        Self {
-            platforms,
-            gifts,
+    pub fn new(rng: &mut Rng) -> Self {
+        let mut world = Self {
+            platforms: vec![],
+            gifts: vec![],
+            chimneys: vec![],
+            hazards: vec![],
+            flags: vec![],
             camera_x: 0.0,
-        }
+            chunks: 0,
+            roof_y: 200.0,
+        };
+        world.generate_ahead(rng);
+        world
+    }
+
+    // x of the flag that ends `level` (1-based)
+    pub fn finish_x(level: u32) -> f32 {
+        (level * LEVEL_CHUNKS) as f32 * CHUNK_W - 48.0
     }
 
-    pub fn update(&mut self, player_x: f32) {
-        // Camera follows player
+    pub fn update(&mut self, player_x: f32, rng: &mut Rng) {
+        // Camera follows player, but never scrolls back
         let target_cam = player_x - 100.0;
         if target_cam > self.camera_x {
             self.camera_x = target_cam;
         }
+
+        self.generate_ahead(rng);
+
+        // Forget what has scrolled off to the left
+        let left = self.camera_x - 64.0;
+        self.platforms.retain(|p| p.x + p.width > left);
+        self.gifts.retain(|g| g.x + 12.0 > left);
+        self.chimneys.retain(|c| c.x + Chimney::W > left);
+        self.hazards.retain(|h| h.x + h.width > left);
+        self.flags.retain(|f| f.x + 16.0 > left);
     }
 
-    pub fn draw(&self) {
-        // Draw sky
-        rect!(w = screen().w(), h = screen().h(), color = 0x87CEEBff);
+    // Keeps at least one full screen generated past the right edge
+    fn generate_ahead(&mut self, rng: &mut Rng) {
+        while (self.chunks as f32) * CHUNK_W < self.camera_x + 512.0 + CHUNK_W {
+            self.generate_chunk(rng);
+        }
+    }
 
-        // HUD
+    fn generate_chunk(&mut self, rng: &mut Rng) {
+        let index = self.chunks;
+        self.chunks += 1;
 
+        let start = index as f32 * CHUNK_W;
+        let end = start + CHUNK_W;
+        let level = index / LEVEL_CHUNKS + 1;
+        let ends_level = index % LEVEL_CHUNKS == LEVEL_CHUNKS - 1;
+        // Gaps widen and hazards get denser with each level
+        let gap_range = 16 + level.min(6) * 8;
+        let hazard_chance = (15 + level * 10).min(60);
+
+        // Chunks after the first open with a gap, since the previous one ended on a roof
+        let mut x = start;
+        if index > 0 {
+            x += Self::gap(rng, gap_range);
+        }
+
+        loop {
+            let remaining = end - x;
+            // The first roof is a safe run-up the width of the screen
+            let mut width = if index == 0 { 320.0 } else { MIN_ROOF_W + (rng.u32() % 129) as f32 };
+            if remaining - width < MIN_ROOF_W + MAX_GAP {
+                width = remaining;
+            }
+
+            let y = self.roof_y;
+            self.platforms.push(Platform { x, y, width, height: ROOF_BOTTOM - y, roof: true });
+            x += width;
+            let last = x >= end;
+
+            if last && ends_level {
+                self.flags.push(Flag { x: end - 48.0, y, level });
+            } else if index > 0 {
+                self.decorate_roof(rng, x - width, y, width, hazard_chance);
+            }
+            if last {
+                break;
+            }
+
+            // Next roof within jumping height of this one
+            let gap = Self::gap(rng, gap_range);
+            let rise = (rng.u32() % 81) as f32 - 40.0;
+            self.roof_y = (y + rise).clamp(150.0, 230.0);
+
+            // Sometimes a ledge with a gift over the gap
+            if rng.u32() % 100 < 35 {
+                let lx = x + gap / 2.0 - 24.0;
+                let ly = y.min(self.roof_y) - 60.0 - (rng.u32() % 30) as f32;
+                self.platforms.push(Platform { x: lx, y: ly, width: 48.0, height: 16.0, roof: false });
+                self.gifts.push(Gift { x: lx + 18.0, y: ly - 14.0, collected: false });
+            }
+            x += gap;
+        }
+    }
+
+    fn gap(rng: &mut Rng, range: u32) -> f32 {
+        (24 + rng.u32() % range).min(MAX_GAP as u32) as f32
+    }
+
+    // Chimney, icicles and a gift on a roof, kept clear of its edges
+    fn decorate_roof(&mut self, rng: &mut Rng, x: f32, y: f32, width: f32, hazard_chance: u32) {
+        let mut chimney_x = None;
+        if width >= 112.0 && rng.u32() % 100 < 45 {
+            let cx = x + 40.0 + (rng.u32() % (width - 80.0 - Chimney::W) as u32) as f32;
+            let cy = y - Chimney::H;
+            self.chimneys.push(Chimney { x: cx, y: cy, delivered: false });
+            self.platforms.push(Platform { x: cx, y: cy, width: Chimney::W, height: Chimney::H, roof: false });
+            chimney_x = Some(cx);
+        }
+
+        if rng.u32() % 100 < hazard_chance {
+            let hw = if rng.u32().is_multiple_of(2) { 16.0 } else { 32.0 };
+            let hx = x + 32.0 + (rng.u32() % (width - 64.0 - hw).max(1.0) as u32) as f32;
+            let clear = chimney_x.is_none_or(|cx| hx + hw + 8.0 < cx || hx > cx + Chimney::W + 8.0);
+            if clear {
+                self.hazards.push(Hazard { x: hx, y: y - Hazard::H, width: hw });
+            }
+        }
+
+        if rng.u32() % 100 < 30 {
+            let gx = x + 16.0 + (rng.u32() % (width - 32.0) as u32) as f32;
+            self.gifts.push(Gift { x: gx, y: y - 30.0, collected: false });
+        }
+    }
+
+    // Top-left for a player `height` tall, on the first roof at or after `from`
+    pub fn respawn_point(&self, from: f32, height: f32) -> (f32, f32) {
+        self.platforms.iter()
+            .filter(|p| p.roof && p.x + p.width > from + 24.0)
+            .min_by(|a, b| a.x.total_cmp(&b.x))
+            .map(|p| (p.x.max(from) + 4.0, p.y - height))
+            .unwrap_or((from, 0.0))
+    }
+
+    pub fn draw(&self) {
+        // Draw night sky
+        rect!(w = screen().w(), h = screen().h(), color = 0x0B1A3AFF);
+        // Moon (slow parallax)
+        let moon_x = 420 - ((self.camera_x * 0.05) as i32 % 600);
+        rect!(x = moon_x, y = 30, w = 28, h = 28, color = 0xFFF9C4FF);
 
         // Draw Platforms
         for plat in &self.platforms {
-            rect!(
-                x = (plat.x - self.camera_x) as i32,
-                y = plat.y as i32,
-                w = plat.width as u32,
-                h = plat.height as u32,
-                color = 0xFFFFFFFF // Snowy white
-            );
-             // Top snow layer
-            rect!(
-                x = (plat.x - self.camera_x) as i32,
-                y = plat.y as i32,
-                w = plat.width as u32,
-                h = 4,
-                color = 0xE0F7FAFF
-            );
+            let px = (plat.x - self.camera_x) as i32;
+            if px > 512 || px + (plat.width as i32) < 0 {
+                continue;
+            }
+            if plat.roof {
+                // Brick building with lit windows
+                rect!(x = px, y = plat.y as i32, w = plat.width as u32, h = plat.height as u32, color = 0x6D3B2FFF);
+                let mut wx = px + 12;
+                while wx + 10 < px + plat.width as i32 - 8 {
+                    let mut wy = plat.y as i32 + 16;
+                    while wy < 288 {
+                        rect!(x = wx, y = wy, w = 10, h = 12, color = 0xFFD54FFF);
+                        wy += 28;
+                    }
+                    wx += 24;
+                }
+            } else {
+                rect!(x = px, y = plat.y as i32, w = plat.width as u32, h = plat.height as u32, color = 0xFFFFFFFF); // Snowy white
+            }
+            // Top snow layer
+            rect!(x = px, y = plat.y as i32, w = plat.width as u32, h = 4, color = 0xE0F7FAFF);
+        }
+
+        // Chimneys (brick, smoking once a gift is down)
+        for c in &self.chimneys {
+            let cx = (c.x - self.camera_x) as i32;
+            rect!(x = cx, y = c.y as i32, w = Chimney::W as u32, h = Chimney::H as u32, color = 0xB23A2EFF);
+            rect!(x = cx - 2, y = c.y as i32, w = Chimney::W as u32 + 4, h = 5, color = 0xE0F7FAFF);
+            if c.delivered {
+                rect!(x = cx + 6, y = c.y as i32 - 10, w = 6, h = 6, color = 0xCCCCCC99);
+                rect!(x = cx + 10, y = c.y as i32 - 20, w = 8, h = 8, color = 0xCCCCCC66);
+            }
+        }
+
+        // Icicles
+        for h in &self.hazards {
+            let hx = (h.x - self.camera_x) as i32;
+            let mut sx = hx;
+            while sx < hx + h.width as i32 {
+                rect!(x = sx + 2, y = h.y as i32, w = 4, h = Hazard::H as u32, color = 0x81D4FAFF);
+                rect!(x = sx + 3, y = h.y as i32 - 2, w = 2, h = 2, color = 0xFFFFFFFF);
+                sx += 8;
+            }
+        }
+
+        // Finish flags
+        for f in &self.flags {
+            let fx = (f.x - self.camera_x) as i32;
+            rect!(x = fx, y = f.y as i32 - 48, w = 3, h = 48, color = 0xDDDDDDFF);
+            rect!(x = fx + 3, y = f.y as i32 - 48, w = 18, h = 12, color = 0x2ECC71FF);
+            let label = format!("{}", f.level);
+            text!(&label, x = fx + 9, y = f.y as i32 - 46, font = "small", color = 0xFFFFFFFF);
         }
 
         // Draw Gifts
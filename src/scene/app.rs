@@ -0,0 +1,160 @@
+use turbo::*;
+use crate::model::*;
+
+// State shared by every scene, owned by GameState next to the scene stack
+#[turbo::serialize]
+pub struct App {
+    pub snow: Vec<SnowFlake>,
+    // Menu snow + source of per-run seeds
+    pub rng: Rng,
+    // Persistent Data
+    pub save_data: SaveData,
+    // Multiplayer setup, kept between matches
+    pub p1_name: String,
+    pub p2_name: String,
+    pub p3_name: String,
+    pub p4_name: String,
+    // 2-4 players, and whether four play as two pairs
+    pub mp_players: u32,
+    pub mp_pairs: bool,
+    pub mp_duration: u32,
+    pub mp_level_selection: u32, // 1-5
+    // Saved arena picked instead of a level (the slot after the last one is "new arena")
+    pub mp_arena: Option<usize>,
+    // One match, best of 3/5 on the chosen level, or levels 1-5 in turn
+    pub mp_series: SeriesKind,
+    // CPU difficulty for player 2, or None for a second human
+    pub mp_rival: Option<RivalLevel>,
+    // What each multiplayer player joined with
+    pub devices: [InputDevice; MAX_PLAYERS],
+    // Devices that have pressed something since launch (Turbo can't list connected pads)
+    pub detected: Vec<InputDevice>,
+    // Player one's physical buttons, this frame and last
+    pub pad_raw: u16,
+    pub pad_prev: u16,
+    shake_timer: u32,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl App {
+    pub fn new() -> Self {
+        let mut rng = Rng::new(random::u32());
+        let mut snow = vec![];
+        for _ in 0..100 {
+            snow.push(SnowFlake::new(&mut rng));
+        }
+
+        Self {
+            snow,
+            rng,
+            save_data: SaveData::load(),
+            p1_name: "PLAYER 1".to_string(),
+            p2_name: "PLAYER 2".to_string(),
+            p3_name: "PLAYER 3".to_string(),
+            p4_name: "PLAYER 4".to_string(),
+            mp_players: 2,
+            mp_pairs: false,
+            mp_duration: 3,
+            mp_level_selection: 1,
+            mp_arena: None,
+            mp_series: SeriesKind::Single,
+            mp_rival: None,
+            devices: InputDevice::DEFAULT,
+            detected: vec![],
+            pad_raw: 0,
+            pad_prev: 0,
+            shake_timer: 0,
+        }
+    }
+
+    // Once per frame, before the scenes update
+    pub fn tick(&mut self) {
+        // Update Snow
+        for flake in self.snow.iter_mut() {
+            flake.update(&mut self.rng);
+        }
+
+        self.pad_prev = self.pad_raw;
+        self.pad_raw = Pad::poll(0);
+
+        for device in InputDevice::ALL {
+            if !self.detected.contains(&device) && device.poll() != 0 {
+                self.detected.push(device);
+            }
+        }
+
+        // Music Loop (only restarts once the track has finished)
+        self.save_data.settings.apply_music("home_music");
+
+        if self.shake_timer > 0 {
+            self.shake_timer -= 1;
+            if self.shake_timer == 0 {
+                camera::remove_shake();
+            }
+        }
+    }
+
+    // Every pad as the games see them, after the player's button mapping.
+    // Replays store these, so a replay plays back the same whatever the mapping is later.
+    pub fn live_bits(&self) -> [u16; MAX_PLAYERS] {
+        let settings = &self.save_data.settings;
+        std::array::from_fn(|i| settings.remap(if i == 0 { self.pad_raw } else { Pad::poll(i) }))
+    }
+
+    // Multiplayer reads the devices the players joined with instead
+    pub fn bits_for(&self, mode: ReplayMode) -> [u16; MAX_PLAYERS] {
+        if mode != ReplayMode::Multiplayer {
+            return self.live_bits();
+        }
+        let settings = &self.save_data.settings;
+        self.devices.map(|device| settings.remap(device.poll()))
+    }
+
+    pub fn mp_arena(&self) -> Option<&Arena> {
+        self.save_data.arenas.get(self.mp_arena?)
+    }
+
+    pub fn mp_name_mut(&mut self, index: usize) -> &mut String {
+        match index {
+            0 => &mut self.p1_name,
+            1 => &mut self.p2_name,
+            2 => &mut self.p3_name,
+            _ => &mut self.p4_name,
+        }
+    }
+
+    // Multiplayer names in player order, as many as are playing
+    pub fn mp_names(&self) -> Vec<String> {
+        [&self.p1_name, &self.p2_name, &self.p3_name, &self.p4_name]
+            .into_iter()
+            .take(self.mp_players as usize)
+            .cloned()
+            .collect()
+    }
+
+    // Player one's mapped pad, for controls that live outside the games (pause, help, leaving)
+    pub fn live_pad(&self) -> Pad {
+        let settings = &self.save_data.settings;
+        Pad::from_bits(settings.remap(self.pad_raw), settings.remap(self.pad_prev))
+    }
+
+    // Screen shake asked for by a game this frame, if the player wants it
+    pub fn request_shake(&mut self, amount: usize) {
+        if amount > 0 && self.save_data.settings.screen_shake {
+            camera::shake(amount);
+            self.shake_timer = 12;
+        }
+    }
+
+    pub fn clear_shake(&mut self) {
+        if self.shake_timer > 0 {
+            self.shake_timer = 0;
+            camera::remove_shake();
+        }
+    }
+}
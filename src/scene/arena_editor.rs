@@ -0,0 +1,307 @@
+use turbo::*;
+use crate::model::*;
+use crate::scene::*;
+
+// What A does at the editor cursor
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub enum EditorTool {
+    House,
+    PowerHouse,
+    Bomb,
+    Wood,
+    Snowman,
+    Tree,
+    SnowPile,
+    River,
+    Bridge,
+    DogCage,
+    Erase,
+    Rules,
+    Name,
+}
+
+impl EditorTool {
+    pub const ALL: [EditorTool; 13] = [
+        EditorTool::House, EditorTool::PowerHouse, EditorTool::Bomb, EditorTool::Wood,
+        EditorTool::Snowman, EditorTool::Tree, EditorTool::SnowPile, EditorTool::River,
+        EditorTool::Bridge, EditorTool::DogCage, EditorTool::Erase, EditorTool::Rules,
+        EditorTool::Name,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EditorTool::House => "HOUSE",
+            EditorTool::PowerHouse => "POWER HOUSE",
+            EditorTool::Bomb => "BOMB",
+            EditorTool::Wood => "WOOD",
+            EditorTool::Snowman => "SNOWMAN",
+            EditorTool::Tree => "TREE",
+            EditorTool::SnowPile => "SNOW PILE",
+            EditorTool::River => "RIVER",
+            EditorTool::Bridge => "BRIDGE",
+            EditorTool::DogCage => "DOG CAGE",
+            EditorTool::Erase => "ERASE",
+            EditorTool::Rules => "RULES",
+            EditorTool::Name => "NAME",
+        }
+    }
+
+    // Rivers and bridges are dragged out between two corners
+    fn is_area(&self) -> bool {
+        matches!(self, EditorTool::River | EditorTool::Bridge)
+    }
+}
+
+// Builds a multiplayer arena on the field itself. The field is drawn by a
+// MultiplayerGame laid out from the arena, so it looks as it will play.
+#[turbo::serialize]
+pub struct ArenaEditorScene {
+    // Save slot being edited, None for a new arena
+    slot: Option<usize>,
+    arena: Arena,
+    preview: MultiplayerGame,
+    tool: EditorTool,
+    cursor: (f32, f32),
+    // First corner of a river or bridge being placed
+    anchor: Option<(f32, f32)>,
+    naming: bool,
+    // Rules panel open (A with the RULES tool), and its highlighted line
+    rules_open: bool,
+    rule_selection: usize,
+}
+
+impl ArenaEditorScene {
+    pub fn new(slot: Option<usize>, arena: Arena) -> Self {
+        // Four players so every team's houses and spawn show
+        let names = (1..=MAX_PLAYERS).map(|i| format!("P{}", i)).collect();
+        let mut preview = MultiplayerGame::with_players(names, false, 3, arena.rules.level, 1);
+        preview.load_arena(arena.clone());
+        Self { slot, arena, preview, tool: EditorTool::House, cursor: (256.0, 144.0), anchor: None, naming: false, rules_open: false, rule_selection: 0 }
+    }
+
+    fn refresh(&mut self) {
+        self.preview.load_arena(self.arena.clone());
+    }
+
+    fn apply_tool(&mut self) {
+        let (x, y) = self.cursor;
+        // Only bridges go on water
+        let blocked = self.arena.in_water(x, y);
+        let arena = &mut self.arena;
+        match self.tool {
+            EditorTool::House => match arena.house_at(x, y) {
+                // Neutral, then each team in turn
+                Some(i) => arena.houses[i].team = (arena.houses[i].team + 1) % (MAX_PLAYERS as u8 + 1),
+                None if !blocked => arena.houses.push(ArenaHouse { x, y, team: 0, power: false }),
+                None => {},
+            },
+            EditorTool::PowerHouse if !blocked => arena.houses.push(ArenaHouse { x, y, team: 0, power: true }),
+            EditorTool::Bomb if !blocked => arena.obstacles.push(ArenaObstacle { x, y, kind: 0 }),
+            EditorTool::Wood if !blocked => arena.obstacles.push(ArenaObstacle { x, y, kind: 1 }),
+            EditorTool::Snowman if !blocked => arena.obstacles.push(ArenaObstacle { x, y, kind: 2 }),
+            EditorTool::Tree if !blocked => arena.decors.push(Decor { x, y, kind: 0 }),
+            EditorTool::SnowPile if !blocked => arena.decors.push(Decor { x, y, kind: 1 }),
+            EditorTool::River | EditorTool::Bridge => match self.anchor.take() {
+                None => self.anchor = Some(self.cursor),
+                Some(anchor) => {
+                    let zone = Zone::between(anchor, self.cursor);
+                    if zone.w < 8.0 || zone.h < 8.0 {
+                        return;
+                    }
+                    if self.tool == EditorTool::River {
+                        arena.water.push(run_off_edges(zone));
+                    } else {
+                        arena.bridges.push(zone);
+                    }
+                },
+            },
+            EditorTool::DogCage if !blocked => arena.cage = (x, y),
+            EditorTool::Erase => {
+                arena.erase_at(x, y);
+            },
+            EditorTool::Rules => self.rules_open = true,
+            EditorTool::Name => self.naming = true,
+            _ => return,
+        }
+        self.refresh();
+    }
+
+    // Up/Down picks a rule, A or Left/Right changes it, B closes the panel
+    fn update_rules(&mut self) {
+        let gp = gamepad::get(0);
+        let count = ArenaRule::ALL.len();
+        if gp.up.just_pressed() {
+            self.rule_selection = (self.rule_selection + count - 1) % count;
+        }
+        if gp.down.just_pressed() {
+            self.rule_selection = (self.rule_selection + 1) % count;
+        }
+        let step = if gp.a.just_pressed() || gp.right.just_pressed() { 1 } else if gp.left.just_pressed() { -1 } else { 0 };
+        if step != 0 {
+            self.arena.change_rule(ArenaRule::ALL[self.rule_selection], step);
+            self.refresh();
+        }
+        if gp.b.just_pressed() {
+            self.rules_open = false;
+        }
+    }
+
+    fn update_name(&mut self) {
+        let gp = gamepad::get(0);
+        let kb = turbo::keyboard::get();
+        let name = &mut self.arena.name;
+        for c in kb.chars() {
+            if (c.is_alphanumeric() || c == ' ') && name.len() < 10 {
+                name.push(c.to_ascii_uppercase());
+            }
+        }
+        if kb.backspace().just_pressed() {
+            name.pop();
+        }
+        if kb.enter().just_pressed() || gp.a.just_pressed() || gp.b.just_pressed() {
+            self.naming = false;
+            *name = name.trim().to_string();
+            if name.is_empty() {
+                *name = "ARENA".to_string();
+            }
+        }
+    }
+}
+
+// Rivers drawn up to the border carry on past it, like the built-in ones
+fn run_off_edges(mut zone: Zone) -> Zone {
+    if zone.x <= 8.0 {
+        zone.w += zone.x + 16.0;
+        zone.x = -16.0;
+    }
+    if zone.y <= 8.0 {
+        zone.h += zone.y + 16.0;
+        zone.y = -16.0;
+    }
+    if zone.x + zone.w >= 504.0 {
+        zone.w = 528.0 - zone.x;
+    }
+    if zone.y + zone.h >= 280.0 {
+        zone.h = 304.0 - zone.y;
+    }
+    zone
+}
+
+impl Scene for ArenaEditorScene {
+    fn update(&mut self, app: &mut App) -> Transition {
+        if self.naming {
+            self.update_name();
+            return Transition::None;
+        }
+        if self.rules_open {
+            self.update_rules();
+            return Transition::None;
+        }
+
+        let gp = gamepad::get(0);
+        let kb = turbo::keyboard::get();
+
+        // Cursor (held D-pad moves it 2px a frame)
+        let speed = 2.0;
+        if gp.left.pressed() { self.cursor.0 -= speed; }
+        if gp.right.pressed() { self.cursor.0 += speed; }
+        if gp.up.pressed() { self.cursor.1 -= speed; }
+        if gp.down.pressed() { self.cursor.1 += speed; }
+        self.cursor = (self.cursor.0.clamp(0.0, 512.0), self.cursor.1.clamp(0.0, 288.0));
+
+        // X: Next tool
+        if gp.x.just_pressed() {
+            let i = EditorTool::ALL.iter().position(|t| *t == self.tool).unwrap_or(0);
+            self.tool = EditorTool::ALL[(i + 1) % EditorTool::ALL.len()];
+            self.anchor = None;
+        }
+
+        if gp.a.just_pressed() {
+            self.apply_tool();
+        }
+
+        // B: Drop a half-placed river/bridge, otherwise erase under the cursor
+        if gp.b.just_pressed() && self.anchor.take().is_none() && self.arena.erase_at(self.cursor.0, self.cursor.1) {
+            self.refresh();
+        }
+
+        // Start saves (and exports the JSON to the log), Esc throws the changes away
+        if gp.start.just_pressed() {
+            log!("{}", self.arena.to_json());
+            if let Some(slot) = app.save_data.store_arena(self.slot, self.arena.clone()) {
+                app.mp_arena = Some(slot);
+            }
+            return Transition::Pop;
+        }
+        if kb.escape().just_pressed() {
+            return Transition::Pop;
+        }
+        Transition::None
+    }
+
+    fn draw(&self, app: &App) {
+        self.preview.draw_field(&app.save_data.settings);
+
+        // Dog cage (only drawn by the game under level 5 rules)
+        let (cx, cy) = (self.arena.cage.0 as i32, self.arena.cage.1 as i32);
+        rect!(x=cx-12, y=cy-12, w=24, h=24, border_size=1, border_color=0x5D4037FF, color=0x00000000);
+
+        // River or bridge being dragged out
+        if let Some(anchor) = self.anchor {
+            let zone = Zone::between(anchor, self.cursor);
+            rect!(x=zone.x as i32, y=zone.y as i32, w=zone.w as u32, h=zone.h as u32, border_size=1, border_color=0xFFFF00FF, color=0xFFFF0044);
+        }
+
+        // Cursor
+        let (x, y) = (self.cursor.0 as i32, self.cursor.1 as i32);
+        rect!(x=x-6, y=y, w=13, h=1, color=0x000000FF);
+        rect!(x=x, y=y-6, w=1, h=13, color=0x000000FF);
+
+        // Tool bar
+        rect!(x=0, y=0, w=512, h=14, color=0x000000CC);
+        let tool = match self.tool {
+            EditorTool::Rules if self.rules_open => "RULES - UP/DOWN: Pick  A/LEFT/RIGHT: Change  B: Done".to_string(),
+            EditorTool::Rules => "TOOL: RULES - A: Open".to_string(),
+            EditorTool::Name if self.naming => format!("NAME: {}_", self.arena.name),
+            EditorTool::Name => format!("TOOL: NAME ({})", self.arena.name),
+            tool if tool.is_area() && self.anchor.is_some() => format!("TOOL: {} - A: Second Corner", tool.name()),
+            tool => format!("TOOL: {}", tool.name()),
+        };
+        text!(&tool, x=4, y=3, font="small", color=0xFFFF00FF);
+        let counts = format!("{} | L{} | {} houses", self.arena.name, self.arena.rules.level, self.arena.houses.len());
+        text!(&counts, x=300, y=3, font="small", color=0xFFFFFFFF);
+
+        rect!(x=0, y=276, w=512, h=12, color=0x000000CC);
+        let help = "A: Place  X: Tool  B: Erase  START: Save  ESC: Discard";
+        text!(help, x=4, y=279, font="small", color=0xAAAAAAFF);
+
+        // Rules panel
+        if self.rules_open {
+            let (px, py) = (146, 60);
+            rect!(x=px, y=py, w=220, h=24 + ArenaRule::ALL.len() as u32 * 14, color=0x000000EE, border_size=1, border_color=0xFFFF00FF);
+            text!("RULES", x=px + 8, y=py + 6, font="medium", color=0xFFFF00FF);
+            for (i, rule) in ArenaRule::ALL.iter().enumerate() {
+                let y = py + 22 + i as i32 * 14;
+                let color = if i == self.rule_selection { 0xFFFF00FF } else { 0xAAAAAAFF };
+                if i == self.rule_selection {
+                    text!(">", x=px + 8, y=y, font="small", color=color);
+                }
+                text!(rule.name(), x=px + 18, y=y, font="small", color=color);
+                let value = rule.value(&self.arena.rules);
+                text!(&value, x=px + 140, y=y, font="small", color=0xFFFFFFFF);
+            }
+        }
+    }
+
+    fn instructions(&self) -> Option<Vec<&'static str>> {
+        Some(vec![
+            "Arena Editor:",
+            "- D-Pad: Move, X: Next tool, A: Use it",
+            "- House: A on a house changes its team",
+            "- River / Bridge: A at two corners",
+            "- Rules: A, then Left / Right to change",
+            "- B: Erase   Start: Save   Esc: Discard",
+        ])
+    }
+}
@@ -0,0 +1,56 @@
+use turbo::*;
+use crate::model::*;
+use crate::scene::*;
+
+#[turbo::serialize]
+#[derive(Default)]
+pub struct DeveloperScene;
+
+impl DeveloperScene {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Scene for DeveloperScene {
+    fn update(&mut self, _app: &mut App) -> Transition {
+        if gamepad::get(0).b.just_pressed() {
+            return Transition::Pop;
+        }
+        Transition::None
+    }
+
+    fn draw(&self, _app: &App) {
+        // Title
+        let scale = 3; 
+        let title_pixel_width = ((5 + 7) * 6 + 4) * scale;
+        let start_x_title = (512 - title_pixel_width as i32) / 2;
+        draw_title(start_x_title, 20, scale as u32);
+        
+        let center_x = |text: &str, font_w: i32| -> i32 {
+            (512 - (text.len() as i32 * font_w)) / 2
+        };
+
+        // Header
+        let txt_dev = "DEVELOPERS";
+        text!(txt_dev, x = center_x(txt_dev, 8), y = 70, font = "large", color = 0xFF0000FF);
+        
+        // Names
+        let txt_name1 = "Aarif Khan";
+        text!(txt_name1, x = center_x(txt_name1, 6), y = 100, font = "medium", color = 0xFFFFFFFF);
+        
+        let txt_name2 = "Azhan Ali";
+        text!(txt_name2, x = center_x(txt_name2, 6), y = 120, font = "medium", color = 0xFFFFFFFF);
+        
+        // Team
+        let txt_team_label = "Team Name:";
+        text!(txt_team_label, x = center_x(txt_team_label, 6), y = 150, font = "medium", color = 0xFFFF00FF);
+        
+        let txt_team = "Tm-AzhanAarif";
+        text!(txt_team, x = center_x(txt_team, 6), y = 165, font = "medium", color = 0xFFFFFFFF);
+        
+        // Back
+        let txt_back = "Press X to return";
+        text!(txt_back, x = center_x(txt_back, 5), y = 220, font = "small", color = 0xAAAAAAFF);
+    }
+}
@@ -0,0 +1,47 @@
+use turbo::*;
+use crate::scene::*;
+
+// Help box pushed over any scene with Select (Shift) or Y (S)
+#[turbo::serialize]
+pub struct InstructionsScene {
+    pub lines: Vec<String>,
+}
+
+impl InstructionsScene {
+    pub fn new(lines: &[&str]) -> Self {
+        Self { lines: lines.iter().map(|line| line.to_string()).collect() }
+    }
+}
+
+impl Scene for InstructionsScene {
+    fn update(&mut self, _app: &mut App) -> Transition {
+        let gp = gamepad::get(0);
+        if gp.select.just_pressed() || gp.y.just_pressed() || gp.b.just_pressed() {
+            return Transition::Pop;
+        }
+        Transition::None
+    }
+
+    fn draw(&self, _app: &App) {
+        // Overlay Box
+        rect!(x=50, y=50, w=412, h=188, color=0x000000EE);
+        rect!(x=50, y=50, w=412, h=188, border_size=2, border_color=0xFFFFFFFF, color=0x00000000);
+
+        text!("INSTRUCTIONS", x=180, y=60, font="large", color=0xFFFF00FF);
+
+        for (i, line) in self.lines.iter().enumerate() {
+            text!(line, x=70, y=100 + (i as i32 * 20), font="medium", color=0xFFFFFFFF);
+        }
+
+        text!("Press Select (Shift) or Y (S) to Close", x=140, y=220, font="small", color=0xAAAAAAFF);
+    }
+
+    // Select / Y closes this one instead of stacking another
+    fn instructions(&self) -> Option<Vec<&'static str>> {
+        None
+    }
+
+    fn is_overlay(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,155 @@
+use turbo::*;
+use crate::model::*;
+use crate::scene::*;
+
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub enum MenuOption {
+    SinglePlayer,
+    Multiplayer,
+    Online,
+    Records,
+    Replays,
+    Options,
+    Developer,
+}
+
+// Main menu, the root of the scene stack
+#[turbo::serialize]
+pub struct MenuScene {
+    pub option: MenuOption,
+}
+
+impl Default for MenuScene {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MenuScene {
+    pub fn new() -> Self {
+        Self { option: MenuOption::SinglePlayer }
+    }
+
+    fn draw_menu_item(&self, label: &str, index: i32, y: i32) {
+        let is_selected = match (index, self.option) {
+            (0, MenuOption::SinglePlayer) => true,
+            (1, MenuOption::Multiplayer) => true,
+            (2, MenuOption::Online) => true,
+            (3, MenuOption::Records) => true,
+            (4, MenuOption::Replays) => true,
+            (5, MenuOption::Options) => true,
+            (6, MenuOption::Developer) => true,
+            _ => false,
+        };
+
+        let color = if is_selected { 0xFFFF00FF } else { 0xFFFFFFFF }; 
+        let arrow = if is_selected { "> " } else { "" }; // No indentation on unselected
+        let suffix = if is_selected { " <" } else { "" };
+        
+        let full_text = format!("{}{}{}", arrow, label, suffix);
+        let text_w = full_text.len() as i32 * 8;
+        
+        // Center text (Hardcoded 512 width, 8px char width for Large)
+        // Note: arrows might offset visual center, but mathematical center is correct based on string len
+        let x = (512 - text_w) / 2;
+        
+        text!(&full_text, x = x, y = y, font = "large", color = color);
+    }
+}
+
+impl Scene for MenuScene {
+    fn update(&mut self, app: &mut App) -> Transition {
+        if gamepad::get(0).up.just_pressed() {
+            self.option = match self.option {
+                MenuOption::SinglePlayer => MenuOption::Developer,
+                MenuOption::Multiplayer => MenuOption::SinglePlayer,
+                MenuOption::Online => MenuOption::Multiplayer,
+                MenuOption::Records => MenuOption::Online,
+                MenuOption::Replays => MenuOption::Records,
+                MenuOption::Options => MenuOption::Replays,
+                MenuOption::Developer => MenuOption::Options,
+            };
+        }
+        if gamepad::get(0).down.just_pressed() {
+            self.option = match self.option {
+                MenuOption::SinglePlayer => MenuOption::Multiplayer,
+                MenuOption::Multiplayer => MenuOption::Online,
+                MenuOption::Online => MenuOption::Records,
+                MenuOption::Records => MenuOption::Replays,
+                MenuOption::Replays => MenuOption::Options,
+                MenuOption::Options => MenuOption::Developer,
+                MenuOption::Developer => MenuOption::SinglePlayer,
+            };
+        }
+
+        if gamepad::get(0).start.just_pressed() || gamepad::get(0).a.just_pressed() {
+            let next: AnyScene = match self.option {
+                MenuOption::SinglePlayer => SinglePlayerScene::new().into(),
+                MenuOption::Multiplayer => {
+                    app.mp_level_selection = 1;
+                    app.mp_arena = None;
+                    LevelSelectScene::new().into()
+                },
+                MenuOption::Online => OnlineLobbyScene::new().into(),
+                MenuOption::Records => RecordsScene::new().into(),
+                MenuOption::Replays => ReplaysScene::new().into(),
+                MenuOption::Options => OptionsScene::new().into(),
+                MenuOption::Developer => DeveloperScene::new().into(),
+            };
+            return Transition::Push(next);
+        }
+        Transition::None
+    }
+
+    fn draw(&self, _app: &App) {
+        // Custom Pixel Title
+        let scale = 4; // Reduced scale to fit better
+        // "SANTA" (5 chars) + "ISLANDS" (7 chars) = 12 * 6px + space(32px)
+        let title_pixel_width = ((5 + 7) * 6 + 4) * scale;
+        let start_x = (512 - title_pixel_width as i32) / 2; // Hardcoced 512 width
+        
+        draw_title(start_x, 20, scale as u32);
+        
+        // Subtitle / Decor
+        let sub = "- Christmas Adventure -";
+        let sub_w = sub.len() as i32 * 8; // Large font is ~8px wide
+        text!(sub, x = (512 - sub_w) / 2, y = 80, font = "large", color = 0xFFFF00FF);
+
+        // Menu Box
+        let box_w = 260; // Wider to fit large text
+        let box_h = 150;
+        let box_x = (512 - box_w) / 2;
+        let box_y = 100;
+        
+        // Box border
+        rect!(x = box_x - 2, y = box_y - 2, w = box_w as u32 + 4, h = box_h as u32 + 4,  color = 0xFFFFFFFF);
+        // Box background
+        rect!(x = box_x, y = box_y, w = box_w as u32, h = box_h as u32, color = 0x000000FF);
+
+        // Options
+        let start_y = box_y + 8;
+        let line_height = 20;
+
+        self.draw_menu_item("Single Player", 0, start_y);
+        self.draw_menu_item("Multiplayer", 1, start_y + line_height);
+        self.draw_menu_item("Online", 2, start_y + line_height * 2);
+        self.draw_menu_item("Records", 3, start_y + line_height * 3);
+        self.draw_menu_item("Replays", 4, start_y + line_height * 4);
+        self.draw_menu_item("Options", 5, start_y + line_height * 5);
+        self.draw_menu_item("Developer", 6, start_y + line_height * 6);
+        
+        // Instructions
+        let instr = "Arrows: Move | Space: Select";
+        let instr_w = instr.len() as i32 * 8;
+        text!(instr, x = (512 - instr_w) / 2, y = 260, font = "large", color = 0x555555FF);
+    }
+
+    fn instructions(&self) -> Option<Vec<&'static str>> {
+        Some(vec![
+            "Main Menu:",
+            "- Arrow Keys: Navigate Options",
+            "- A / Start: Select Option",
+        ])
+    }
+}
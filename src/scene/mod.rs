@@ -0,0 +1,41 @@
+mod stack;
+pub use stack::*;
+
+mod app;
+pub use app::*;
+
+mod menu;
+pub use menu::*;
+
+mod single_player;
+pub use single_player::*;
+
+mod multiplayer_setup;
+pub use multiplayer_setup::*;
+
+mod arena_editor;
+pub use arena_editor::*;
+
+mod online;
+pub use online::*;
+
+mod play;
+pub use play::*;
+
+mod records;
+pub use records::*;
+
+mod replays;
+pub use replays::*;
+
+mod options;
+pub use options::*;
+
+mod developer;
+pub use developer::*;
+
+mod pause;
+pub use pause::*;
+
+mod instructions;
+pub use instructions::*;
@@ -0,0 +1,692 @@
+use turbo::*;
+use crate::model::*;
+use crate::scene::*;
+
+// The multiplayer screens replace one another, so leaving a match pops
+// straight back to the main menu.
+
+#[turbo::serialize]
+#[derive(Default)]
+pub struct LevelSelectScene;
+
+impl LevelSelectScene {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Scene for LevelSelectScene {
+    fn update(&mut self, app: &mut App) -> Transition {
+        let gp = gamepad::get(0);
+
+        // Top row: levels 1-5. Bottom row: saved arenas, then a "new arena" tile while there's room.
+        let arenas = app.save_data.arenas.len();
+        let last_tile = arenas.min(MAX_ARENAS - 1);
+
+        match app.mp_arena {
+            None => {
+                // Navigation (Left/Right)
+                if gp.left.just_pressed() && app.mp_level_selection > 1 { app.mp_level_selection -= 1; }
+                if gp.right.just_pressed() && app.mp_level_selection < 5 { app.mp_level_selection += 1; }
+                // Down to the arenas
+                if gp.down.just_pressed() {
+                    app.mp_arena = Some((app.mp_level_selection as usize - 1).min(last_tile));
+                }
+            },
+            Some(i) => {
+                if gp.left.just_pressed() && i > 0 { app.mp_arena = Some(i - 1); }
+                if gp.right.just_pressed() && i < last_tile { app.mp_arena = Some(i + 1); }
+                // Up to the levels
+                if gp.up.just_pressed() {
+                    app.mp_level_selection = (i as u32 + 1).min(5);
+                    app.mp_arena = None;
+                }
+                // X edits a saved arena
+                if gp.x.just_pressed() && let Some(arena) = app.mp_arena() {
+                    return Transition::Push(ArenaEditorScene::new(Some(i), arena.clone()).into());
+                }
+            },
+        }
+
+        // Select -> Go to Setup, or build a new arena
+        if gp.start.just_pressed() || gp.a.just_pressed() {
+            match (app.mp_arena, app.mp_arena()) {
+                (Some(_), Some(arena)) => app.mp_level_selection = arena.rules.level,
+                (Some(_), None) => {
+                    let arena = Arena::new(format!("ARENA {}", arenas + 1));
+                    return Transition::Push(ArenaEditorScene::new(None, arena).into());
+                },
+                _ => {},
+            }
+            return Transition::Replace(MultiplayerSetupScene::new(SetupRow::Name(0)).into()); // Start at P1
+        }
+        
+        // Back -> Menu
+        if gp.b.just_pressed() {
+            return Transition::Pop;
+        }
+        Transition::None
+    }
+
+    fn draw(&self, app: &App) {
+        let center_x = |text: &str, font_w: i32| -> i32 {
+             (512 - (text.len() as i32 * font_w)) / 2
+        };
+
+        text!("SELECT LEVEL", x = center_x("SELECT LEVEL", 8), y = 25, font = "large", color = 0xFFFF00FF);
+        
+        let start_y = 55;
+        let box_w = 72;
+        let box_h = 54;
+        let gap_x = 16;
+        let gap_y = 30;
+        
+        // Levels 1-5 along the top row
+        let row_w = 5 * box_w + 4 * gap_x;
+        let start_x = (512 - row_w) / 2;
+        
+        for i in 1..=5 {
+            let x = start_x + (i - 1) * (box_w + gap_x);
+            let y = start_y;
+            
+            let is_selected = app.mp_arena.is_none() && app.mp_level_selection == i as u32;
+            let color = if is_selected { 0x00FF00FF } else { 0x444444FF };
+            
+            // Box
+            rect!(x=x-2, y=y-2, w=(box_w+4) as u32, h=(box_h+4) as u32, color=color);
+            
+            // Procedural Background
+            let bg_color = 0x000000FF; 
+            rect!(x=x, y=y, w=box_w as u32, h=box_h as u32, color=bg_color);
+            
+            // Thumbnail
+            let sprite_name = match i {
+                1 => "thumb_mp_lvl1",
+                2 => "thumb_mp_lvl2",
+                3 => "thumb_mp_lvl3",
+                4 => "thumb_mp_lvl4",
+                5 => "thumb_mp_lvl5",
+                _ => "",
+            };
+            if !sprite_name.is_empty() {
+                 sprite!(sprite_name, x=x, y=y, w=box_w as u32, h=box_h as u32);
+            }
+            
+            // Level Label (Below Box)
+            let lvl_text = format!("Level {}", i);
+            let txt_len = lvl_text.len() as i32 * 4; // Est 4px width for small
+            let txt_x = x + (box_w / 2) - (txt_len / 2);
+            
+            let label_col = if is_selected { 0x00FF00FF } else { 0x888888FF };
+            text!(&lvl_text, x=txt_x, y=y+box_h+8, font="small", color=label_col);
+        }
+
+        // Saved arenas along the bottom row, drawn as little maps
+        let arenas = &app.save_data.arenas;
+        let tiles = (arenas.len() + 1).min(MAX_ARENAS) as i32;
+        let row_w = tiles * box_w + (tiles - 1) * gap_x;
+        let start_x = (512 - row_w) / 2;
+        let y = start_y + box_h + gap_y;
+        for i in 0..tiles {
+            let x = start_x + i * (box_w + gap_x);
+            let is_selected = app.mp_arena == Some(i as usize);
+            let color = if is_selected { 0x00FF00FF } else { 0x444444FF };
+            rect!(x=x-2, y=y-2, w=(box_w+4) as u32, h=(box_h+4) as u32, color=color);
+
+            let label = match arenas.get(i as usize) {
+                Some(arena) => {
+                    draw_arena_thumb(arena, x, y, box_w, box_h);
+                    arena.name.clone()
+                },
+                None => {
+                    rect!(x=x, y=y, w=box_w as u32, h=box_h as u32, color=0x000000FF);
+                    text!("+", x=x + box_w / 2 - 4, y=y + box_h / 2 - 6, font="large", color=color);
+                    "NEW ARENA".to_string()
+                },
+            };
+            let txt_x = x + (box_w / 2) - (label.len() as i32 * 4 / 2);
+            let label_col = if is_selected { 0x00FF00FF } else { 0x888888FF };
+            text!(&label, x=txt_x, y=y+box_h+8, font="small", color=label_col);
+        }
+        
+        // Text Instructions (Below Grid)
+        let grid_bottom = y + box_h + 20;
+        
+        let msg_start = "Press START to Continue";
+        let msg_back = if app.mp_arena().is_some() { "X: Edit Arena   B: Back" } else { "Press X to Back" };
+        
+        // Use 8px for medium to try centering better
+        text!(msg_start, x=center_x(msg_start, 8), y=grid_bottom + 10, font="medium", color=0xFFFFFFFF);
+        text!(msg_back, x=center_x(msg_back, 4), y=grid_bottom + 30, font="small", color=0xAAAAAAFF);
+    }
+}
+
+// Water, bridges and houses of an arena scaled down into a level select tile
+fn draw_arena_thumb(arena: &Arena, x: i32, y: i32, w: i32, h: i32) {
+    let sx = |v: f32| x + (v * w as f32 / 512.0) as i32;
+    let sy = |v: f32| y + (v * h as f32 / 288.0) as i32;
+    let sw = |v: f32| ((v * w as f32 / 512.0) as u32).max(1);
+    let sh = |v: f32| ((v * h as f32 / 288.0) as u32).max(1);
+    rect!(x=x, y=y, w=w as u32, h=h as u32, color=0xC8E6C9FF);
+    for z in &arena.water {
+        // Clipped to the tile; rivers usually run off the field
+        let (x0, y0) = (z.x.max(0.0), z.y.max(0.0));
+        let (x1, y1) = ((z.x + z.w).min(512.0), (z.y + z.h).min(288.0));
+        rect!(x=sx(x0), y=sy(y0), w=sw(x1 - x0), h=sh(y1 - y0), color=0x29B6F6FF);
+    }
+    for b in &arena.bridges {
+        rect!(x=sx(b.x), y=sy(b.y), w=sw(b.w), h=sh(b.h), color=0x8D6E63FF);
+    }
+    for o in &arena.obstacles {
+        rect!(x=sx(o.x), y=sy(o.y), w=3, h=3, color=0x555555FF);
+    }
+    for house in &arena.houses {
+        let roof = match house.team {
+            0 if house.power => 0xFFD700FF,
+            0 => 0xFFFFFFFF,
+            team => SLOTS[team as usize - 1].wall,
+        };
+        rect!(x=sx(house.x) - 2, y=sy(house.y) - 2, w=4, h=4, color=roof);
+    }
+}
+
+// A line of the setup screen; which ones show depends on the player count
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub enum SetupRow {
+    Name(u8),
+    Players,
+    Series,
+    Rival,
+    Time,
+    Start,
+}
+
+impl SetupRow {
+    pub fn all(app: &App) -> Vec<SetupRow> {
+        let mut rows: Vec<SetupRow> = (0..app.mp_players as u8).map(SetupRow::Name).collect();
+        rows.push(SetupRow::Players);
+        rows.push(SetupRow::Series);
+        // The CPU only ever plays a 1v1
+        if app.mp_players == 2 {
+            rows.push(SetupRow::Rival);
+        }
+        rows.push(SetupRow::Time);
+        rows.push(SetupRow::Start);
+        rows
+    }
+}
+
+// Player count choices in order: (players, pairs)
+const PLAYER_CHOICES: [(u32, bool); 4] = [(2, false), (3, false), (4, false), (4, true)];
+
+#[turbo::serialize]
+pub struct MultiplayerSetupScene {
+    pub setup_row: SetupRow,
+    pub edit_cursor: usize,
+    pub is_editing: bool,
+}
+
+impl MultiplayerSetupScene {
+    pub fn new(setup_row: SetupRow) -> Self {
+        Self { setup_row, edit_cursor: 0, is_editing: false }
+    }
+}
+
+impl Scene for MultiplayerSetupScene {
+    fn update(&mut self, app: &mut App) -> Transition {
+        let gp = gamepad::get(0);
+
+        if self.is_editing {
+            // EDIT MODE
+            let SetupRow::Name(index) = self.setup_row else {
+                self.is_editing = false;
+                return Transition::None;
+            };
+            let target_name = app.mp_name_mut(index as usize);
+            
+            // KEYBOARD INPUT
+            let kb = turbo::keyboard::get();
+            
+            // Text Input
+            for c in kb.chars() {
+                 // Only allow A-Z, 0-9, and space for simplicity/font support
+                 if (c.is_alphanumeric() || c == ' ') && target_name.len() < 10 {
+                     target_name.push(c.to_ascii_uppercase());
+                     self.edit_cursor = target_name.len();
+                 }
+            }
+            
+            // Backspace
+            if kb.backspace().just_pressed() && target_name.len() > 0 {
+                target_name.pop();
+                self.edit_cursor = target_name.len();
+            }
+            
+            // Stop Editing (Enter, Escape, or Gamepad A/B/Start)
+            if kb.enter().just_pressed() || kb.escape().just_pressed() || gp.a.just_pressed() || gp.b.just_pressed() || gp.start.just_pressed() {
+                self.is_editing = false;
+                *target_name = target_name.trim().to_string();
+                if target_name.is_empty() {
+                    *target_name = format!("PLAYER {}", index + 1);
+                }
+            }
+            
+            // Ensure cursor is valid (just in case)
+            self.edit_cursor = target_name.len();
+            
+        } else {
+            // NAVIGATION MODE
+            let rows = SetupRow::all(app);
+            let row = rows.iter().position(|r| *r == self.setup_row).unwrap_or(rows.len() - 1);
+            
+            // Navigation (Up/Down)
+            if gp.up.just_pressed() && row > 0 {
+                self.setup_row = rows[row - 1];
+            }
+            if gp.down.just_pressed() && row + 1 < rows.len() {
+                self.setup_row = rows[row + 1];
+            }
+            
+            // Row Interaction
+            match self.setup_row {
+                SetupRow::Name(_) => {
+                    if gp.a.just_pressed() || gp.start.just_pressed() {
+                        self.is_editing = true;
+                        self.edit_cursor = 0;
+                    }
+                },
+                SetupRow::Players => { // 2, 3, 4, then 4 as two pairs
+                    let i = PLAYER_CHOICES.iter().position(|c| *c == (app.mp_players, app.mp_pairs)).unwrap_or(0);
+                    let i = if gp.left.just_pressed() && i > 0 {
+                        i - 1
+                    } else if gp.right.just_pressed() && i + 1 < PLAYER_CHOICES.len() {
+                        i + 1
+                    } else {
+                        i
+                    };
+                    (app.mp_players, app.mp_pairs) = PLAYER_CHOICES[i];
+                    if app.mp_players > 2 {
+                        app.mp_rival = None;
+                    }
+                },
+                SetupRow::Series => { // Single, Best of 3 / 5, Campaign
+                    let i = SeriesKind::ALL.iter().position(|k| *k == app.mp_series).unwrap_or(0);
+                    if gp.left.just_pressed() && i > 0 { app.mp_series = SeriesKind::ALL[i - 1]; }
+                    if gp.right.just_pressed() && i + 1 < SeriesKind::ALL.len() { app.mp_series = SeriesKind::ALL[i + 1]; }
+                },
+                SetupRow::Rival => { // Human, then CPU Easy / Normal / Hard
+                    let choices = [None, Some(RivalLevel::Easy), Some(RivalLevel::Normal), Some(RivalLevel::Hard)];
+                    let i = choices.iter().position(|c| *c == app.mp_rival).unwrap_or(0);
+                    if gp.left.just_pressed() && i > 0 { app.mp_rival = choices[i - 1]; }
+                    if gp.right.just_pressed() && i < 3 { app.mp_rival = choices[i + 1]; }
+                },
+                SetupRow::Time => { // Duration
+                    if gp.left.just_pressed() && app.mp_duration > 1 { app.mp_duration -= 1; }
+                    if gp.right.just_pressed() && app.mp_duration < 10 { app.mp_duration += 1; }
+                },
+                SetupRow::Start => {
+                    if gp.start.just_pressed() || gp.a.just_pressed() {
+                         // Against the CPU there's only one player to join
+                         if app.mp_rival.is_some() {
+                             app.devices = InputDevice::DEFAULT;
+                             return Transition::Replace(MultiplayerInstructionsScene::new().into());
+                         }
+                         return Transition::Replace(MultiplayerJoinScene::new(app.mp_players as usize).into());
+                    }
+                },
+            }
+            
+            // Back
+            // Back to Level Select
+            if gp.b.just_pressed() {
+                return Transition::Replace(LevelSelectScene::new().into());
+            }
+        }
+        Transition::None
+    }
+
+    fn draw(&self, app: &App) {
+        // Title
+        text!("MULTIPLAYER SETUP", x = 180, y = 40, font = "large", color = 0xFFFF00FF);
+        
+        // Helper
+        let center_x = |text: &str, font_w: i32| -> i32 {
+            (512 - (text.len() as i32 * font_w)) / 2
+        };
+
+        let rows = SetupRow::all(app);
+        let start_y = 60;
+        let gap = 24;
+        
+        let editing = self.is_editing;
+        
+        for (i, row) in rows.iter().enumerate() {
+            let y = start_y + i as i32 * gap;
+            let selected = self.setup_row == *row;
+            let label_col = if selected { 0x00FF00FF } else { 0xAAAAAAFF };
+            let value_col = if selected { 0xFFFFFFFF } else { 0x888888FF };
+            match row {
+                SetupRow::Name(index) => {
+                    let label = format!("Player {} Name:", index + 1);
+                    text!(&label, x = 100, y = y, font="medium", color = label_col);
+                    
+                    let box_col = if selected && editing { 0xFFFF00FF } else { 0xFFFFFFFF };
+                    rect!(x=260, y=y-2, w=140, h=14, color=box_col); 
+                    rect!(x=261, y=y-1, w=138, h=12, color=0x000000FF); 
+                    
+                    // Render chars
+                    let name = app.mp_names()[*index as usize].clone();
+                    for (i, c) in name.chars().enumerate() {
+                        let cx = 270 + (i as i32 * 10);
+                        let s = c.to_string();
+                        text!(&s, x=cx, y=y+1, font="medium", color=0xFFFFFFFF);
+                    }
+                    if selected && editing {
+                         let cx = 270 + (self.edit_cursor as i32 * 10);
+                         rect!(x=cx, y=y+11, w=8, h=2, color=0xFFFF00FF);
+                    }
+                    if selected && !editing { text!("(Press SPACE to Edit)", x=410, y=y+2, font="small", color=0x666666FF); }
+                },
+                SetupRow::Players => {
+                    text!("Players:", x = 100, y = y, font="medium", color = label_col);
+                    let players_val = if app.mp_pairs {
+                        format!(" < {} PLAYERS, 2v2 > ", app.mp_players)
+                    } else {
+                        format!(" < {} PLAYERS > ", app.mp_players)
+                    };
+                    text!(&players_val, x=270, y=y, font="medium", color=value_col);
+                },
+                SetupRow::Series => {
+                    text!("Series:", x = 100, y = y, font="medium", color = label_col);
+                    let series_val = format!(" < {} > ", app.mp_series.name());
+                    text!(&series_val, x=270, y=y, font="medium", color=value_col);
+                },
+                SetupRow::Rival => {
+                    text!("Player 2 Is:", x = 100, y = y, font="medium", color = label_col);
+                    let rival_val = match app.mp_rival {
+                        Some(level) => format!(" < CPU {} > ", level.name()),
+                        None => " < HUMAN > ".to_string(),
+                    };
+                    text!(&rival_val, x=270, y=y, font="medium", color=value_col);
+                },
+                SetupRow::Time => {
+                    text!("Duration:", x = 100, y = y, font="medium", color = label_col);
+                    let time_val = format!(" < {} mins > ", app.mp_duration);
+                    text!(&time_val, x=270, y=y, font="medium", color=value_col);
+                },
+                SetupRow::Start => {
+                    let btn_y = 230;
+                    let btn_w = 120;
+                    let btn_x = (512 - btn_w) / 2;
+                    
+                    let btn_col = if selected { 0x00E676FF } else { 0x444444FF };
+                    let txt_col = if selected { 0x000000FF } else { 0xAAAAAAFF };
+                    
+                    rect!(x=btn_x as i32, y=btn_y, w=btn_w as u32, h=30, color=btn_col);
+                    text!("START GAME", x = center_x("START GAME", 8), y = btn_y + 10, font="large", color=txt_col);
+                },
+            }
+        }
+    }
+}
+
+// "Press A to join": each player picks a gamepad or a keyboard layout
+#[turbo::serialize]
+#[derive(Default)]
+pub struct MultiplayerJoinScene {
+    pub join: JoinState,
+    // Each device's buttons last frame, in InputDevice::ALL order
+    prev: [u16; InputDevice::ALL.len()],
+}
+
+impl MultiplayerJoinScene {
+    pub fn new(players: usize) -> Self {
+        Self { join: JoinState::for_players(players), prev: [0; InputDevice::ALL.len()] }
+    }
+}
+
+impl Scene for MultiplayerJoinScene {
+    // Buttons held when the screen opens don't count as presses
+    fn enter(&mut self, _app: &mut App) {
+        for (i, device) in InputDevice::ALL.iter().enumerate() {
+            self.prev[i] = device.poll();
+        }
+    }
+
+    fn update(&mut self, app: &mut App) -> Transition {
+        let mut back = turbo::keyboard::get().escape().just_pressed();
+        let mut begin = false;
+
+        for (i, device) in InputDevice::ALL.iter().enumerate() {
+            let bits = device.poll();
+            let pad = Pad::from_bits(bits, self.prev[i]);
+            self.prev[i] = bits;
+
+            let joined = self.join.slots.contains(&Some(*device));
+            if pad.a.just_pressed() && !joined {
+                self.join.join(*device);
+            } else if pad.b.just_pressed() && !self.join.leave(*device) {
+                back = true;
+            } else if pad.start.just_pressed() && joined {
+                begin = true;
+            }
+        }
+
+        if back {
+            return Transition::Replace(MultiplayerSetupScene::new(SetupRow::Start).into());
+        }
+        if begin && let Some(devices) = self.join.devices() {
+            app.devices[..devices.len()].copy_from_slice(&devices);
+            return Transition::Replace(MultiplayerInstructionsScene::new().into());
+        }
+        Transition::None
+    }
+
+    fn draw(&self, app: &App) {
+        let center_x = |text: &str, font_w: i32| -> i32 { (512 - (text.len() as i32 * font_w)) / 2 };
+
+        let title = "PRESS A TO JOIN";
+        text!(title, x = center_x(title, 8), y = 25, font = "large", color = 0xFFFF00FF);
+
+        // Player Slots, split across the same 400px row however many there are
+        let names = app.mp_names();
+        let count = self.join.players as i32;
+        let slot_w = (400 - (count - 1) * 16) / count;
+        for (slot, name) in names.iter().enumerate().take(self.join.players) {
+            let x = 56 + slot as i32 * (slot_w + 16);
+            let y = 55;
+            let border = if self.join.slots[slot].is_some() { 0x00FF00FF } else { 0x444444FF };
+            rect!(x = x, y = y, w = slot_w as u32, h = 56, color = 0x000000FF, border_size = 2, border_color = border);
+            text!(name, x = x + 8, y = y + 8, font = "medium", color = 0xFFFFFFFF);
+            match self.join.slots[slot] {
+                Some(device) => {
+                    text!(device.name(), x = x + 8, y = y + 26, font = "medium", color = 0x00FF00FF);
+                    text!("B: Leave", x = x + 8, y = y + 42, font = "small", color = 0xAAAAAAFF);
+                },
+                None => {
+                    let blink = (time::tick() / 30).is_multiple_of(2);
+                    let col = if blink { 0xFFFFFFFF } else { 0x888888FF };
+                    let waiting = if count > 2 { "Press A" } else { "Waiting for A..." };
+                    text!(waiting, x = x + 8, y = y + 26, font = "medium", color = col);
+                },
+            }
+        }
+
+        // Devices
+        text!("Devices:", x = 56, y = 122, font = "medium", color = 0xFFD700FF);
+        for (i, device) in InputDevice::ALL.iter().enumerate() {
+            let y = 138 + i as i32 * 13;
+            let seen = app.detected.contains(device);
+            let col = if seen { 0xFFFFFFFF } else { 0x666666FF };
+            text!(device.name(), x = 56, y = y, font = "small", color = col);
+            text!(device.legend(), x = 136, y = y, font = "small", color = col);
+            if seen {
+                text!("OK", x = 440, y = y, font = "small", color = 0x00FF00FF);
+            }
+        }
+
+        let footer = if self.join.devices().is_some() { "Press START to Continue" } else { "Every player presses A" };
+        text!(footer, x = center_x(footer, 8), y = 240, font = "medium", color = 0x00FF00FF);
+        let back = "B (unjoined) / ESC: Back";
+        text!(back, x = center_x(back, 5), y = 258, font = "small", color = 0xAAAAAAFF);
+    }
+
+    fn instructions(&self) -> Option<Vec<&'static str>> {
+        Some(vec![
+            "Joining:",
+            "- A: Join with that gamepad or keys",
+            "- B: Leave again",
+            "- Two players can share one keyboard",
+            "- Start: Continue once everyone joined",
+        ])
+    }
+}
+
+#[turbo::serialize]
+#[derive(Default)]
+pub struct MultiplayerInstructionsScene;
+
+impl MultiplayerInstructionsScene {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Scene for MultiplayerInstructionsScene {
+    fn update(&mut self, app: &mut App) -> Transition {
+        let gp = gamepad::get(0);
+        let kb = turbo::keyboard::get();
+        
+        // SPACE or START to Start Game
+        if gp.start.just_pressed() || gp.a.just_pressed() || kb.space().just_pressed() {
+            let held = app.bits_for(ReplayMode::Multiplayer);
+            let mut replay = Replay::new(ReplayMode::Multiplayer, app.rng.u32(), held);
+            replay.p1_name = app.p1_name.clone();
+            replay.p2_name = app.p2_name.clone();
+            replay.p3_name = app.p3_name.clone();
+            replay.p4_name = app.p4_name.clone();
+            replay.players = app.mp_players;
+            replay.pairs = app.mp_pairs;
+            replay.minutes = app.mp_duration;
+            replay.level = app.mp_level_selection; // Uses stored level
+            replay.rival = app.mp_rival;
+            replay.series = app.mp_series;
+            replay.arena = app.mp_arena().cloned();
+            return Transition::Replace(launch(replay));
+        }
+        
+        // B to Go Back to the join step (or setup, against the CPU)
+        if gp.b.just_pressed() || kb.escape().just_pressed() {
+            if app.mp_rival.is_some() {
+                return Transition::Replace(MultiplayerSetupScene::new(SetupRow::Start).into());
+            }
+            return Transition::Replace(MultiplayerJoinScene::new(app.mp_players as usize).into());
+        }
+        Transition::None
+    }
+
+    fn draw(&self, app: &App) {
+        let center_x = |text: &str, font_w: i32| -> i32 { (512 - (text.len() as i32 * font_w)) / 2 };
+        
+        // A campaign opens on level 1 whatever was picked
+        let lvl = if app.mp_series == SeriesKind::Campaign { 1 } else { app.mp_level_selection };
+        let names = app.mp_names();
+        
+        // Header
+        let title = match (app.mp_series, app.mp_arena()) {
+            (SeriesKind::Campaign, _) => "CAMPAIGN: LEVEL 1".to_string(),
+            (SeriesKind::Single, Some(arena)) => arena.name.clone(),
+            (series, Some(arena)) => format!("{}, {}", arena.name, series.name()),
+            (SeriesKind::Single, None) => format!("LEVEL {}", lvl),
+            (series, None) => format!("LEVEL {}, {}", lvl, series.name()),
+        };
+        text!(&title, x = center_x(&title, 8), y = 30, font = "large", color = 0xFFFF00FF);
+        
+        let sub = match lvl {
+            1 => "Classic Collection",
+            2 => "Shifting Village",
+            3 => "River Crossing",
+            4 => "Power & Peril",
+            5 => "The Dog Chase",
+            _ => "Unknown Level"
+        };
+        let sub = match app.mp_arena() {
+            Some(_) if app.mp_series != SeriesKind::Campaign => format!("Custom Arena, {} Rules", sub),
+            _ => sub.to_string(),
+        };
+        let sub = sub.as_str();
+        text!(sub, x = center_x(sub, 5), y = 55, font = "medium", color = 0x00FFFFFF);
+        
+        // Instructions Content
+        let start_y = 90;
+        let x_left = 60;
+        
+        let mut lines = vec![];
+        
+        lines.push(format!("Overview:"));
+        match lvl {
+            1 => {
+                lines.push("Collect houses to earn points.".to_string());
+                lines.push("Watch out for basic obstacles.".to_string());
+            },
+            2 => {
+                lines.push("Houses reshuffle every 15 seconds!".to_string());
+                lines.push("Beware of Bombs that reset your score.".to_string());
+            },
+            3 => {
+                lines.push("Cross the river carefully using bridges.".to_string());
+                lines.push("Don't fall in the water!".to_string());
+                lines.push("Shadow trails slow you down.".to_string());
+            },
+            4 => {
+                lines.push("Snowmen freeze you on contact!".to_string());
+                lines.push("Power House spawns Risky Gifts.".to_string());
+                lines.push("Risky Gift: +60 or -60 points!".to_string());
+            },
+            5 => {
+                lines.push("THE DOG IS WATCHING!".to_string());
+                lines.push("-60 Gift wakes the dog.".to_string());
+                lines.push("Dog bite = -100 points.".to_string());
+                lines.push("Goal: Survive & Score High.".to_string());
+            },
+             _ => {}
+        }
+        lines.push("".to_string());
+        
+        lines.push("Controls:".to_string());
+        let roles = ["Santa", "Rival", "Green", "Orange"];
+        for (i, name) in names.iter().enumerate() {
+            match app.mp_rival {
+                Some(level) if i == 1 => lines.push(format!("{} ({}): CPU, {}", name, roles[i], level.name())),
+                _ => lines.push(format!("{} ({}): {}", name, roles[i], app.devices[i].legend())),
+            }
+        }
+        
+        lines.push("".to_string());
+        lines.push("Win Condition:".to_string());
+        if app.mp_pairs {
+            lines.push("Santa + Green vs Rival + Orange, scores combined!".to_string());
+        } else {
+            lines.push("Highest score when time runs out wins!".to_string());
+        }
+        match app.mp_series {
+            SeriesKind::Single => {},
+            SeriesKind::Campaign => lines.push("Levels 1-5: most rounds won, then points!".to_string()),
+            series => lines.push(format!("First to {} round wins takes the series!", series.rounds() / 2 + 1)),
+        }
+        
+        // Tighter lines once the list would run into the footer
+        let gap = (145 / lines.len() as i32).min(16);
+        for (i, line) in lines.iter().enumerate() {
+            let col = if line.ends_with(':') { 0xFFD700FF } else { 0xFFFFFFFF };
+            text!(line, x=x_left, y=start_y + (i as i32 * gap), font="medium", color=col);
+        }
+        
+        // Footer
+        let footer1 = "Press SPACE to START GAME";
+        let footer2 = "Press B / ESC to Go Back";
+        text!(footer1, x=center_x(footer1, 5), y=240, font="medium", color=0x00FF00FF);
+        text!(footer2, x=center_x(footer2, 5), y=260, font="small", color=0xAAAAAAFF);
+    }
+}
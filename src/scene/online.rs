@@ -0,0 +1,342 @@
+use turbo::*;
+use crate::model::*;
+use crate::scene::*;
+
+// Lobby codes skip letters and digits that are easy to mix up
+const CODE_CHARS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+const CODE_LEN: usize = 4;
+
+// Online lobby: pick a name, then create a lobby or join one by its code
+#[turbo::serialize]
+pub struct OnlineLobbyScene {
+    pub row: u8, // 0=Name, 1=Create, 2=Join
+    pub code: String,
+    pub is_editing: bool,
+}
+
+impl Default for OnlineLobbyScene {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OnlineLobbyScene {
+    pub fn new() -> Self {
+        Self { row: 1, code: String::new(), is_editing: false }
+    }
+
+    fn new_code(rng: &mut Rng) -> String {
+        (0..CODE_LEN).map(|_| CODE_CHARS[rng.u32() as usize % CODE_CHARS.len()] as char).collect()
+    }
+}
+
+impl Scene for OnlineLobbyScene {
+    fn update(&mut self, app: &mut App) -> Transition {
+        let gp = gamepad::get(0);
+        let kb = turbo::keyboard::get();
+
+        if self.is_editing {
+            let (target, max) = if self.row == 0 { (&mut app.p1_name, 10) } else { (&mut self.code, CODE_LEN) };
+            for c in kb.chars() {
+                if (c.is_ascii_alphanumeric() || (c == ' ' && self.row == 0)) && target.len() < max {
+                    target.push(c.to_ascii_uppercase());
+                }
+            }
+            if kb.backspace().just_pressed() {
+                target.pop();
+            }
+
+            if kb.enter().just_pressed() || kb.escape().just_pressed() || gp.a.just_pressed() || gp.b.just_pressed() || gp.start.just_pressed() {
+                self.is_editing = false;
+                *target = target.trim().to_string();
+                if self.row == 0 && target.is_empty() {
+                    *target = "PLAYER 1".to_string();
+                }
+                // A finished code joins straight away
+                if self.row == 2 && self.code.len() == CODE_LEN && !gp.b.just_pressed() && !kb.escape().just_pressed() {
+                    return Transition::Replace(OnlineRoomScene::new(self.code.clone()).into());
+                }
+            }
+            return Transition::None;
+        }
+
+        if gp.up.just_pressed() && self.row > 0 { self.row -= 1; }
+        if gp.down.just_pressed() && self.row < 2 { self.row += 1; }
+
+        if gp.a.just_pressed() || gp.start.just_pressed() {
+            match self.row {
+                1 => return Transition::Replace(OnlineRoomScene::new(Self::new_code(&mut app.rng)).into()),
+                2 if self.code.len() == CODE_LEN && gp.start.just_pressed() => {
+                    return Transition::Replace(OnlineRoomScene::new(self.code.clone()).into());
+                },
+                _ => self.is_editing = true,
+            }
+        }
+
+        if gp.b.just_pressed() || kb.escape().just_pressed() {
+            return Transition::Pop;
+        }
+        Transition::None
+    }
+
+    fn draw(&self, app: &App) {
+        let center_x = |text: &str, font_w: i32| -> i32 { (512 - (text.len() as i32 * font_w)) / 2 };
+
+        text!("ONLINE MATCH", x = center_x("ONLINE MATCH", 8), y = 30, font = "large", color = 0xFFFF00FF);
+        let sub = "Santa vs. Rival over Turbo OS";
+        text!(sub, x = center_x(sub, 5), y = 52, font = "medium", color = 0x00FFFFFF);
+
+        let row_col = |row: u8| if self.row == row { 0x00FF00FF } else { 0xAAAAAAFF };
+        let box_col = |row: u8| if self.row == row && self.is_editing { 0xFFFF00FF } else { 0xFFFFFFFF };
+
+        // Name (Row 0)
+        let y = 90;
+        text!("Your Name:", x = 100, y = y, font = "medium", color = row_col(0));
+        rect!(x = 260, y = y - 2, w = 140, h = 14, color = box_col(0));
+        rect!(x = 261, y = y - 1, w = 138, h = 12, color = 0x000000FF);
+        text!(&app.p1_name, x = 270, y = y + 1, font = "medium", color = 0xFFFFFFFF);
+
+        // Create (Row 1)
+        let y = 130;
+        let create = if self.row == 1 { "> CREATE LOBBY <" } else { "CREATE LOBBY" };
+        text!(create, x = center_x(create, 8), y = y, font = "large", color = row_col(1));
+
+        // Join (Row 2)
+        let y = 170;
+        text!("Join Code:", x = 100, y = y, font = "medium", color = row_col(2));
+        rect!(x = 260, y = y - 2, w = 60, h = 14, color = box_col(2));
+        rect!(x = 261, y = y - 1, w = 58, h = 12, color = 0x000000FF);
+        for (i, c) in self.code.chars().enumerate() {
+            let s = c.to_string();
+            text!(&s, x = 268 + i as i32 * 12, y = y + 1, font = "medium", color = 0xFFFFFFFF);
+        }
+        if self.row == 2 && !self.is_editing {
+            let hint = if self.code.len() == CODE_LEN { "(A: Edit, START: Join)" } else { "(Press A to Type)" };
+            text!(hint, x = 330, y = y + 2, font = "small", color = 0x666666FF);
+        }
+
+        let footer = "The host picks the level and time in the lobby";
+        text!(footer, x = center_x(footer, 5), y = 230, font = "small", color = 0xAAAAAAFF);
+        let back = "Press B / ESC to Go Back";
+        text!(back, x = center_x(back, 5), y = 258, font = "small", color = 0xAAAAAAFF);
+    }
+
+    fn instructions(&self) -> Option<Vec<&'static str>> {
+        Some(vec![
+            "Online Match:",
+            "- Create Lobby: share the code shown",
+            "- Join Code: type a friend's code",
+            "- First in the lobby is the host",
+            "- Needs a Turbo OS connection",
+        ])
+    }
+}
+
+// One lobby's channel: seats, the host's match setup, then the match itself.
+// The channel runs the match; this side sends buttons and draws snapshots.
+#[turbo::serialize]
+pub struct OnlineRoomScene {
+    pub code: String,
+    pub seat: Option<u8>,
+    pub lobby: LobbyInfo,
+    pub full: bool,
+    // Join is sent once the channel is reachable
+    joined: bool,
+    pub setup_row: u8, // 0=Level, 1=Duration (host only)
+    // Local copy of the match, overwritten by every snapshot
+    pub game: Option<MultiplayerGame>,
+}
+
+impl OnlineRoomScene {
+    pub fn new(code: String) -> Self {
+        Self {
+            code,
+            seat: None,
+            lobby: LobbyInfo::default(),
+            full: false,
+            joined: false,
+            setup_row: 0,
+            game: None,
+        }
+    }
+
+    fn is_host(&self) -> bool {
+        self.seat == Some(0)
+    }
+
+    fn in_match(&self) -> bool {
+        self.game.as_ref().is_some_and(|g| !g.game_over)
+    }
+
+    fn handle(&mut self, event: OnlineEvent, app: &mut App) {
+        match event {
+            OnlineEvent::Seated(seat) => self.seat = Some(seat),
+            OnlineEvent::Full => self.full = true,
+            OnlineEvent::Lobby(lobby) => {
+                // Someone left mid-match
+                if !lobby.in_match {
+                    self.game = None;
+                }
+                self.lobby = lobby;
+            },
+            OnlineEvent::Snapshot(snap) => {
+                let game = self.game.get_or_insert_with(|| {
+                    let [p1, p2] = self.lobby.names.clone().map(Option::unwrap_or_default);
+                    MultiplayerGame::new(p1, p2, app.mp_duration, snap.current_level, app.rng.u32())
+                });
+                game.apply_snapshot(snap);
+            },
+        }
+    }
+
+    // Host's level and duration rows
+    fn update_setup(&mut self, app: &mut App) {
+        let gp = gamepad::get(0);
+        if gp.up.just_pressed() { self.setup_row = 0; }
+        if gp.down.just_pressed() { self.setup_row = 1; }
+        let step = gp.right.just_pressed() as i32 - gp.left.just_pressed() as i32;
+        if step != 0 {
+            if self.setup_row == 0 {
+                app.mp_level_selection = (app.mp_level_selection as i32 + step).clamp(1, 5) as u32;
+            } else {
+                app.mp_duration = (app.mp_duration as i32 + step).clamp(1, 10) as u32;
+            }
+        }
+    }
+}
+
+impl Scene for OnlineRoomScene {
+    fn update(&mut self, app: &mut App) -> Transition {
+        let gp = gamepad::get(0);
+        let leave = turbo::keyboard::get().escape().just_pressed() || (gp.b.just_pressed() && !self.in_match());
+
+        if let Some(conn) = MatchChannel::subscribe(&self.code) {
+            if !self.joined {
+                self.joined = conn.send(&OnlineRequest::Join { name: app.p1_name.clone() }).is_ok();
+            }
+            while let Ok(event) = conn.recv() {
+                self.handle(event, app);
+            }
+
+            if self.game.is_some() {
+                // The host's message each frame also steps the match
+                let _ = conn.send(&OnlineRequest::Input { bits: app.live_bits()[0] });
+            } else if self.is_host() && self.lobby.names.iter().all(|n| n.is_some()) {
+                self.update_setup(app);
+                if gp.a.just_pressed() || gp.start.just_pressed() {
+                    let _ = conn.send(&OnlineRequest::Start {
+                        minutes: app.mp_duration,
+                        level: app.mp_level_selection,
+                        seed: app.rng.u32(),
+                    });
+                }
+            }
+        }
+
+        if let Some(game) = &mut self.game {
+            game.update_effects();
+            for name in game.take_sounds() {
+                app.save_data.settings.play_sfx(&name);
+            }
+            app.request_shake(game.take_shake());
+        }
+
+        if leave {
+            return Transition::Pop;
+        }
+        Transition::None
+    }
+
+    fn draw(&self, app: &App) {
+        if let Some(game) = &self.game {
+            game.draw(&app.save_data.settings);
+            let badge = format!("ONLINE {}", self.code);
+            rect!(x = 4, y = 274, w = 70, h = 12, color = 0x000000AA);
+            text!(&badge, x = 8, y = 277, font = "small", color = 0x00FFFFFF);
+            return;
+        }
+
+        let center_x = |text: &str, font_w: i32| -> i32 { (512 - (text.len() as i32 * font_w)) / 2 };
+
+        let title = format!("LOBBY {}", self.code);
+        text!(&title, x = center_x(&title, 8), y = 25, font = "large", color = 0xFFFF00FF);
+        let share = "Share this code with your rival";
+        text!(share, x = center_x(share, 5), y = 45, font = "small", color = 0xAAAAAAFF);
+
+        let status = if self.full {
+            "This lobby is full"
+        } else if !self.joined {
+            "Connecting to Turbo OS..."
+        } else if self.seat.is_none() {
+            "Joining..."
+        } else {
+            ""
+        };
+        if !status.is_empty() {
+            text!(status, x = center_x(status, 8), y = 120, font = "large", color = 0xFF6666FF);
+            let back = "Press B / ESC to Go Back";
+            text!(back, x = center_x(back, 5), y = 258, font = "small", color = 0xAAAAAAFF);
+            return;
+        }
+
+        // Seats
+        for (seat, name) in self.lobby.names.iter().enumerate() {
+            let x = 56 + seat as i32 * 208;
+            let y = 65;
+            let border = if name.is_some() { 0x00FF00FF } else { 0x444444FF };
+            rect!(x = x, y = y, w = 192, h = 48, color = 0x000000FF, border_size = 2, border_color = border);
+            let role = if seat == 0 { "SANTA (HOST)" } else { "RIVAL" };
+            text!(role, x = x + 10, y = y + 8, font = "small", color = 0xFFD700FF);
+            match name {
+                Some(name) => {
+                    let you = if self.seat == Some(seat as u8) { " (YOU)" } else { "" };
+                    let label = format!("{}{}", name, you);
+                    text!(&label, x = x + 10, y = y + 24, font = "medium", color = 0xFFFFFFFF);
+                },
+                None => text!("Waiting...", x = x + 10, y = y + 24, font = "medium", color = 0x888888FF),
+            }
+        }
+
+        // Match setup
+        let ready = self.lobby.names.iter().all(|n| n.is_some());
+        if self.is_host() {
+            let row_col = |row: u8| if self.setup_row == row { 0x00FF00FF } else { 0xAAAAAAFF };
+            text!("Level:", x = 140, y = 135, font = "medium", color = row_col(0));
+            let level = format!("< {} >", app.mp_level_selection);
+            text!(&level, x = 270, y = 135, font = "medium", color = row_col(0));
+            text!("Duration:", x = 140, y = 160, font = "medium", color = row_col(1));
+            let duration = format!("< {} mins >", app.mp_duration);
+            text!(&duration, x = 270, y = 160, font = "medium", color = row_col(1));
+        }
+        let footer = match (self.is_host(), ready) {
+            (true, true) => "Press START to Begin",
+            (true, false) => "Waiting for a rival to join",
+            (false, _) => "Waiting for the host to start",
+        };
+        text!(footer, x = center_x(footer, 8), y = 210, font = "medium", color = 0x00FF00FF);
+        let back = "Press B / ESC to Leave";
+        text!(back, x = center_x(back, 5), y = 258, font = "small", color = 0xAAAAAAFF);
+    }
+
+    // Frees the seat (and ends the match for the other player)
+    fn exit(&mut self, app: &mut App) {
+        if let Some(conn) = MatchChannel::subscribe(&self.code) {
+            let _ = conn.send(&OnlineRequest::Leave);
+        }
+        app.clear_shake();
+    }
+
+    // Opening the overlay would stop the updates, and the host's updates drive the match
+    fn instructions(&self) -> Option<Vec<&'static str>> {
+        if self.game.is_some() {
+            return None;
+        }
+        Some(vec![
+            "Online Lobby:",
+            "- Host: Up/Down + Left/Right: Setup",
+            "- Host: Start: Begin the match",
+            "- In a match: Esc leaves",
+            "- Leaving ends the match for both",
+        ])
+    }
+}
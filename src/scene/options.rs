@@ -0,0 +1,144 @@
+use turbo::*;
+use crate::model::*;
+use crate::scene::*;
+
+// Music, Music Vol, SFX Vol, Shake, Particles, 6 bindings, Reset, Back
+const OPTION_ROWS: usize = 13;
+
+// Reachable from the main menu and the pause menu; saved when it closes
+#[turbo::serialize]
+pub struct OptionsScene {
+    pub selection: usize,
+    pub binding: Option<usize>, // Action waiting for its new button
+}
+
+impl Default for OptionsScene {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OptionsScene {
+    pub fn new() -> Self {
+        Self { selection: 0, binding: None }
+    }
+}
+
+impl Scene for OptionsScene {
+    // Navigated with the raw pad, so a bad mapping can always be undone here
+    fn update(&mut self, app: &mut App) -> Transition {
+        // Waiting for a rebind: the first physical button pressed wins
+        if let Some(index) = self.binding {
+            let pressed = app.pad_raw & !app.pad_prev;
+            if turbo::keyboard::get().escape().just_pressed() {
+                self.binding = None;
+            } else if let Some(bit) = REMAPPABLE.iter().copied().find(|b| pressed & b != 0) {
+                app.save_data.settings.rebind(index, bit);
+                self.binding = None;
+            }
+            return Transition::None;
+        }
+
+        let gp = gamepad::get(0);
+        if gp.down.just_pressed() {
+            self.selection = (self.selection + 1) % OPTION_ROWS;
+        }
+        if gp.up.just_pressed() {
+            self.selection = (self.selection + OPTION_ROWS - 1) % OPTION_ROWS;
+        }
+
+        let step = if gp.right.just_pressed() { 1 } else if gp.left.just_pressed() { -1 } else { 0 };
+        let confirm = gp.a.just_pressed() || gp.start.just_pressed();
+        let change = confirm || step != 0;
+        let volume = |v: u8| (v as i32 + step).clamp(0, MAX_VOLUME as i32) as u8;
+
+        let settings = &mut app.save_data.settings;
+        let mut close = gp.b.just_pressed();
+        match self.selection {
+            0 => if change { settings.music_on = !settings.music_on },
+            1 => settings.music_volume = volume(settings.music_volume),
+            2 => {
+                if step != 0 {
+                    settings.sfx_volume = volume(settings.sfx_volume);
+                    settings.play_sfx("coin"); // Preview
+                }
+            },
+            3 => if change { settings.screen_shake = !settings.screen_shake },
+            4 => if change { settings.reduced_particles = !settings.reduced_particles },
+            5..=10 => if confirm { self.binding = Some(self.selection - 5) },
+            11 => if confirm { *settings = Settings::new() },
+            _ => close |= confirm,
+        }
+
+        if close {
+            return Transition::Pop;
+        }
+        Transition::None
+    }
+
+    fn draw(&self, app: &App) {
+        let center_x = |text: &str, font_w: i32| -> i32 { (512 - (text.len() as i32 * font_w)) / 2 };
+
+        let title = "OPTIONS";
+        text!(title, x = center_x(title, 8), y = 16, font = "large", color = 0xFFFF00FF);
+
+        let settings = &app.save_data.settings;
+        let on_off = |on: bool| if on { "ON".to_string() } else { "OFF".to_string() };
+        let bar = |v: u8| format!("{}{}", "#".repeat(v as usize), "-".repeat((MAX_VOLUME - v) as usize));
+        let actions = ["A", "B", "X", "Y", "Start", "Select"];
+
+        let mut rows = vec![
+            ("Music".to_string(), on_off(settings.music_on)),
+            ("Music Volume".to_string(), bar(settings.music_volume)),
+            ("SFX Volume".to_string(), bar(settings.sfx_volume)),
+            ("Screen Shake".to_string(), on_off(settings.screen_shake)),
+            ("Particles".to_string(), if settings.reduced_particles { "REDUCED".to_string() } else { "FULL".to_string() }),
+        ];
+        for (i, action) in actions.iter().enumerate() {
+            let value = if self.binding == Some(i) {
+                "Press a button...".to_string()
+            } else {
+                button_name(settings.bindings[i]).to_string()
+            };
+            rows.push((format!("Action {}", action), value));
+        }
+        rows.push(("Reset Defaults".to_string(), "".to_string()));
+        rows.push(("Back".to_string(), "".to_string()));
+
+        for (i, (label, value)) in rows.iter().enumerate() {
+            let y = 44 + i as i32 * 15;
+            let selected = i == self.selection;
+            let col = if selected { 0xFFFF00FF } else { 0xFFFFFFFF };
+            if selected {
+                text!(">", x = 104, y = y, font = "medium", color = col);
+            }
+            text!(label, x = 120, y = y, font = "medium", color = col);
+            let value_col = if selected { 0xFFFFFFFF } else { 0xAAAAAAFF };
+            text!(value, x = 280, y = y, font = "medium", color = value_col);
+        }
+
+        let footer = if self.binding.is_some() {
+            "Press the new button | Esc: Cancel"
+        } else {
+            "Left/Right: Change | A: Toggle / Rebind | B: Save & Back"
+        };
+        text!(footer, x = center_x(footer, 5), y = 260, font = "small", color = 0xAAAAAAFF);
+    }
+
+    fn exit(&mut self, app: &mut App) {
+        app.save_data.save();
+    }
+
+    // Not while waiting for a new button, which may be Select or Y
+    fn instructions(&self) -> Option<Vec<&'static str>> {
+        if self.binding.is_some() {
+            return None;
+        }
+        Some(vec![
+            "Options:",
+            "- Left/Right: Change | A: Toggle / Rebind",
+            "- Rebind: pick an action, press its new button",
+            "- Saved when you leave with B",
+        ])
+    }
+}
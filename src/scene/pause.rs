@@ -0,0 +1,140 @@
+use turbo::*;
+use crate::scene::*;
+
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+pub enum PauseItem {
+    Resume,
+    Options,
+    Restart,
+    Quit,
+}
+
+impl PauseItem {
+    pub const ALL: [PauseItem; 4] = [PauseItem::Resume, PauseItem::Options, PauseItem::Restart, PauseItem::Quit];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PauseItem::Resume => "Resume",
+            PauseItem::Options => "Options",
+            PauseItem::Restart => "Restart",
+            PauseItem::Quit => "Quit to Menu",
+        }
+    }
+}
+
+// Shared pause overlay for every mode. The game underneath is frozen
+// simply by not being on top of the scene stack.
+#[turbo::serialize]
+pub struct PauseScene {
+    pub selection: usize,
+    pub confirming: Option<PauseItem>,
+}
+
+impl Default for PauseScene {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PauseScene {
+    pub fn new() -> Self {
+        Self { selection: 0, confirming: None }
+    }
+}
+
+impl Scene for PauseScene {
+    // Resume pops straight back to the game; Restart and Quit are handed to it
+    fn update(&mut self, app: &mut App) -> Transition {
+        let gp = app.live_pad();
+        let kb = turbo::keyboard::get();
+        let ok = gp.a.just_pressed() || gp.start.just_pressed();
+        let back = gp.b.just_pressed() || kb.escape().just_pressed();
+
+        if let Some(item) = self.confirming {
+            if ok {
+                return Transition::Return(item);
+            }
+            if back {
+                self.confirming = None;
+            }
+            return Transition::None;
+        }
+
+        if back {
+            return Transition::Pop;
+        }
+
+        let count = PauseItem::ALL.len();
+        if gp.down.just_pressed() {
+            self.selection = (self.selection + 1) % count;
+        }
+        if gp.up.just_pressed() {
+            self.selection = (self.selection + count - 1) % count;
+        }
+
+        if ok {
+            let item = PauseItem::ALL[self.selection];
+            match item {
+                PauseItem::Resume => return Transition::Pop,
+                PauseItem::Options => return Transition::Push(OptionsScene::new().into()),
+                // Anything that throws away the current run asks first
+                PauseItem::Restart | PauseItem::Quit => self.confirming = Some(item),
+            }
+        }
+        Transition::None
+    }
+
+    fn draw(&self, _app: &App) {
+        let center_x = |text: &str, font_w: i32| -> i32 { (512 - (text.len() as i32 * font_w)) / 2 };
+
+        // Dim the frozen game
+        rect!(w = 512, h = 288, color = 0x000000AA);
+
+        let box_w = 220;
+        let box_h = 150;
+        let box_x = (512 - box_w) / 2;
+        let box_y = (288 - box_h) / 2;
+        rect!(x = box_x, y = box_y, w = box_w as u32, h = box_h as u32, color = 0x000000EE);
+        rect!(x = box_x, y = box_y, w = box_w as u32, h = box_h as u32, border_size = 2, border_color = 0xFFFFFFFF, color = 0x00000000);
+
+        let title = "PAUSED";
+        text!(title, x = center_x(title, 8), y = box_y + 12, font = "large", color = 0xFFFF00FF);
+
+        if let Some(item) = self.confirming {
+            let q = match item {
+                PauseItem::Restart => "Restart this run?",
+                _ => "Quit to the menu?",
+            };
+            let warn = "Current progress will be lost.";
+            let hint = "A: Yes | B: No";
+            text!(q, x = center_x(q, 6), y = box_y + 50, font = "medium", color = 0xFFFFFFFF);
+            text!(warn, x = center_x(warn, 5), y = box_y + 70, font = "small", color = 0xE74C3CFF);
+            text!(hint, x = center_x(hint, 5), y = box_y + 100, font = "small", color = 0xAAAAAAFF);
+            return;
+        }
+
+        for (i, item) in PauseItem::ALL.iter().enumerate() {
+            let selected = i == self.selection;
+            let label = if selected { format!("> {} <", item.label()) } else { item.label().to_string() };
+            let col = if selected { 0xFFFF00FF } else { 0xFFFFFFFF };
+            text!(&label, x = center_x(&label, 8), y = box_y + 42 + i as i32 * 22, font = "large", color = col);
+        }
+
+        let hint = "A: Select | Esc: Resume";
+        text!(hint, x = center_x(hint, 5), y = box_y + box_h - 14, font = "small", color = 0xAAAAAAFF);
+    }
+
+    fn instructions(&self) -> Option<Vec<&'static str>> {
+        Some(vec![
+            "Pause Menu:",
+            "- Up/Down: Choose | A: Select",
+            "- B / Esc: Resume",
+            "- Restart and Quit ask first",
+        ])
+    }
+
+    fn is_overlay(&self) -> bool {
+        true
+    }
+}
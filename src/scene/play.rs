@@ -0,0 +1,162 @@
+use turbo::*;
+use crate::model::*;
+use crate::scene::*;
+
+// Where a play scene's buttons come from
+#[turbo::serialize]
+pub enum Feed {
+    // Live pads, recorded into the replay
+    Live(Replay),
+    // A saved replay drives the game; B stops it
+    Playback(ReplayPlayer),
+}
+
+impl Feed {
+    pub fn replay(&self) -> &Replay {
+        match self {
+            Feed::Live(replay) => replay,
+            Feed::Playback(player) => &player.replay,
+        }
+    }
+}
+
+// Starts a live, recorded run of the replay's mode and setup
+pub fn launch(replay: Replay) -> AnyScene {
+    build(Feed::Live(replay))
+}
+
+// Plays a saved replay back
+pub fn watch(replay: Replay) -> AnyScene {
+    build(Feed::Playback(ReplayPlayer::new(replay)))
+}
+
+fn build(feed: Feed) -> AnyScene {
+    match feed.replay().mode {
+        ReplayMode::GiftPacking => PlayScene::<FactoryGame>::new(feed).into(),
+        ReplayMode::RaindeerRush => PlayScene::<SleighGame>::new(feed).into(),
+        ReplayMode::Breaker => PlayScene::<BreakerGame>::new(feed).into(),
+        ReplayMode::SilentSanta => PlayScene::<StealthGame>::new(feed).into(),
+        ReplayMode::Multiplayer => PlayScene::<MultiplayerGame>::new(feed).into(),
+        ReplayMode::RooftopRun => PlayScene::<RooftopGame>::new(feed).into(),
+    }
+}
+
+// One session of any game mode: feeds it input, records or replays it,
+// and handles pausing, restarting, records, sound and shake for it.
+#[turbo::serialize]
+pub struct PlayScene<G> {
+    pub game: G,
+    feed: Feed,
+    input_feed: InputFeed,
+}
+
+impl<G: GameMode> PlayScene<G> {
+    pub fn new(feed: Feed) -> Self {
+        let replay = feed.replay();
+        Self {
+            game: G::from_replay(replay),
+            input_feed: InputFeed::new(replay.held),
+            feed,
+        }
+    }
+
+    // Buttons the game sees this frame. None means playback finished or was cancelled with B.
+    fn next_input(&mut self, app: &App) -> Option<Input> {
+        let bits = match &mut self.feed {
+            Feed::Live(replay) => {
+                let bits = app.bits_for(replay.mode);
+                replay.push(bits);
+                bits
+            },
+            Feed::Playback(player) => {
+                if app.live_pad().b.just_pressed() {
+                    return None;
+                }
+                player.next_frame()?
+            },
+        };
+        Some(self.input_feed.next(bits))
+    }
+
+    // Same mode and setup, fresh seed (or the same replay from the top)
+    fn restarted(&self, app: &mut App) -> AnyScene {
+        match &self.feed {
+            Feed::Playback(player) => watch(player.replay.clone()),
+            Feed::Live(old) => {
+                let held = app.bits_for(old.mode);
+                let mut replay = old.restarted(app.rng.u32(), held);
+                // Breaker's level select offers whatever has been unlocked since
+                replay.breaker_unlocked = app.save_data.breaker_unlocked;
+                launch(replay)
+            },
+        }
+    }
+
+    // Shown on top of a game while a replay drives it
+    fn draw_playback_badge(&self) {
+        if let Feed::Playback(player) = &self.feed {
+            let total = player.replay.frame_count().max(1);
+            let bar_w = 88;
+            let filled = player.frame.min(total) * bar_w / total;
+            rect!(x = 404, y = 4, w = 100, h = 18, color = 0x000000CC);
+            text!("REPLAY", x = 410, y = 7, font = "small", color = 0xFF4444FF);
+            rect!(x = 410, y = 15, w = bar_w, h = 3, color = 0x555555FF);
+            rect!(x = 410, y = 15, w = filled, h = 3, color = 0xFFFFFFFF);
+            text!("B: Stop", x = 460, y = 7, font = "small", color = 0xAAAAAAFF);
+        }
+    }
+}
+
+impl<G: GameMode> Scene for PlayScene<G> {
+    fn update(&mut self, app: &mut App) -> Transition {
+        let Some(input) = self.next_input(app) else {
+            return Transition::Pop;
+        };
+
+        self.game.update(&input);
+        for name in self.game.take_sounds() {
+            app.save_data.settings.play_sfx(&name);
+        }
+        app.request_shake(self.game.take_shake());
+        if let Feed::Live(_) = self.feed {
+            self.game.submit_record(&mut app.save_data, &app.p1_name);
+            self.game.save_progress(&mut app.save_data);
+        }
+
+        // Mid-run, leaving goes through the pause menu
+        if !self.game.is_running() && app.live_pad().b.just_pressed() {
+            return Transition::Pop;
+        }
+        Transition::None
+    }
+
+    fn draw(&self, app: &App) {
+        self.game.draw(&app.save_data.settings);
+        self.game.draw_records(&app.save_data);
+        self.draw_playback_badge();
+    }
+
+    // Every live session is kept, however it ends
+    fn exit(&mut self, app: &mut App) {
+        if let Feed::Live(replay) = &self.feed {
+            app.save_data.add_replay(replay.clone());
+        }
+        app.clear_shake();
+    }
+
+    fn resume(&mut self, app: &mut App, choice: Option<PauseItem>) -> Transition {
+        match choice {
+            Some(PauseItem::Restart) => Transition::Replace(self.restarted(app)),
+            Some(PauseItem::Quit) => Transition::Pop,
+            _ => Transition::None,
+        }
+    }
+
+    fn instructions(&self) -> Option<Vec<&'static str>> {
+        Some(self.game.instructions().to_vec())
+    }
+
+    fn can_pause(&self) -> bool {
+        self.game.is_running()
+    }
+}
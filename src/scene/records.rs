@@ -0,0 +1,64 @@
+use turbo::*;
+use crate::model::*;
+use crate::scene::*;
+
+#[turbo::serialize]
+pub struct RecordsScene {
+    pub selection: usize,
+}
+
+impl Default for RecordsScene {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RecordsScene {
+    pub fn new() -> Self {
+        Self { selection: 0 }
+    }
+}
+
+impl Scene for RecordsScene {
+    fn update(&mut self, _app: &mut App) -> Transition {
+        let gp = gamepad::get(0);
+        let count = RecordKind::ALL.len();
+
+        if gp.right.just_pressed() {
+            self.selection = (self.selection + 1) % count;
+        }
+        if gp.left.just_pressed() {
+            self.selection = (self.selection + count - 1) % count;
+        }
+
+        if gp.b.just_pressed() {
+            return Transition::Pop;
+        }
+        Transition::None
+    }
+
+    fn draw(&self, app: &App) {
+        let center_x = |text: &str, font_w: i32| -> i32 { (512 - (text.len() as i32 * font_w)) / 2 };
+
+        let title = "RECORDS";
+        text!(title, x = center_x(title, 8), y = 20, font = "large", color = 0xFFFF00FF);
+
+        let kind = RecordKind::ALL[self.selection];
+        let header = format!("< {} >", kind.title());
+        text!(&header, x = center_x(&header, 8), y = 45, font = "large", color = 0x00FFFFFF);
+
+        app.save_data.records.draw_table(kind, (512 - 140) / 2, 75, None);
+
+        let footer = "Left/Right: Mode | B: Back";
+        text!(footer, x = center_x(footer, 5), y = 260, font = "small", color = 0xAAAAAAFF);
+    }
+
+    fn instructions(&self) -> Option<Vec<&'static str>> {
+        Some(vec![
+            "Records:",
+            "- Left/Right: Switch Game Mode",
+            "- Top 10 per mode (Breaker per difficulty)",
+            "- B: Go Back",
+        ])
+    }
+}
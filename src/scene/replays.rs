@@ -0,0 +1,100 @@
+use turbo::*;
+use crate::model::*;
+use crate::scene::*;
+
+#[turbo::serialize]
+pub struct ReplaysScene {
+    pub selection: usize,
+}
+
+impl Default for ReplaysScene {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReplaysScene {
+    pub fn new() -> Self {
+        Self { selection: 0 }
+    }
+}
+
+impl Scene for ReplaysScene {
+    fn update(&mut self, app: &mut App) -> Transition {
+        let gp = gamepad::get(0);
+        let count = app.save_data.replays.len();
+
+        if count > 0 {
+            if gp.down.just_pressed() {
+                self.selection = (self.selection + 1) % count;
+            }
+            if gp.up.just_pressed() {
+                self.selection = (self.selection + count - 1) % count;
+            }
+            // Newest first on screen
+            let index = count - 1 - self.selection.min(count - 1);
+            if gp.a.just_pressed() || gp.start.just_pressed() {
+                let replay = app.save_data.replays[index].clone();
+                return Transition::Push(watch(replay));
+            }
+            if gp.x.just_pressed() {
+                log!("{}", app.save_data.replays[index].to_text());
+            }
+        }
+
+        if gp.b.just_pressed() {
+            return Transition::Pop;
+        }
+        Transition::None
+    }
+
+    fn draw(&self, app: &App) {
+        let center_x = |text: &str, font_w: i32| -> i32 { (512 - (text.len() as i32 * font_w)) / 2 };
+
+        let title = "REPLAYS";
+        text!(title, x = center_x(title, 8), y = 20, font = "large", color = 0xFFFF00FF);
+
+        let replays = &app.save_data.replays;
+        if replays.is_empty() {
+            let msg = "No replays yet - play a game first!";
+            text!(msg, x = center_x(msg, 5), y = 120, font = "medium", color = 0xAAAAAAFF);
+        }
+
+        for (i, replay) in replays.iter().rev().enumerate() {
+            let y = 60 + i as i32 * 34;
+            let selected = i == self.selection;
+            let border = if selected { 0xFFFF00FF } else { 0x555555FF };
+            rect!(x = 96, y = y, w = 320, h = 28, color = 0x000000CC);
+            rect!(x = 96, y = y, w = 320, h = 28, border_size = 1, border_color = border, color = 0x00000000);
+
+            let col = if selected { 0xFFFF00FF } else { 0xFFFFFFFF };
+            text!(replay.mode.title(), x = 104, y = y + 5, font = "medium", color = col);
+
+            let secs = replay.frame_count() / 60;
+            let length = format!("{}:{:02}", secs / 60, secs % 60);
+            text!(&length, x = 376, y = y + 5, font = "medium", color = col);
+
+            let detail = if replay.mode == ReplayMode::Multiplayer {
+                match replay.series {
+                    SeriesKind::Single => format!("{} | Level {} | Seed {}", replay.names().join(" vs "), replay.level, replay.seed),
+                    series => format!("{} | {} | Seed {}", replay.names().join(" vs "), series.name(), replay.seed),
+                }
+            } else {
+                format!("Seed {}", replay.seed)
+            };
+            text!(&detail, x = 104, y = y + 17, font = "small", color = 0xAAAAAAFF);
+        }
+
+        let footer = "Up/Down: Select | A: Watch | X: Export to Log | B: Back";
+        text!(footer, x = center_x(footer, 5), y = 260, font = "small", color = 0xAAAAAAFF);
+    }
+
+    fn instructions(&self) -> Option<Vec<&'static str>> {
+        Some(vec![
+            "Replays:",
+            "- Last 5 sessions are saved automatically",
+            "- A: Watch | X: Export as text to the log",
+            "- B: Go Back (also stops playback)",
+        ])
+    }
+}
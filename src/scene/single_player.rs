@@ -0,0 +1,299 @@
+use turbo::*;
+use crate::model::*;
+use crate::scene::*;
+
+// Mode select grid
+#[turbo::serialize]
+pub struct SinglePlayerScene {
+    pub mode_selection: u32,
+}
+
+impl Default for SinglePlayerScene {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SinglePlayerScene {
+    pub fn new() -> Self {
+        Self { mode_selection: 0 }
+    }
+}
+
+impl Scene for SinglePlayerScene {
+    fn update(&mut self, _app: &mut App) -> Transition {
+        // Grid Navigation
+        if gamepad::get(0).right.just_pressed() {
+            if self.mode_selection % 2 == 0 { self.mode_selection += 1; }
+        }
+        if gamepad::get(0).left.just_pressed() {
+            if self.mode_selection % 2 != 0 { self.mode_selection -= 1; }
+        }
+        if gamepad::get(0).down.just_pressed() {
+            if self.mode_selection < 4 { self.mode_selection += 2; }
+        }
+        if gamepad::get(0).up.just_pressed() {
+            if self.mode_selection >= 2 { self.mode_selection -= 2; }
+        }
+
+        // Back
+        if gamepad::get(0).b.just_pressed() {
+            return Transition::Pop;
+        }
+        
+        // Select (Launch specific game)
+        if gamepad::get(0).a.just_pressed() || gamepad::get(0).start.just_pressed() {
+             // Gift Packing, Reindeer Training (Sleigh), Santa Breaker, Silent Santa (Stealth), Rooftop Run
+             if self.mode_selection < 5 {
+                 return Transition::Push(SinglePlayerInstructionsScene::new(self.mode_selection).into());
+             }
+             // Other modes not implemented yet
+        }
+        Transition::None
+    }
+
+    fn draw(&self, _app: &App) {
+        // 1. Draw Big Title (Same as Menu)
+        let scale = 3; 
+        let title_pixel_width = ((5 + 7) * 6 + 4) * scale;
+        let start_x_title = (512 - title_pixel_width as i32) / 2;
+        draw_title(start_x_title, 20, scale as u32);
+
+        // 2. Sub-header
+        text!("SELECT MODE", x = 210, y = 60, color = 0xFFFF00FF);
+
+        // 3. Instructions
+        text!("Press X to Back", x = 20, y = 20, color = 0xAAAAAAFF);
+
+        let modes = [
+        "Gift Packing", "Raindeer Rush",
+        "Santa Breaker", "Santa Mission",
+        "Rooftop Run", "Snow Chaos"
+    ];
+    
+    let icons = ["🎁", "🦌", "🧱", "🕵️", "🏠", "❄️"];
+
+        // 4. Grid Layout (Squares)
+        let box_size = 60;
+        let gap_x = 40;
+        let _gap_y = 50; // Unused but kept for reference logic
+        
+        // Calculate starting position to center the 2x3 grid
+        // Grid Width = 2 * box + gap = 60*2 + 40 = 160
+        // Grid Height = 3 * box + 2 * gap = 60*3 + 50*2 = 180 + 100 = 280 (Too tall for 288 screen)
+        // Let's adjust gaps and start Y
+        
+        let start_x = (512 - (box_size * 2 + gap_x)) / 2;
+        let start_y = 90;
+        let gap_y_adjusted = 40;
+
+        for i in 0..6 {
+            let row = (i / 2) as i32;
+            let col = (i % 2) as i32;
+            let x = start_x + col * (box_size + gap_x);
+            let y = start_y + row * (box_size + gap_y_adjusted);
+            
+            let is_selected = self.mode_selection == i as u32;
+            let border_color = if is_selected { 0x00FF00FF } else { 0xFFFFFFFF }; // Green if selected
+            let bg_color = if is_selected { 0x222222FF } else { 0x000000FF };
+
+            // Draw Square Box (Thumbnail Container)
+            rect!(x = x - 2, y = y - 2, w = (box_size + 4) as u32, h = (box_size + 4) as u32, color = border_color);
+            rect!(x = x, y = y, w = box_size as u32, h = box_size as u32, color = bg_color);
+            
+            // Draw Icon (Centered in box)
+            if i < 5 {
+                 let sprite_name = match i {
+                     0 => "thumb_factory",
+                     1 => "thumb_sleigh",
+                     2 => "thumb_breaker",
+                     3 => "thumb_stealth",
+                     4 => "thumb_rooftop",
+                     _ => "",
+                 };
+                 // Draw sprite fitted to box (assuming sprite! supports w/h resizing or we rely on default)
+                 // If w/h not supported, this might fail or draw huge.
+                 // Safe bet: use `sprite!(name, x=x, y=y)` and hope for best? No, 1024px is huge.
+                 // Use `w` and `h` parameters if `sprite!` macro follows `rect!` convention.
+                 sprite!(sprite_name, x=x, y=y, w=box_size as u32, h=box_size as u32);
+            } else {
+                 text!(icons[i], x = x + 20, y = y + 20, font = "large", color = 0xFFFFFFFF);
+            }
+            
+            // Draw Mode Name (Below Box)
+            // Center the text below the box
+            let name = modes[i];
+            let name_w = name.len() as i32 * 6; // Estimating medium font width (approx 6px)
+            let text_x = x + (box_size as i32 / 2) - (name_w / 2);
+            
+            // Highlight selected text color
+            let text_color = if is_selected { 0x00FF00FF } else { 0xAAAAAAFF };
+            text!(name, x = text_x, y = y + box_size + 10, font = "medium", color = text_color);
+        }
+    }
+
+    fn instructions(&self) -> Option<Vec<&'static str>> {
+        Some(vec![
+            "Single Player Menu:",
+            "- Arrow Keys: Select Game Mode",
+            "- A / Start: Play Selected Game",
+            "- B: Go Back",
+        ])
+    }
+}
+
+// Rules and controls for the chosen mode; starting replaces it with the game
+#[turbo::serialize]
+pub struct SinglePlayerInstructionsScene {
+    pub mode_selection: u32,
+}
+
+impl SinglePlayerInstructionsScene {
+    pub fn new(mode_selection: u32) -> Self {
+        Self { mode_selection }
+    }
+}
+
+impl Scene for SinglePlayerInstructionsScene {
+    fn update(&mut self, app: &mut App) -> Transition {
+        let gp = gamepad::get(0);
+        let kb = turbo::keyboard::get();
+        
+        // SPACE or START to Start Game
+        if gp.start.just_pressed() || gp.a.just_pressed() || kb.space().just_pressed() {
+            // Launch specific game based on selection
+            let mode = match self.mode_selection {
+                0 => ReplayMode::GiftPacking,
+                1 => ReplayMode::RaindeerRush,
+                2 => ReplayMode::Breaker,
+                3 => ReplayMode::SilentSanta,
+                _ => ReplayMode::RooftopRun,
+            };
+            let mut replay = Replay::new(mode, app.rng.u32(), app.live_bits());
+            replay.breaker_unlocked = app.save_data.breaker_unlocked;
+            return Transition::Replace(launch(replay));
+        }
+        
+        // B to Go Back to Single Player Menu
+        if gp.b.just_pressed() || kb.escape().just_pressed() {
+            return Transition::Pop;
+        }
+        Transition::None
+    }
+
+    fn draw(&self, app: &App) {
+        let center_x = |text: &str, font_w: i32| -> i32 { (512 - (text.len() as i32 * font_w)) / 2 };
+        
+        let title;
+        let overview;
+        let mut lines = vec![];
+        let controls;
+        let win_cond;
+        let lose_cond;
+        
+        if self.mode_selection == 0 {
+             title = "GIFT PACKING";
+             overview = "Help Santa sort gifts correctly!";
+             lines.push("Gifts spawn on the top belt.");
+             lines.push("Pick up gifts (Blue, Green, Purple).");
+             lines.push("Drop them into the matching colored Bin.");
+             lines.push("+100 Points for Correct Bin.");
+             lines.push("-50 Points for Wrong Bin.");
+             controls = "Move: Arrows | Action: A (Z) | Pause: Space";
+             win_cond = "Score as high as possible in 60s!";
+             lose_cond = "Time runs out.";
+        } else if self.mode_selection == 1 {
+             title = "RAINDEER RUSH";
+             overview = "Fly the sleigh and defend spread joy!";
+             lines.push("Fly Santa's Sleigh through the sky.");
+             lines.push("Shoot magic at flying enemies.");
+             lines.push("Avoid crashing into enemies.");
+             lines.push("Survive as difficulty increases.");
+             controls = "Move: Arrows | Shoot: A (Z) | Pause: Space";
+             win_cond = "Survive longer for high score!";
+             lose_cond = "Running out of Lives.";
+        } else if self.mode_selection == 2 {
+             title = "SANTA BREAKER";
+             overview = "Break all bricks to clear levels!";
+             lines.push("Bounce the Santa Ball off the paddle.");
+             lines.push("Destroy all festive bricks.");
+             lines.push("Don't let the ball fall!");
+             lines.push("Advance through multiple levels.");
+             controls = "Move: Left/Right | Launch: A (Z) | Pause: Space";
+             win_cond = "Clear all bricks.";
+             lose_cond = "Lose all lives (Ball drops).";
+        } else if self.mode_selection == 3 {
+             title = "SANTA MISSION";
+             overview = "Deliver gifts without being seen!";
+             lines.push("Sneak past sleeping dogs and patrol wolves.");
+             lines.push("Place 3 Gifts at targets.");
+             lines.push("Eat the Cookie in the Kitchen.");
+             lines.push("Collect 5 Stars.");
+             controls = "Move: Arrows | Interact: A (Z) | Stop: Quiet Down";
+             win_cond = "Complete all tasks & Exit.";
+             lose_cond = "Getting CAUGHT by a dog.";
+        } else if self.mode_selection == 4 {
+             title = "ROOFTOP RUN";
+             overview = "Race across the snowy rooftops!";
+             lines.push("Jump the gaps between houses.");
+             lines.push("Grab gifts (+100) to fill your sack.");
+             lines.push("Land on a chimney to deliver them (+200 each).");
+             lines.push("Reach the flag to clear the level.");
+             controls = "Move: Left/Right | Jump: A (Z) / Up | Pause: Space";
+             win_cond = "Go as far as you can for high score!";
+             lose_cond = "Falling or touching icicles 3 times.";
+        } else {
+             title = "UNKNOWN MODE";
+             overview = "";
+             controls = "";
+             win_cond = "";
+             lose_cond = "";
+        }
+        
+        // Draw
+        
+        // Background Snow
+        for flake in &app.snow {
+             // Use SnowFlake::draw OR manually. SnowFlake has .size field, not .r
+             let sz = flake.size;
+             rect!(x=flake.x as i32, y=flake.y as i32, w=sz, h=sz, color=0xFFFFFF66);
+        }
+        
+        text!(title, x = center_x(title, 8), y = 15, font = "large", color = 0xFFFF00FF);
+        text!(overview, x = center_x(overview, 5), y = 40, font = "medium", color = 0x00FFFFFF);
+        
+        let start_y = 65;
+        let gap = 15;
+        let x_left = 60;
+        
+        // Gameplay Lines
+        lines.insert(0, "How to Play:");
+        for (i, line) in lines.iter().enumerate() {
+             let iter_line: &str = line;
+             let col = if iter_line.ends_with(':') { 0xFFD700FF } else { 0xFFFFFFFF };
+             text!(iter_line, x=x_left, y=start_y + (i as i32 * gap), font="medium", color=col);
+        }
+        
+        let mut y = start_y + (lines.len() as i32) * gap + 5;
+        
+        text!("Controls:", x=x_left, y=y, font="medium", color=0xFFD700FF);
+        y += gap;
+        text!(controls, x=x_left, y=y, font="medium", color=0xFFFFFFFF);
+        y += gap + 5;
+
+        text!("Win Condition:", x=x_left, y=y, font="medium", color=0xFFD700FF);
+        y += gap;
+        text!(win_cond, x=x_left, y=y, font="medium", color=0x2ECC71FF);
+        y += gap + 5;
+
+        text!("Lose Condition:", x=x_left, y=y, font="medium", color=0xFFD700FF);
+        y += gap;
+        text!(lose_cond, x=x_left, y=y, font="medium", color=0xE74C3CFF);
+        
+        // Footer (Fixed at bottom)
+        let footer1 = "Press SPACE to START GAME";
+        let footer2 = "Press B / ESC to Go Back";
+        text!(footer1, x=center_x(footer1, 5), y=250, font="medium", color=0x00FF00FF);
+        text!(footer2, x=center_x(footer2, 5), y=270, font="small", color=0xAAAAAAFF);
+    }
+}
@@ -0,0 +1,194 @@
+use crate::model::*;
+use crate::scene::*;
+
+// Input is ignored for this many frames after any change of scene
+const TRANSITION_FRAMES: u32 = 10;
+
+// Instructions overlay text for scenes that don't provide their own
+pub const STANDARD_CONTROLS: [&str; 4] = [
+    "Standard Controls:",
+    "- Arrows: Move / Navigate",
+    "- A / Start: Confirm / Action",
+    "- B: Cancel / Back",
+];
+
+// What a scene asks the stack to do after its update
+pub enum Transition {
+    None,
+    Push(AnyScene),
+    // Back to the scene below, which gets `resume(None)`
+    Pop,
+    // Pops the pause menu and hands its choice to the scene below
+    Return(PauseItem),
+    Replace(AnyScene),
+}
+
+// A screen, game or overlay. Only the top of the stack updates; drawing
+// starts from the top-most scene that is not an overlay.
+pub trait Scene {
+    // Called once when the scene joins the stack
+    fn enter(&mut self, _app: &mut App) {}
+
+    fn update(&mut self, app: &mut App) -> Transition;
+
+    fn draw(&self, app: &App);
+
+    // Called once when the scene leaves the stack (popped or replaced)
+    fn exit(&mut self, _app: &mut App) {}
+
+    // The scene above was popped; `choice` is set when that was the pause menu
+    fn resume(&mut self, _app: &mut App, _choice: Option<PauseItem>) -> Transition {
+        Transition::None
+    }
+
+    // Lines for the instructions overlay, or None to not offer it right now
+    fn instructions(&self) -> Option<Vec<&'static str>> {
+        Some(STANDARD_CONTROLS.to_vec())
+    }
+
+    // Start / Esc opens the pause menu over this scene
+    fn can_pause(&self) -> bool {
+        false
+    }
+
+    // Drawn on top of the scene below instead of replacing it
+    fn is_overlay(&self) -> bool {
+        false
+    }
+}
+
+// Every scene type in one list. The game state is serialized between frames,
+// so the stack holds this enum rather than trait objects; a new screen or
+// game mode only needs a line here.
+macro_rules! scenes {
+    ($($name:ident($ty:ty)),* $(,)?) => {
+        #[turbo::serialize]
+        pub enum AnyScene {
+            $($name($ty),)*
+        }
+
+        impl AnyScene {
+            pub fn scene(&self) -> &dyn Scene {
+                match self {
+                    $(AnyScene::$name(scene) => scene,)*
+                }
+            }
+
+            pub fn scene_mut(&mut self) -> &mut dyn Scene {
+                match self {
+                    $(AnyScene::$name(scene) => scene,)*
+                }
+            }
+        }
+
+        $(impl From<$ty> for AnyScene {
+            fn from(scene: $ty) -> Self {
+                AnyScene::$name(scene)
+            }
+        })*
+    };
+}
+
+scenes! {
+    Menu(MenuScene),
+    SinglePlayer(SinglePlayerScene),
+    SinglePlayerInstructions(SinglePlayerInstructionsScene),
+    MultiplayerLevelSelect(LevelSelectScene),
+    MultiplayerSetup(MultiplayerSetupScene),
+    MultiplayerJoin(MultiplayerJoinScene),
+    MultiplayerInstructions(MultiplayerInstructionsScene),
+    ArenaEditor(ArenaEditorScene),
+    OnlineLobby(OnlineLobbyScene),
+    OnlineRoom(OnlineRoomScene),
+    GiftPacking(PlayScene<FactoryGame>),
+    RaindeerRush(PlayScene<SleighGame>),
+    Breaker(PlayScene<BreakerGame>),
+    SilentSanta(PlayScene<StealthGame>),
+    RooftopRun(PlayScene<RooftopGame>),
+    Multiplayer(PlayScene<MultiplayerGame>),
+    Records(RecordsScene),
+    Replays(ReplaysScene),
+    Options(OptionsScene),
+    Developer(DeveloperScene),
+    Pause(PauseScene),
+    Instructions(InstructionsScene),
+}
+
+#[turbo::serialize]
+pub struct SceneStack {
+    scenes: Vec<AnyScene>,
+    transition_timer: u32,
+}
+
+impl SceneStack {
+    pub fn new(root: AnyScene) -> Self {
+        Self { scenes: vec![root], transition_timer: 0 }
+    }
+
+    pub fn update(&mut self, app: &mut App) {
+        if self.transition_timer > 0 {
+            self.transition_timer -= 1;
+            return;
+        }
+        let Some(top) = self.scenes.last_mut() else { return };
+        let top = top.scene_mut();
+
+        // Select / Y opens the instructions for whatever is on top,
+        // and Start / Esc pauses anything that allows it
+        let gp = app.live_pad();
+        let pause = gp.start.just_pressed() || turbo::keyboard::get().escape().just_pressed();
+        let transition = if (gp.select.just_pressed() || gp.y.just_pressed())
+            && let Some(lines) = top.instructions()
+        {
+            Transition::Push(InstructionsScene::new(&lines).into())
+        } else if top.can_pause() && pause {
+            Transition::Push(PauseScene::new().into())
+        } else {
+            top.update(app)
+        };
+        self.apply(app, transition);
+    }
+
+    pub fn draw(&self, app: &App) {
+        let base = self.scenes.iter().rposition(|s| !s.scene().is_overlay()).unwrap_or(0);
+        for scene in &self.scenes[base..] {
+            scene.scene().draw(app);
+        }
+    }
+
+    fn apply(&mut self, app: &mut App, transition: Transition) {
+        match transition {
+            Transition::None => return,
+            Transition::Push(scene) => self.push(app, scene),
+            Transition::Pop => self.pop_and_resume(app, None),
+            Transition::Return(choice) => self.pop_and_resume(app, Some(choice)),
+            Transition::Replace(scene) => {
+                self.pop(app);
+                self.push(app, scene);
+            },
+        }
+        self.transition_timer = TRANSITION_FRAMES;
+    }
+
+    fn push(&mut self, app: &mut App, mut scene: AnyScene) {
+        scene.scene_mut().enter(app);
+        self.scenes.push(scene);
+    }
+
+    fn pop(&mut self, app: &mut App) {
+        if let Some(mut scene) = self.scenes.pop() {
+            scene.scene_mut().exit(app);
+        }
+    }
+
+    fn pop_and_resume(&mut self, app: &mut App, choice: Option<PauseItem>) {
+        // The root (main menu) never leaves
+        if self.scenes.len() > 1 {
+            self.pop(app);
+        }
+        if let Some(top) = self.scenes.last_mut() {
+            let next = top.scene_mut().resume(app, choice);
+            self.apply(app, next);
+        }
+    }
+}